@@ -0,0 +1,17 @@
+-- Wallet Authentication
+-- Mutating requests must prove control of the wallet they act as. The server issues a single-use nonce
+-- per wallet, the wallet signs it as SNIP-12 typed data, and the nonce is consumed the first time a
+-- request presents it, so a captured signature cannot be replayed.
+create table auth_challenges (
+    nonce varchar(66) primary key,
+    wallet_address varchar(66) not null check (wallet_address ~ '^0x[a-fA-F0-9]{64}$'),
+    expires_at timestamptz not null,
+    consumed_at timestamptz,
+    created_at timestamptz not null default now()
+);
+
+create index idx_auth_challenges_wallet_address on auth_challenges (wallet_address);
+
+comment on column auth_challenges.nonce is 'Random felt the wallet signs as part of the SNIP-12 Authentication message.';
+comment on column auth_challenges.expires_at is 'The nonce cannot be used after this timestamp.';
+comment on column auth_challenges.consumed_at is 'Timestamp when a request successfully presented this nonce. NULL while unused.';
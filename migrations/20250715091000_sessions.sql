@@ -0,0 +1,23 @@
+-- Sessions
+-- After a wallet signs a challenge it receives a short-lived access token and a long-lived refresh token,
+-- so it does not have to sign every request. Only SHA-256 hashes of the tokens are stored. Refreshing
+-- rotates both tokens, and revoking a session (logout) makes both unusable immediately.
+create table sessions (
+    id uuid primary key default uuid_generate_v1mc(),
+    wallet_address varchar(66) not null check (wallet_address ~ '^0x[a-fA-F0-9]{64}$'),
+    access_token_hash varchar(64) not null unique,
+    access_token_expires_at timestamptz not null,
+    refresh_token_hash varchar(64) not null unique,
+    refresh_token_expires_at timestamptz not null,
+    revoked_at timestamptz,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz
+);
+
+select trigger_updated_at('sessions');
+
+create index idx_sessions_wallet_address on sessions (wallet_address);
+
+comment on column sessions.access_token_hash is 'Hex encoded SHA-256 of the access token sent as `Authorization: Token <token>`.';
+comment on column sessions.refresh_token_hash is 'Hex encoded SHA-256 of the refresh token. Replaced every time the session is refreshed.';
+comment on column sessions.revoked_at is 'Timestamp when the session was logged out. NULL while the session is usable.';
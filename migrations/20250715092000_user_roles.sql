@@ -0,0 +1,38 @@
+-- User Roles
+-- A wallet can hold several roles at once (e.g. a researcher who also owns a project), so roles move out
+-- of the single `escrow_users.type` column into their own table. `user` stays the implicit role of every
+-- wallet and is never stored. Admins grant and revoke roles, and every change is kept in `role_changes`.
+alter type user_type add value if not exists 'validator';
+alter type user_type add value if not exists 'researcher';
+alter type user_type add value if not exists 'project_owner';
+
+create table user_roles (
+    wallet_address varchar(66) not null references escrow_users(wallet_address) on delete cascade,
+    role user_type not null check (role <> 'user'),
+    granted_by varchar(66) check (granted_by ~ '^0x[a-fA-F0-9]{64}$'),
+    created_at timestamptz not null default now(),
+    primary key (wallet_address, role)
+);
+
+insert into user_roles (wallet_address, role)
+select wallet_address, type from escrow_users where type <> 'user';
+
+alter table escrow_users drop column type;
+
+create type role_change_action as enum ('granted', 'revoked');
+
+create table role_changes (
+    id uuid primary key default uuid_generate_v1mc(),
+    wallet_address varchar(66) not null check (wallet_address ~ '^0x[a-fA-F0-9]{64}$'),
+    role user_type not null,
+    action role_change_action not null,
+    changed_by varchar(66) not null check (changed_by ~ '^0x[a-fA-F0-9]{64}$'),
+    reason text check (length(reason) <= 500),
+    created_at timestamptz not null default now()
+);
+
+create index idx_role_changes_wallet_address on role_changes (wallet_address, created_at);
+
+comment on column user_roles.granted_by is 'The admin who granted the role. NULL for roles migrated from escrow_users.type.';
+comment on column role_changes.changed_by is 'The admin wallet that granted or revoked the role.';
+comment on column role_changes.reason is 'Optional free text explaining the change, max 500 characters.';
@@ -0,0 +1,27 @@
+-- Report History
+-- Every status change of a research report is recorded here, so the full timeline of a report (who moved it,
+-- from which state, and when) can be reconstructed. Rows are only ever inserted.
+create table report_history (
+    id uuid primary key default uuid_generate_v1mc(),
+    report_id uuid not null references research_report(id) on delete cascade,
+    from_status report_status_type,
+    to_status report_status_type not null,
+    changed_by varchar(66) not null check (changed_by ~ '^0x[a-fA-F0-9]{64}$'),
+    notes text check (length(notes) <= 1000),
+    created_at timestamptz not null default now()
+);
+
+create index idx_report_history_report_id on report_history (report_id, created_at);
+
+comment on column report_history.from_status is 'Status before the change. NULL for the submission that created the report.';
+comment on column report_history.changed_by is 'The wallet that performed the transition.';
+
+-- Backfill the timeline of reports that existed before history was recorded.
+insert into report_history (report_id, from_status, to_status, changed_by, created_at)
+select id, null, 'submitted', reported_by, coalesce(created_at, now())
+from research_report;
+
+insert into report_history (report_id, from_status, to_status, changed_by, created_at)
+select id, 'submitted', status, coalesce(validated_by, reported_by), coalesce(updated_at, created_at, now())
+from research_report
+where status <> 'submitted';
@@ -0,0 +1,22 @@
+-- Project Rewards
+-- Project owners define how much a finding of each severity pays out. When a validator accepts a report,
+-- the payout for its severity becomes the report's `allocated_reward`, capped by what is left of the
+-- project's bounty.
+create table project_rewards (
+    project_id uuid not null references projects(id) on delete cascade,
+    severity severity_level not null,
+    amount numeric(20, 2) not null check (amount >= 0),
+    created_at timestamptz not null default now(),
+    updated_at timestamptz,
+    primary key (project_id, severity)
+);
+
+select trigger_updated_at('project_rewards');
+
+comment on column project_rewards.amount is 'Payout for an accepted report of this severity, in the project''s bounty currency.';
+
+-- A closed report keeps the reward it was accepted with.
+alter table research_report drop constraint ck_research_award;
+alter table research_report add constraint ck_research_award check (
+    (status in ('accepted', 'closed') and severity is not null) or allocated_reward is null
+);
@@ -0,0 +1,60 @@
+-- Escrow Balances
+-- A wallet holds a separate balance for every currency it has funds in, so a USDC deposit can no longer be
+-- withdrawn or allocated as STRK. `escrow_users` stays the registry of escrow accounts.
+create table escrow_balances (
+    wallet_address varchar(66) not null references escrow_users(wallet_address) on delete restrict,
+    currency varchar(10) not null,
+    balance numeric(30, 2) not null default 0.0 check (balance >= 0),
+    created_at timestamptz not null default now(),
+    updated_at timestamptz,
+    primary key (wallet_address, currency)
+);
+
+select trigger_updated_at('escrow_balances');
+
+comment on column escrow_balances.balance is 'The wallet''s available balance in this currency on the platform''s escrow.';
+
+-- Split existing balances by currency. Where the completed transactions of a wallet add up to its balance,
+-- their per-currency totals are used; otherwise the whole balance goes to the currency the wallet last
+-- deposited in (STRK if it never deposited).
+create temporary table currency_totals on commit drop as
+select
+    wallet_address,
+    currency,
+    sum(case when type in ('deposit', 'bounty_disbursement') then amount else -amount end) as total
+from escrow_transactions
+where status = 'completed'
+group by wallet_address, currency;
+
+create temporary table reconciled_wallets on commit drop as
+select u.wallet_address
+from escrow_users u
+join currency_totals t using (wallet_address)
+group by u.wallet_address, u.balance
+having sum(t.total) = u.balance and min(t.total) >= 0;
+
+insert into escrow_balances (wallet_address, currency, balance)
+select t.wallet_address, t.currency, t.total
+from currency_totals t
+join reconciled_wallets r using (wallet_address)
+where t.total > 0;
+
+insert into escrow_balances (wallet_address, currency, balance)
+select
+    u.wallet_address,
+    coalesce(
+        (
+            select t.currency
+            from escrow_transactions t
+            where t.wallet_address = u.wallet_address and t.type = 'deposit'
+            order by t.created_at desc
+            limit 1
+        ),
+        'STRK'
+    ),
+    u.balance
+from escrow_users u
+where u.balance > 0
+  and u.wallet_address not in (select wallet_address from reconciled_wallets);
+
+alter table escrow_users drop column balance;
@@ -0,0 +1,129 @@
+-- Ledger
+-- Every movement of funds through the escrow is recorded as a journal entry made of postings. A posting adds
+-- a signed amount to one account: positive amounts increase the account's balance, negative amounts decrease
+-- it. The postings of an entry always sum to zero, so money only ever moves between accounts and the whole
+-- ledger sums to zero in every currency.
+--
+-- Accounts are
+--  * `user`:         a wallet's escrow balance in a currency (mirrors `escrow_balances`),
+--  * `project_pool`: the bounty held for a project (mirrors `projects.bounty_amount`),
+--  * `platform`:     the platform's custody of on-chain funds, the counterpart of deposits and withdrawals.
+--    Its balance is the negative of everything the escrow owes to users and projects.
+create type ledger_account_kind as enum ('user', 'project_pool', 'platform');
+
+create table ledger_accounts (
+    id uuid primary key default uuid_generate_v1mc(),
+    kind ledger_account_kind not null,
+    wallet_address varchar(66) check (wallet_address ~ '^0x[a-fA-F0-9]{64}$'),
+    project_id uuid references projects(id) on delete restrict,
+    currency varchar(10) not null,
+    created_at timestamptz not null default now(),
+
+    constraint ck_ledger_accounts_owner check (
+        (kind = 'user' and wallet_address is not null and project_id is null) or
+        (kind = 'project_pool' and project_id is not null and wallet_address is null) or
+        (kind = 'platform' and wallet_address is null and project_id is null)
+    ),
+    constraint uq_ledger_accounts unique nulls not distinct (kind, wallet_address, project_id, currency)
+);
+
+create table journal_entries (
+    id uuid primary key default uuid_generate_v1mc(),
+    transaction_id uuid references escrow_transactions(id) on delete restrict,
+    description text not null,
+    created_at timestamptz not null default now()
+);
+
+create table ledger_postings (
+    id uuid primary key default uuid_generate_v1mc(),
+    journal_entry_id uuid not null references journal_entries(id) on delete restrict,
+    account_id uuid not null references ledger_accounts(id) on delete restrict,
+    amount numeric(30, 2) not null check (amount <> 0),
+    created_at timestamptz not null default now()
+);
+
+create index idx_ledger_postings_journal_entry_id on ledger_postings (journal_entry_id);
+create index idx_ledger_postings_account_id on ledger_postings (account_id);
+
+comment on column journal_entries.transaction_id is 'The escrow transaction this entry books, if any.';
+comment on column ledger_postings.amount is 'Signed amount added to the account. The postings of a journal entry sum to zero.';
+
+-- The ledger is append-only.
+create or replace function reject_ledger_change()
+    returns trigger as
+$$
+begin
+    raise exception '% is append-only', TG_TABLE_NAME;
+end;
+$$ language plpgsql;
+
+create trigger journal_entries_append_only
+    before update or delete on journal_entries
+    for each row execute function reject_ledger_change();
+
+create trigger ledger_postings_append_only
+    before update or delete on ledger_postings
+    for each row execute function reject_ledger_change();
+
+-- An entry must balance, per currency, by the time its transaction commits.
+create or replace function check_journal_entry_balanced()
+    returns trigger as
+$$
+begin
+    if exists (
+        select 1
+        from ledger_postings p
+        join ledger_accounts a on a.id = p.account_id
+        where p.journal_entry_id = NEW.journal_entry_id
+        group by a.currency
+        having sum(p.amount) <> 0
+    ) then
+        raise exception 'journal entry % does not balance', NEW.journal_entry_id;
+    end if;
+    return null;
+end;
+$$ language plpgsql;
+
+create constraint trigger ledger_postings_balanced
+    after insert on ledger_postings
+    deferrable initially deferred
+    for each row execute function check_journal_entry_balanced();
+
+-- Closing a project used to refund the remaining bounty without emptying the pool.
+update projects set bounty_amount = 0 where closed_at is not null and bounty_amount > 0;
+
+-- Open the ledger with the balances and bounties that exist today, funded from the platform account.
+insert into ledger_accounts (kind, currency)
+select distinct 'platform'::ledger_account_kind, currency
+from (
+    select currency from escrow_balances where balance > 0
+    union
+    select bounty_currency from projects where bounty_amount > 0
+) currencies;
+
+insert into ledger_accounts (kind, wallet_address, currency)
+select 'user', wallet_address, currency from escrow_balances where balance > 0;
+
+insert into ledger_accounts (kind, project_id, currency)
+select 'project_pool', id, bounty_currency from projects where bounty_amount > 0;
+
+create temporary table opening_balances on commit drop as
+select uuid_generate_v1mc() as journal_entry_id, a.id as account_id, a.currency, b.balance as amount
+from escrow_balances b
+join ledger_accounts a on a.kind = 'user' and a.wallet_address = b.wallet_address and a.currency = b.currency
+where b.balance > 0
+union all
+select uuid_generate_v1mc(), a.id, a.currency, p.bounty_amount
+from projects p
+join ledger_accounts a on a.kind = 'project_pool' and a.project_id = p.id
+where p.bounty_amount > 0;
+
+insert into journal_entries (id, description)
+select journal_entry_id, 'Opening balance' from opening_balances;
+
+insert into ledger_postings (journal_entry_id, account_id, amount)
+select journal_entry_id, account_id, amount from opening_balances
+union all
+select o.journal_entry_id, a.id, -o.amount
+from opening_balances o
+join ledger_accounts a on a.kind = 'platform' and a.currency = o.currency;
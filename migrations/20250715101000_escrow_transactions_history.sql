@@ -0,0 +1,3 @@
+-- Transaction history is read per wallet, newest first, paginated on (created_at, id).
+create index idx_escrow_transactions_wallet_history on escrow_transactions (wallet_address, created_at, id);
+create index idx_escrow_transactions_history on escrow_transactions (created_at, id);
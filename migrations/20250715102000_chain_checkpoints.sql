@@ -0,0 +1,20 @@
+-- Chain Watcher
+-- Blocks of the escrow contract the chain watcher has processed. It resumes after the highest one; the
+-- earlier ones let it find where the chain diverged after a reorg. Only a recent window is kept.
+create table chain_checkpoints (
+    contract_address varchar(66) not null,
+    block_number bigint not null check (block_number >= 0),
+    block_hash varchar(66) not null,
+    created_at timestamptz not null default now(),
+    primary key (contract_address, block_number)
+);
+
+-- Deposits ingested from contract events remember the block they were seen in, so a reorg can undo them.
+alter table escrow_transactions
+    add column block_number bigint,
+    add column block_hash varchar(66);
+
+create index idx_escrow_transactions_block on escrow_transactions (block_number) where block_number is not null;
+
+comment on column escrow_transactions.block_number is 'Block the transaction was observed in by the chain watcher, if it was.';
+comment on column escrow_transactions.block_hash is 'Hash of that block.';
@@ -0,0 +1,28 @@
+-- Withdrawal Payouts
+-- Withdrawals are paid out by the platform's payout worker, which records the hash of the transfer it submits.
+-- Until then a withdrawal is pending without a hash; every other transaction still carries one.
+alter table escrow_transactions
+    alter column transaction_hash drop not null,
+    add constraint ck_escrow_transactions_hash check (
+        transaction_hash is not null or (type = 'withdrawal' and status <> 'completed')
+    ),
+    add column payout_attempts integer not null default 0 check (payout_attempts >= 0),
+    add column payout_next_attempt_at timestamptz,
+    add column payout_error text;
+
+create index idx_escrow_transactions_payout_queue on escrow_transactions (created_at, id)
+    where type = 'withdrawal' and status = 'pending' and transaction_hash is null;
+
+comment on column escrow_transactions.payout_attempts is 'Times the payout worker tried to submit this withdrawal.';
+comment on column escrow_transactions.payout_next_attempt_at is 'Earliest time the payout worker retries a rejected submission.';
+comment on column escrow_transactions.payout_error is 'Why the last submission of this withdrawal was rejected.';
+
+-- Kill switches for background jobs, flipped by admins at runtime.
+create table job_controls (
+    job varchar(50) primary key,
+    paused boolean not null default false,
+    updated_by varchar(66) check (updated_by ~ '^0x[a-fA-F0-9]{64}$'),
+    updated_at timestamptz
+);
+
+insert into job_controls (job) values ('payouts');
@@ -0,0 +1,20 @@
+-- Idempotency keys
+-- Clients send an `Idempotency-Key` header with requests that move money, so a request retried after
+-- a timeout is not executed twice. The first request under a key stores a fingerprint of itself and,
+-- once it finished, its response; later requests under the same key get that response replayed.
+create table idempotency_keys (
+    wallet_address varchar(66) not null check (wallet_address ~ '^0x[a-fA-F0-9]{64}$'),
+    idempotency_key varchar(255) not null,
+    request_hash varchar(64) not null,
+    response_status smallint,
+    response_content_type text,
+    response_body bytea,
+    created_at timestamptz not null default now(),
+    completed_at timestamptz,
+    primary key (wallet_address, idempotency_key)
+);
+
+create index idx_idempotency_keys_created_at on idempotency_keys (created_at);
+
+comment on column idempotency_keys.request_hash is 'Hex encoded SHA-256 of the method, path and body of the first request sent under the key.';
+comment on column idempotency_keys.response_status is 'Status of the stored response. NULL while the first request is still being processed.';
@@ -0,0 +1,28 @@
+-- Withdrawal Approvals
+-- Withdrawals above the configured threshold of their currency need sign-off from several admins before the
+-- payout worker sends them. Their funds stay reserved while they wait; a single admin can reject them, which
+-- releases the funds again.
+create type withdrawal_request_status as enum ('pending', 'approved', 'rejected');
+
+create table withdrawal_requests (
+    id uuid primary key default uuid_generate_v1mc(),
+    transaction_id uuid not null unique references escrow_transactions(id) on delete cascade,
+    approvals_required integer not null check (approvals_required > 0),
+    status withdrawal_request_status not null default 'pending',
+    rejected_by varchar(66) check (rejected_by ~ '^0x[a-fA-F0-9]{64}$'),
+    rejection_reason text,
+    created_at timestamptz not null default now(),
+    decided_at timestamptz
+);
+
+create index idx_withdrawal_requests_pending on withdrawal_requests (created_at) where status = 'pending';
+
+create table withdrawal_approvals (
+    withdrawal_request_id uuid not null references withdrawal_requests(id) on delete cascade,
+    admin_address varchar(66) not null check (admin_address ~ '^0x[a-fA-F0-9]{64}$'),
+    approved_at timestamptz not null default now(),
+    primary key (withdrawal_request_id, admin_address)
+);
+
+comment on column withdrawal_requests.approvals_required is 'Distinct admins that must approve, fixed when the withdrawal is requested.';
+comment on column withdrawal_requests.decided_at is 'When the last required approval or the rejection came in.';
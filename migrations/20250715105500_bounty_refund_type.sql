@@ -0,0 +1,4 @@
+-- Bounty Refunds
+-- Funds returned from a project's bounty to its owner are recorded as escrow transactions of their own. The new
+-- value can only be used once this migration is committed, see `20250715110000_reconciliation.sql`.
+alter type transaction_type add value 'bounty_refund';
@@ -0,0 +1,69 @@
+-- Reconciliation
+-- A periodic job recomputes every escrow balance and project bounty from `escrow_transactions` and records
+-- where they disagree. Balances only ever change together with a transaction, so any mismatch points to a
+-- bug or a manual edit.
+alter table escrow_transactions
+    drop constraint ck_escrow_transactions_project_required_check,
+    add constraint ck_escrow_transactions_project_required_check check (
+        (type in ('bounty_allocation', 'bounty_disbursement', 'bounty_refund') and project_id is not null) or
+        (type in ('deposit', 'withdrawal') and project_id is null)
+    );
+
+comment on column escrow_transactions.type is 'Type of transaction: deposit, withdrawal, bounty_allocation, bounty_disbursement or bounty_refund.';
+
+-- Refunds made on project close before they were recorded as transactions, recovered from the ledger.
+insert into escrow_transactions (
+    wallet_address, project_id, type, amount, currency, transaction_hash, status, notes, created_at
+)
+select
+    owner.wallet_address,
+    pool.project_id,
+    'bounty_refund',
+    credit.amount,
+    owner.currency,
+    'refund_' || replace(e.id::text, '-', ''),
+    'completed',
+    e.description,
+    e.created_at
+from journal_entries e
+join ledger_postings credit on credit.journal_entry_id = e.id and credit.amount > 0
+join ledger_accounts owner on owner.id = credit.account_id and owner.kind = 'user'
+join ledger_postings debit on debit.journal_entry_id = e.id and debit.amount < 0
+join ledger_accounts pool on pool.id = debit.account_id and pool.kind = 'project_pool'
+where e.transaction_id is null
+  and e.description = 'Bounty refunded on project close';
+
+create type reconciliation_mismatch_kind as enum ('wallet_balance', 'project_bounty');
+
+create table reconciliation_runs (
+    id uuid primary key default uuid_generate_v1mc(),
+    started_at timestamptz not null default now(),
+    finished_at timestamptz not null default now(),
+    wallets_checked integer not null,
+    projects_checked integer not null,
+    mismatches integer not null
+);
+
+create index idx_reconciliation_runs_started_at on reconciliation_runs (started_at desc);
+
+create table reconciliation_mismatches (
+    id uuid primary key default uuid_generate_v1mc(),
+    run_id uuid not null references reconciliation_runs(id) on delete cascade,
+    kind reconciliation_mismatch_kind not null,
+    wallet_address varchar(66),
+    project_id uuid,
+    currency varchar(10),
+    recorded numeric(30, 2) not null,
+    expected numeric(30, 2) not null,
+    created_at timestamptz not null default now(),
+
+    constraint ck_reconciliation_mismatches_subject check (
+        (kind = 'wallet_balance' and wallet_address is not null and currency is not null and project_id is null) or
+        (kind = 'project_bounty' and project_id is not null and wallet_address is null)
+    )
+);
+
+create index idx_reconciliation_mismatches_run_id on reconciliation_mismatches (run_id);
+
+comment on column reconciliation_mismatches.recorded is 'The balance or bounty as stored.';
+comment on column reconciliation_mismatches.expected is 'The balance or bounty the transactions add up to.';
@@ -0,0 +1,46 @@
+-- Full-text Search
+-- Projects are searched by name and description, reports by title and body. Each keeps a weighted
+-- `search_vector` that a trigger maintains whenever the searched columns change; names and titles rank above
+-- descriptions and bodies.
+alter table projects add column search_vector tsvector;
+alter table research_report add column search_vector tsvector;
+
+create or replace function set_project_search_vector()
+    returns trigger as
+$$
+begin
+    NEW.search_vector =
+        setweight(to_tsvector('english', coalesce(NEW.name, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(NEW.description, '')), 'B');
+    return NEW;
+end;
+$$ language plpgsql;
+
+create or replace function set_report_search_vector()
+    returns trigger as
+$$
+begin
+    NEW.search_vector =
+        setweight(to_tsvector('english', coalesce(NEW.title, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(NEW.body, '')), 'B');
+    return NEW;
+end;
+$$ language plpgsql;
+
+create trigger set_search_vector
+    before insert or update of name, description on projects
+    for each row execute function set_project_search_vector();
+
+create trigger set_search_vector
+    before insert or update of title, body on research_report
+    for each row execute function set_report_search_vector();
+
+-- Backfill through the triggers.
+update projects set name = name;
+update research_report set title = title;
+
+create index idx_projects_search_vector on projects using gin (search_vector);
+create index idx_research_report_search_vector on research_report using gin (search_vector);
+
+comment on column projects.search_vector is 'Weighted name and description, maintained by the set_search_vector trigger.';
+comment on column research_report.search_vector is 'Weighted title and body, maintained by the set_search_vector trigger.';
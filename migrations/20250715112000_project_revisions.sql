@@ -0,0 +1,15 @@
+-- Project Revisions
+-- Owners can edit a project after creating it. Every edit is recorded with the fields it changed, so
+-- researchers can follow how a project's scope evolved.
+create table project_revisions (
+    id uuid primary key default uuid_generate_v1mc(),
+    project_id uuid not null references projects(id) on delete cascade,
+    revision integer not null check (revision > 0),
+    changed_by varchar(66) not null check (changed_by ~ '^0x[a-fA-F0-9]{64}$'),
+    changes jsonb not null,
+    created_at timestamptz not null default now(),
+    unique (project_id, revision)
+);
+
+comment on column project_revisions.revision is 'Sequence number of the edit within its project, starting at 1.';
+comment on column project_revisions.changes is 'The changed fields, as {"<field>": {"from": <old>, "to": <new>}}.';
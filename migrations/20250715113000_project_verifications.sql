@@ -0,0 +1,27 @@
+-- Project Verifications
+-- Owners prove they control a project before it is marked verified. The server issues a token, which the owner
+-- commits to a `.fortichain` file in the project's repository or signs with the account that deployed the
+-- project's contract. Admins can also approve or reject a verification by hand.
+create type project_verification_status as enum ('pending', 'verified', 'rejected');
+create type project_verification_method as enum ('repository_file', 'deployer_signature', 'manual');
+
+create table project_verifications (
+    id uuid primary key default uuid_generate_v1mc(),
+    project_id uuid not null references projects(id) on delete cascade,
+    token varchar(66) not null unique check (token ~ '^0x[a-f0-9]{62}$'),
+    repository_url text not null check (repository_url ~ '^(https?|ftp)://[^\s/$.?#].[^\s]*$'),
+    status project_verification_status not null default 'pending',
+    method project_verification_method,
+    reviewed_by varchar(66) check (reviewed_by ~ '^0x[a-fA-F0-9]{64}$'),
+    notes text check (length(notes) <= 1000),
+    created_at timestamptz not null default now(),
+    decided_at timestamptz,
+    constraint ck_project_verifications_method check (status <> 'verified' or method is not null)
+);
+
+-- A new challenge replaces the project's pending one.
+create unique index uq_project_verifications_pending on project_verifications (project_id) where status = 'pending';
+
+comment on column project_verifications.token is 'Random felt the owner proves control with.';
+comment on column project_verifications.method is 'How the verification was decided. NULL while pending or when rejected by an admin.';
+comment on column project_verifications.reviewed_by is 'The admin who approved or rejected the verification by hand.';
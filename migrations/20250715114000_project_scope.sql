@@ -0,0 +1,42 @@
+-- Project Scope
+-- Owners list the assets researchers may test, each with the highest severity a finding against it can
+-- be rated, and the items they may not. Every change publishes a new version of the scope instead of
+-- editing the current one, and a report points at the in-scope asset it targets, so it is judged against
+-- the scope as it stood when it was submitted.
+create type scope_asset_type as enum ('smart_contract', 'repository', 'web_url');
+
+create table project_scopes (
+    project_id uuid not null references projects(id) on delete cascade,
+    version integer not null check (version > 0),
+    rules text check (length(rules) <= 10000),
+    created_by varchar(66) not null check (created_by ~ '^0x[a-fA-F0-9]{64}$'),
+    created_at timestamptz not null default now(),
+    primary key (project_id, version)
+);
+
+create table project_scope_assets (
+    id uuid primary key default uuid_generate_v1mc(),
+    project_id uuid not null,
+    version integer not null,
+    in_scope boolean not null,
+    asset_type scope_asset_type,
+    identifier varchar(500) not null check (length(identifier) > 0),
+    description text check (length(description) <= 1000),
+    max_severity severity_level,
+    foreign key (project_id, version) references project_scopes(project_id, version) on delete cascade,
+    constraint ck_project_scope_assets_in_scope check (
+        (in_scope and asset_type is not null and max_severity is not null) or
+        (not in_scope and max_severity is null)
+    )
+);
+
+create index idx_project_scope_assets_version on project_scope_assets(project_id, version);
+
+comment on column project_scopes.version is 'Sequence number of the scope within its project, starting at 1. The highest is current.';
+comment on column project_scopes.rules is 'Free-form rules of engagement researchers must follow.';
+comment on column project_scope_assets.identifier is 'Contract address, repository path or commit, or web URL the item covers.';
+comment on column project_scope_assets.max_severity is 'Highest severity a finding against this asset can be accepted with. Only set in scope.';
+
+alter table research_report add column scope_asset_id uuid references project_scope_assets(id);
+
+comment on column research_report.scope_asset_id is 'In-scope asset the report targets, from the scope current at submission.';
@@ -0,0 +1,17 @@
+-- Project Status
+-- Owners can pause a project to stop new reports for a while, for example during a redeploy, without
+-- closing it: the bounty stays escrowed and reports already submitted carry on. `closed_at` still
+-- marks a closed project, so the status is derived from it and from `paused_at`.
+create type project_status as enum ('active', 'paused', 'closed');
+
+alter table projects add column paused_at timestamptz;
+alter table projects add column status project_status not null generated always as (
+    case
+        when closed_at is not null then 'closed'::project_status
+        when paused_at is not null then 'paused'::project_status
+        else 'active'::project_status
+    end
+) stored;
+
+comment on column projects.paused_at is 'When the owner paused the project. Cleared on resume.';
+comment on column projects.status is 'Closed once closed_at is set, otherwise paused while paused_at is set.';
@@ -0,0 +1,25 @@
+-- Project Members
+-- A project's owner can bring in a team. Maintainers act on the project alongside the owner, verifying
+-- it and funding its bounty; viewers can read its reports. A member is invited and joins once they
+-- accept. The owner stays `projects.owner_address`, so members here are never owners.
+create type project_member_role as enum ('owner', 'maintainer', 'viewer');
+
+create table project_members (
+    project_id uuid not null references projects(id) on delete cascade,
+    wallet_address varchar(66) not null check (wallet_address ~ '^0x[a-fA-F0-9]{64}$'),
+    role project_member_role not null check (role <> 'owner'),
+    invited_by varchar(66) not null check (invited_by ~ '^0x[a-fA-F0-9]{64}$'),
+    invited_at timestamptz not null default now(),
+    accepted_at timestamptz,
+    primary key (project_id, wallet_address)
+);
+
+create index idx_project_members_wallet on project_members(wallet_address);
+
+comment on column project_members.accepted_at is 'When the invitee joined. The invitation is pending until then.';
+
+-- Ownership moves in two steps: the owner names the new owner, who then accepts.
+alter table projects add column pending_owner_address varchar(66)
+    check (pending_owner_address ~ '^0x[a-fA-F0-9]{64}$');
+
+comment on column projects.pending_owner_address is 'Wallet the owner offered the project to, until it accepts or the offer is cancelled.';
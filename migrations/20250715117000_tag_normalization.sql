@@ -0,0 +1,26 @@
+-- Tag Normalization
+-- Tag names are compared case-insensitively, so "DeFi" and "defi" are the same tag. Tags that only
+-- differ in case or surrounding whitespace are merged into the oldest of them before the collation
+-- changes, keeping that tag's spelling.
+with canonical as (
+    select id, min(id) over (partition by lower(btrim(name))) as keep_id
+    from tags
+)
+insert into project_tags (project_id, tag_id)
+select pt.project_id, c.keep_id
+from project_tags pt
+join canonical c on c.id = pt.tag_id
+where c.id <> c.keep_id
+on conflict do nothing;
+
+delete from tags t
+where exists (
+    select 1 from tags older
+    where lower(btrim(older.name)) = lower(btrim(t.name)) and older.id < t.id
+);
+
+update tags set name = btrim(name) where name <> btrim(name);
+
+alter table tags alter column name type varchar(50) collate case_insensitive;
+
+comment on column tags.name is 'Unique regardless of case; keeps the spelling it was first created with.';
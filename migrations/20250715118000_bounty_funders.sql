@@ -0,0 +1,19 @@
+-- Bounty Funders
+-- Maintainers can fund a project's bounty alongside its owner, and ownership can change hands, so
+-- what is left of a bounty is refunded to whoever put it in rather than to the current owner. Each
+-- funder holds a share of the bounty; disbursements draw on every share in proportion, and the
+-- shares always add up to `projects.bounty_amount`.
+create table project_bounty_funders (
+    project_id uuid not null references projects(id) on delete cascade,
+    wallet_address varchar(66) not null check (wallet_address ~ '^0x[a-fA-F0-9]{64}$'),
+    amount numeric(20, 2) not null check (amount >= 0),
+    primary key (project_id, wallet_address)
+);
+
+comment on column project_bounty_funders.amount is 'The funder''s part of the bounty still held, in the bounty''s currency.';
+
+-- Only owners could fund a bounty until now.
+insert into project_bounty_funders (project_id, wallet_address, amount)
+select id, owner_address, bounty_amount
+from projects
+where bounty_amount > 0;
@@ -0,0 +1,13 @@
+-- HTML Escaping
+-- Search snippets are HTML: `ts_headline` wraps the matched terms in `<mark>` but copies the rest of
+-- the text as it is. Report bodies and project descriptions are escaped with this function first,
+-- so `<mark>` is the only markup a snippet carries.
+create function html_escape(input text)
+    returns text
+    language sql
+    immutable strict parallel safe
+as
+$$
+select replace(replace(replace(replace(replace(input,
+    '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), '"', '&quot;'), '''', '&#39;');
+$$;
@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use starknet::{
+    core::types::{ExecutionResult, Felt, StarknetError, TransactionStatus},
+    providers::{JsonRpcClient, Provider, ProviderError, Url, jsonrpc::HttpTransport},
+};
+
+/// What the chain says about a transaction hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainTransactionStatus {
+    /// Unknown to the chain or not final yet.
+    Pending,
+    /// Final and executed successfully.
+    Confirmed,
+    /// Final but reverted, or not a valid transaction hash at all.
+    Failed,
+}
+
+/// Tells whether an on-chain transaction is final.
+///
+/// Escrow transactions stay `pending` until their source reports the hash as final. Keeping this
+/// behind a trait lets tests settle transactions against an in-memory chain.
+#[async_trait]
+pub trait ConfirmationSource: Send + Sync {
+    async fn status(&self, transaction_hash: &str) -> anyhow::Result<ChainTransactionStatus>;
+}
+
+/// Reads transaction statuses from a Starknet node over JSON-RPC.
+pub struct RpcConfirmationSource {
+    provider: JsonRpcClient<HttpTransport>,
+}
+
+impl RpcConfirmationSource {
+    pub fn new(rpc_url: &str) -> anyhow::Result<Self> {
+        let url = Url::parse(rpc_url)?;
+        Ok(Self {
+            provider: JsonRpcClient::new(HttpTransport::new(url)),
+        })
+    }
+}
+
+#[async_trait]
+impl ConfirmationSource for RpcConfirmationSource {
+    async fn status(&self, transaction_hash: &str) -> anyhow::Result<ChainTransactionStatus> {
+        let Ok(hash) = Felt::from_hex(transaction_hash) else {
+            return Ok(ChainTransactionStatus::Failed);
+        };
+
+        match self.provider.get_transaction_status(hash).await {
+            Ok(
+                TransactionStatus::AcceptedOnL2(result) | TransactionStatus::AcceptedOnL1(result),
+            ) => Ok(match result {
+                ExecutionResult::Succeeded => ChainTransactionStatus::Confirmed,
+                ExecutionResult::Reverted { .. } => ChainTransactionStatus::Failed,
+            }),
+            Ok(_) => Ok(ChainTransactionStatus::Pending),
+            // The node may simply not have seen the transaction yet.
+            Err(ProviderError::StarknetError(StarknetError::TransactionHashNotFound)) => {
+                Ok(ChainTransactionStatus::Pending)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use starknet::{
+    core::types::{ExecutionResult, Felt, StarknetError},
+    macros::{felt, selector},
+    providers::{JsonRpcClient, Provider, ProviderError, Url, jsonrpc::HttpTransport},
+};
+
+/// The Universal Deployer Contract, legacy and current. Both have the same address on mainnet and
+/// Sepolia. Only their `ContractDeployed` events are trusted, since any contract can emit one.
+pub const UNIVERSAL_DEPLOYERS: [Felt; 2] = [
+    felt!("0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf"),
+    felt!("0x02ceed65a4bd731034c01113685c831b01c15d7d432f71afb1cf1634b53a2125"),
+];
+
+/// Finds the account that deployed a contract, from the transaction that deployed it.
+///
+/// Owners can prove they control a project by signing with its deployer account. Keeping this
+/// behind a trait lets tests deploy contracts on a stand-in node.
+#[async_trait]
+pub trait ContractDeployerSource: Send + Sync {
+    /// The account that deployed `contract_address`, if `transaction_hash` deployed it.
+    async fn deployer(
+        &self,
+        contract_address: Felt,
+        transaction_hash: Felt,
+    ) -> anyhow::Result<Option<Felt>>;
+}
+
+/// Reads the deployment from the transaction's receipt over JSON-RPC.
+pub struct RpcContractDeployerSource {
+    provider: JsonRpcClient<HttpTransport>,
+}
+
+impl RpcContractDeployerSource {
+    pub fn new(rpc_url: &str) -> anyhow::Result<Self> {
+        let url = Url::parse(rpc_url)?;
+        Ok(Self {
+            provider: JsonRpcClient::new(HttpTransport::new(url)),
+        })
+    }
+}
+
+#[async_trait]
+impl ContractDeployerSource for RpcContractDeployerSource {
+    async fn deployer(
+        &self,
+        contract_address: Felt,
+        transaction_hash: Felt,
+    ) -> anyhow::Result<Option<Felt>> {
+        let receipt = match self
+            .provider
+            .get_transaction_receipt(transaction_hash)
+            .await
+        {
+            Ok(receipt) => receipt.receipt,
+            Err(ProviderError::StarknetError(StarknetError::TransactionHashNotFound)) => {
+                return Ok(None);
+            }
+            Err(e) => return Err(e.into()),
+        };
+        if !matches!(receipt.execution_result(), ExecutionResult::Succeeded) {
+            return Ok(None);
+        }
+
+        // `ContractDeployed` carries the deployed address, then the deployer.
+        Ok(receipt
+            .events()
+            .iter()
+            .filter(|event| UNIVERSAL_DEPLOYERS.contains(&event.from_address))
+            .filter(|event| event.keys.first() == Some(&selector!("ContractDeployed")))
+            .find_map(|event| match event.data.as_slice() {
+                [address, deployer, ..] if *address == contract_address => Some(*deployer),
+                _ => None,
+            }))
+    }
+}
@@ -0,0 +1,11 @@
+mod confirmation;
+mod deployer;
+mod payout;
+mod signature;
+mod typed_data;
+
+pub use confirmation::*;
+pub use deployer::*;
+pub use payout::*;
+pub use signature::*;
+pub use typed_data::*;
@@ -0,0 +1,221 @@
+use async_trait::async_trait;
+use starknet::{
+    accounts::{Account, ExecutionEncoding, SingleOwnerAccount},
+    core::{
+        types::{
+            BlockId, BlockTag, BroadcastedInvokeTransactionV3, Call, Felt,
+            MaybePreConfirmedBlockWithTxHashes, U256,
+        },
+        utils::{cairo_short_string_to_felt, get_selector_from_name},
+    },
+    providers::{JsonRpcClient, Provider, ProviderError, Url, jsonrpc::HttpTransport},
+    signers::{LocalWallet, SigningKey},
+};
+
+/// L2 gas an ERC-20 transfer from an account contract is allowed to burn, validation included.
+const TRANSFER_L2_GAS: u64 = 5_000_000;
+
+/// L1 data gas an ERC-20 transfer is allowed to burn.
+const TRANSFER_L1_DATA_GAS: u64 = 1_000;
+
+/// Headroom over the current gas prices, so a transfer still lands if they rise before inclusion.
+const GAS_PRICE_MARGIN: u128 = 2;
+
+/// An ERC-20 transfer out of the platform hot wallet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transfer {
+    pub token: Felt,
+    pub recipient: Felt,
+    /// In the token's base units.
+    pub amount: U256,
+}
+
+/// Current gas prices, in fri per unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasPrices {
+    pub l1_gas: u128,
+    pub l2_gas: u128,
+    pub l1_data_gas: u128,
+}
+
+/// A transaction signed by the hot wallet, ready to be broadcast.
+#[derive(Debug, Clone)]
+pub struct SignedInvoke {
+    pub transaction_hash: Felt,
+    pub transaction: BroadcastedInvokeTransactionV3,
+}
+
+/// Why a node did not take a transaction.
+#[derive(Debug, thiserror::Error)]
+pub enum SubmitError {
+    /// The node refused the transaction, so it can be signed again and resubmitted.
+    #[error("transaction rejected: {0}")]
+    Rejected(String),
+    /// The transaction may or may not have reached the node.
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Signs payouts on behalf of the platform hot wallet.
+#[async_trait]
+pub trait PayoutSigner: Send + Sync {
+    /// Account the payouts are sent from.
+    fn address(&self) -> Felt;
+
+    async fn sign_transfer(
+        &self,
+        transfer: &Transfer,
+        nonce: Felt,
+        gas_prices: &GasPrices,
+    ) -> anyhow::Result<SignedInvoke>;
+}
+
+/// The node payouts are submitted to. Kept behind a trait so the payout worker can be tested
+/// without a network.
+#[async_trait]
+pub trait PayoutNode: Send + Sync {
+    /// Next nonce of `account`, counting transactions the node has not included yet.
+    async fn nonce(&self, account: Felt) -> anyhow::Result<Felt>;
+
+    async fn gas_prices(&self) -> anyhow::Result<GasPrices>;
+
+    /// Broadcasts `invoke` and returns the hash the node accepted it under.
+    async fn submit(&self, invoke: &SignedInvoke) -> Result<Felt, SubmitError>;
+}
+
+/// Signs with a private key held by the server.
+pub struct LocalPayoutSigner {
+    account: SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>,
+}
+
+impl LocalPayoutSigner {
+    /// `chain_id` is the short string id, e.g. `SN_SEPOLIA`. Nothing is sent to `rpc_url` while
+    /// signing.
+    pub fn new(
+        rpc_url: &str,
+        chain_id: &str,
+        address: &str,
+        private_key: &str,
+    ) -> anyhow::Result<Self> {
+        let provider = JsonRpcClient::new(HttpTransport::new(Url::parse(rpc_url)?));
+        let signer = LocalWallet::from_signing_key(SigningKey::from_secret_scalar(Felt::from_hex(
+            private_key,
+        )?));
+        Ok(Self {
+            account: SingleOwnerAccount::new(
+                provider,
+                signer,
+                Felt::from_hex(address)?,
+                cairo_short_string_to_felt(chain_id)?,
+                ExecutionEncoding::New,
+            ),
+        })
+    }
+}
+
+#[async_trait]
+impl PayoutSigner for LocalPayoutSigner {
+    fn address(&self) -> Felt {
+        self.account.address()
+    }
+
+    async fn sign_transfer(
+        &self,
+        transfer: &Transfer,
+        nonce: Felt,
+        gas_prices: &GasPrices,
+    ) -> anyhow::Result<SignedInvoke> {
+        let call = Call {
+            to: transfer.token,
+            selector: get_selector_from_name("transfer")?,
+            calldata: vec![
+                transfer.recipient,
+                Felt::from(transfer.amount.low()),
+                Felt::from(transfer.amount.high()),
+            ],
+        };
+        let execution = self
+            .account
+            .execute_v3(vec![call])
+            .nonce(nonce)
+            .l1_gas(0)
+            .l1_gas_price(gas_prices.l1_gas * GAS_PRICE_MARGIN)
+            .l2_gas(TRANSFER_L2_GAS)
+            .l2_gas_price(gas_prices.l2_gas * GAS_PRICE_MARGIN)
+            .l1_data_gas(TRANSFER_L1_DATA_GAS)
+            .l1_data_gas_price(gas_prices.l1_data_gas * GAS_PRICE_MARGIN)
+            .tip(0)
+            .prepared()?;
+
+        Ok(SignedInvoke {
+            transaction_hash: execution.transaction_hash(false),
+            transaction: execution.get_invoke_request(false, false).await?,
+        })
+    }
+}
+
+/// Submits payouts to a Starknet node over JSON-RPC.
+pub struct RpcPayoutNode {
+    provider: JsonRpcClient<HttpTransport>,
+}
+
+impl RpcPayoutNode {
+    pub fn new(rpc_url: &str) -> anyhow::Result<Self> {
+        let url = Url::parse(rpc_url)?;
+        Ok(Self {
+            provider: JsonRpcClient::new(HttpTransport::new(url)),
+        })
+    }
+}
+
+#[async_trait]
+impl PayoutNode for RpcPayoutNode {
+    async fn nonce(&self, account: Felt) -> anyhow::Result<Felt> {
+        Ok(self
+            .provider
+            .get_nonce(BlockId::Tag(BlockTag::PreConfirmed), account)
+            .await?)
+    }
+
+    async fn gas_prices(&self) -> anyhow::Result<GasPrices> {
+        let (l1_gas, l2_gas, l1_data_gas) = match self
+            .provider
+            .get_block_with_tx_hashes(BlockId::Tag(BlockTag::Latest))
+            .await?
+        {
+            MaybePreConfirmedBlockWithTxHashes::Block(block) => (
+                block.l1_gas_price,
+                block.l2_gas_price,
+                block.l1_data_gas_price,
+            ),
+            MaybePreConfirmedBlockWithTxHashes::PreConfirmedBlock(block) => (
+                block.l1_gas_price,
+                block.l2_gas_price,
+                block.l1_data_gas_price,
+            ),
+        };
+
+        let fri = |price: Felt| -> anyhow::Result<u128> {
+            price
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("gas price {price:#x} does not fit in a u128"))
+        };
+        Ok(GasPrices {
+            l1_gas: fri(l1_gas.price_in_fri)?,
+            l2_gas: fri(l2_gas.price_in_fri)?,
+            l1_data_gas: fri(l1_data_gas.price_in_fri)?,
+        })
+    }
+
+    async fn submit(&self, invoke: &SignedInvoke) -> Result<Felt, SubmitError> {
+        match self
+            .provider
+            .add_invoke_transaction(&invoke.transaction)
+            .await
+        {
+            Ok(result) => Ok(result.transaction_hash),
+            Err(ProviderError::StarknetError(e)) => Err(SubmitError::Rejected(e.to_string())),
+            Err(e) => Err(SubmitError::Unknown(e.into())),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use starknet::{
+    core::types::{BlockId, BlockTag, Felt, FunctionCall},
+    macros::{selector, short_string},
+    providers::{JsonRpcClient, Provider, ProviderError, Url, jsonrpc::HttpTransport},
+};
+
+/// Checks that a signature over a message hash was produced by a Starknet account.
+///
+/// Starknet accounts are contracts, so the authoritative check is the account's own
+/// `is_valid_signature` entrypoint. Keeping this behind a trait lets tests verify
+/// signatures from locally generated keypairs without a node.
+#[async_trait]
+pub trait SignatureVerifier: Send + Sync {
+    async fn verify(&self, account: Felt, hash: Felt, signature: &[Felt]) -> anyhow::Result<bool>;
+}
+
+/// Verifies signatures by calling `is_valid_signature` on the account contract over JSON-RPC.
+pub struct RpcSignatureVerifier {
+    provider: JsonRpcClient<HttpTransport>,
+}
+
+impl RpcSignatureVerifier {
+    pub fn new(rpc_url: &str) -> anyhow::Result<Self> {
+        let url = Url::parse(rpc_url)?;
+        Ok(Self {
+            provider: JsonRpcClient::new(HttpTransport::new(url)),
+        })
+    }
+}
+
+#[async_trait]
+impl SignatureVerifier for RpcSignatureVerifier {
+    async fn verify(&self, account: Felt, hash: Felt, signature: &[Felt]) -> anyhow::Result<bool> {
+        let mut calldata = vec![hash, Felt::from(signature.len())];
+        calldata.extend_from_slice(signature);
+
+        let result = self
+            .provider
+            .call(
+                FunctionCall {
+                    contract_address: account,
+                    entry_point_selector: selector!("is_valid_signature"),
+                    calldata,
+                },
+                BlockId::Tag(BlockTag::Latest),
+            )
+            .await;
+
+        match result {
+            // OpenZeppelin and Argent accounts return 'VALID', older accounts return 1.
+            Ok(output) => Ok(matches!(
+                output.first(),
+                Some(value) if *value == short_string!("VALID") || *value == Felt::ONE
+            )),
+            // Undeployed accounts and rejected signatures surface as contract errors.
+            Err(ProviderError::StarknetError(e)) => {
+                tracing::warn!(account = %account.to_hex_string(), "is_valid_signature failed: {e}");
+                Ok(false)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use serde_json::{Value, json};
+use starknet::core::types::{Felt, TypedData};
+
+/// Name of the SNIP-12 signing domain shared by every message the platform asks wallets to sign.
+pub const SIGNING_DOMAIN_NAME: &str = "FortiChain";
+
+/// SNIP-12 (revision 1) typed data a wallet signs to prove it controls `wallet_address`.
+///
+/// The nonce is single-use and issued by the `/auth/challenge` endpoint, the same JSON is
+/// returned to clients so they can hand it to the wallet's `signMessage` unchanged.
+pub fn authentication_typed_data(chain_id: &str, wallet_address: &str, nonce: &str) -> Value {
+    json!({
+        "types": {
+            "StarknetDomain": [
+                { "name": "name", "type": "shortstring" },
+                { "name": "version", "type": "shortstring" },
+                { "name": "chainId", "type": "shortstring" },
+                { "name": "revision", "type": "shortstring" }
+            ],
+            "Authentication": [
+                { "name": "wallet", "type": "ContractAddress" },
+                { "name": "nonce", "type": "felt" }
+            ]
+        },
+        "primaryType": "Authentication",
+        "domain": {
+            "name": SIGNING_DOMAIN_NAME,
+            "version": "1",
+            "chainId": chain_id,
+            "revision": "1"
+        },
+        "message": {
+            "wallet": wallet_address,
+            "nonce": nonce
+        }
+    })
+}
+
+/// SNIP-12 (revision 1) typed data the deployer of `contract_address` signs to prove it controls the
+/// project. `token` is the project's verification token.
+pub fn project_verification_typed_data(
+    chain_id: &str,
+    contract_address: Felt,
+    token: &str,
+) -> Value {
+    json!({
+        "types": {
+            "StarknetDomain": [
+                { "name": "name", "type": "shortstring" },
+                { "name": "version", "type": "shortstring" },
+                { "name": "chainId", "type": "shortstring" },
+                { "name": "revision", "type": "shortstring" }
+            ],
+            "ProjectVerification": [
+                { "name": "contract", "type": "ContractAddress" },
+                { "name": "token", "type": "felt" }
+            ]
+        },
+        "primaryType": "ProjectVerification",
+        "domain": {
+            "name": SIGNING_DOMAIN_NAME,
+            "version": "1",
+            "chainId": chain_id,
+            "revision": "1"
+        },
+        "message": {
+            "contract": contract_address.to_hex_string(),
+            "token": token
+        }
+    })
+}
+
+/// Computes the SNIP-12 message hash of `typed_data` as signed by `account`.
+pub fn typed_data_hash(typed_data: Value, account: Felt) -> anyhow::Result<Felt> {
+    let typed_data: TypedData = serde_json::from_value(typed_data)?;
+    Ok(typed_data.message_hash(account)?)
+}
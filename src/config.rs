@@ -1,6 +1,10 @@
+use crate::http::validate_currency;
+use bigdecimal::BigDecimal;
 use serde::Deserialize;
 use std::{
+    collections::HashMap,
     net::{Ipv6Addr, SocketAddr},
+    str::FromStr,
     sync::Arc,
 };
 
@@ -13,8 +17,72 @@ pub struct Configuration {
     pub app_port: u16,
     pub database_url: String,
     pub max_db_connections: u32,
+    pub starknet_rpc_url: String,
+    pub starknet_chain_id: String,
+    pub escrow_contract_address: Option<String>,
+    pub escrow_start_block: u64,
+    pub escrow_tokens: Vec<EscrowToken>,
+    pub payout_account_address: Option<String>,
+    pub payout_private_key: Option<String>,
+    /// Withdrawals above these amounts, by currency, wait for admin approval.
+    pub withdrawal_approval_thresholds: HashMap<String, BigDecimal>,
+    /// Distinct admins that must approve a withdrawal above its threshold.
+    pub withdrawal_approvals_required: u32,
+    /// Hosts whose repositories are fetched to verify project ownership.
+    pub verification_repository_hosts: Vec<String>,
 }
 
+/// A token the escrow contract accepts, and the currency its deposits are booked in.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EscrowToken {
+    pub currency: String,
+    pub address: String,
+    pub decimals: u32,
+}
+
+impl TryFrom<&str> for EscrowToken {
+    type Error = String;
+
+    /// Parses `CURRENCY:address:decimals`, e.g. `STRK:0x0471...938d:18`. The currency must be one
+    /// the platform accepts, or its deposits could never be withdrawn or allocated.
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let invalid =
+            || format!("{s} is not a valid escrow token. Use `CURRENCY:address:decimals`.");
+        let mut parts = s.trim().split(':');
+        let (Some(currency), Some(address), Some(decimals), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        validate_currency(currency, &())
+            .map_err(|_| format!("{currency} is not a supported escrow token currency."))?;
+        Ok(EscrowToken {
+            currency: currency.to_string(),
+            address: address.to_string(),
+            decimals: decimals.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Parses `CURRENCY:amount`, e.g. `STRK:10000`. The currency must be one the platform accepts,
+/// spelled exactly as withdrawals name it, or the threshold would never hold a withdrawal back.
+fn parse_threshold(s: &str) -> Result<(String, BigDecimal), String> {
+    let invalid = || format!("{s} is not a valid threshold. Use `CURRENCY:amount`.");
+    let (currency, amount) = s.trim().split_once(':').ok_or_else(invalid)?;
+    validate_currency(currency, &())
+        .map_err(|_| format!("{currency} is not a supported withdrawal currency."))?;
+    let amount = BigDecimal::from_str(amount).map_err(|_| invalid())?;
+    Ok((currency.to_string(), amount))
+}
+
+/// The currencies the platform accepts, at their mainnet token addresses. Sepolia deployments set
+/// `ESCROW_TOKENS`, as its USDC and USDT live elsewhere.
+const DEFAULT_ESCROW_TOKENS: &str = "STRK:0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d:18,\
+    USDC:0x053c91253bc9682c04929ca02ed00b3e423f6710d2ee7e0d5ebb06f3ecf368a8:6,\
+    USDT:0x068f5c6a61780768455de69077e07e89787839bf8166decfbf92b645209c0fb8:6";
+
+const DEFAULT_VERIFICATION_REPOSITORY_HOSTS: &str = "github.com,gitlab.com";
+
 impl Configuration {
     pub fn new() -> Config {
         let env = env_var("APP_ENVIRONMENT")
@@ -29,6 +97,59 @@ impl Configuration {
             .parse::<u32>()
             .expect("DB_MAX_CONNECTIONS is invalid or not specified.");
 
+        let starknet_rpc_url = env_var("STARKNET_RPC_URL");
+        // Short string chain id used as the SNIP-12 signing domain, e.g. SN_MAIN or SN_SEPOLIA.
+        let starknet_chain_id = env_var("STARKNET_CHAIN_ID");
+
+        // The chain watcher only runs when the escrow contract is configured.
+        let escrow_contract_address = std::env::var("ESCROW_CONTRACT_ADDRESS").ok();
+        let escrow_start_block = std::env::var("ESCROW_START_BLOCK")
+            .map(|block| {
+                block
+                    .parse::<u64>()
+                    .expect("ESCROW_START_BLOCK is invalid. Please specify a block number.")
+            })
+            .unwrap_or(0);
+        let escrow_tokens = std::env::var("ESCROW_TOKENS")
+            .unwrap_or_else(|_| DEFAULT_ESCROW_TOKENS.to_string())
+            .split(',')
+            .map(|token| EscrowToken::try_from(token).expect("ESCROW_TOKENS is invalid."))
+            .collect();
+
+        // Hot wallet withdrawals are paid out from. The payout worker only runs when both are set.
+        let payout_account_address = std::env::var("PAYOUT_ACCOUNT_ADDRESS").ok();
+        let payout_private_key = std::env::var("PAYOUT_PRIVATE_KEY").ok();
+
+        // Treasury sign-off on large withdrawals, as CURRENCY:amount pairs. Unset means none needs it.
+        let withdrawal_approval_thresholds = std::env::var("WITHDRAWAL_APPROVAL_THRESHOLDS")
+            .map(|thresholds| {
+                thresholds
+                    .split(',')
+                    .map(|threshold| {
+                        parse_threshold(threshold)
+                            .expect("WITHDRAWAL_APPROVAL_THRESHOLDS is invalid.")
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let withdrawal_approvals_required = std::env::var("WITHDRAWAL_APPROVALS_REQUIRED")
+            .map(|approvals| {
+                approvals
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|&approvals| approvals > 0)
+                    .expect("WITHDRAWAL_APPROVALS_REQUIRED is invalid. Please specify a positive number.")
+            })
+            .unwrap_or(2);
+
+        // Project verification fetches from owner-supplied URLs, so only these hosts are contacted.
+        let verification_repository_hosts = std::env::var("VERIFICATION_REPOSITORY_HOSTS")
+            .unwrap_or_else(|_| DEFAULT_VERIFICATION_REPOSITORY_HOSTS.to_string())
+            .split(',')
+            .map(|host| host.trim().to_lowercase())
+            .filter(|host| !host.is_empty())
+            .collect();
+
         let listen_address = SocketAddr::from((Ipv6Addr::UNSPECIFIED, app_port));
 
         // Configuration values to be safely shared across requests.
@@ -38,6 +159,16 @@ impl Configuration {
             app_port,
             database_url,
             max_db_connections,
+            starknet_rpc_url,
+            starknet_chain_id,
+            escrow_contract_address,
+            escrow_start_block,
+            escrow_tokens,
+            payout_account_address,
+            payout_private_key,
+            withdrawal_approval_thresholds,
+            withdrawal_approvals_required,
+            verification_repository_hosts,
         })
     }
 
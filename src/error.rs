@@ -35,6 +35,13 @@ pub enum Error {
     #[error("conflict")]
     Conflict,
 
+    /// Return `409 Conflict` with a machine-readable `reason` in a JSON body.
+    #[error("conflict: {message}")]
+    ConflictReason {
+        reason: Cow<'static, str>,
+        message: Cow<'static, str>,
+    },
+
     /// Return `422 Unprocessable Entity`
     #[error("error in the request body")]
     UnprocessableEntity {
@@ -49,6 +56,17 @@ pub enum Error {
 }
 
 impl Error {
+    /// Convenient constructor for `Error::ConflictReason`.
+    pub fn conflict(
+        reason: impl Into<Cow<'static, str>>,
+        message: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self::ConflictReason {
+            reason: reason.into(),
+            message: message.into(),
+        }
+    }
+
     /// Convenient constructor for `Error::UnprocessableEntity`.
     pub fn unprocessable_entity<K, V>(errors: impl IntoIterator<Item = (K, V)>) -> Self
     where
@@ -73,7 +91,7 @@ impl Error {
             Self::Unauthorized => StatusCode::UNAUTHORIZED,
             Self::Forbidden => StatusCode::FORBIDDEN,
             Self::NotFound => StatusCode::NOT_FOUND,
-            Self::Conflict => StatusCode::CONFLICT,
+            Self::Conflict | Self::ConflictReason { .. } => StatusCode::CONFLICT,
             Self::UnprocessableEntity { .. } => StatusCode::UNPROCESSABLE_ENTITY,
             Self::DatabaseError(_) | Self::InternalServerError(_) => {
                 StatusCode::INTERNAL_SERVER_ERROR
@@ -98,6 +116,14 @@ impl IntoResponse for Error {
                 .into_response(),
             Self::NotFound => (StatusCode::NOT_FOUND, self.to_string()).into_response(),
             Self::Conflict => (StatusCode::CONFLICT, self.to_string()).into_response(),
+            Self::ConflictReason { reason, message } => {
+                #[derive(serde::Serialize)]
+                struct Conflict {
+                    reason: Cow<'static, str>,
+                    message: Cow<'static, str>,
+                }
+                (StatusCode::CONFLICT, Json(Conflict { reason, message })).into_response()
+            }
             Self::UnprocessableEntity { errors } => {
                 #[derive(serde::Serialize)]
                 struct Errors {
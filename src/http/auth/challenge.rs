@@ -0,0 +1,48 @@
+use crate::{
+    AppState, Result,
+    chain::authentication_typed_data,
+    http::auth::{
+        CHALLENGE_TTL_MINUTES, ChallengeRequest, ChallengeResponse, parse_wallet_address,
+    },
+};
+use axum::{Json, extract::State};
+use garde::Validate;
+use rand::Rng;
+
+#[tracing::instrument(name = "challenge_handler", skip(state, payload))]
+pub async fn challenge_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<ChallengeRequest>,
+) -> Result<Json<ChallengeResponse>> {
+    payload.validate()?;
+    let (_, wallet_address) =
+        parse_wallet_address(&payload.wallet_address).expect("address validated above");
+
+    // 31 bytes always fit in a felt.
+    let nonce = format!("0x{}", hex::encode(rand::rng().random::<[u8; 31]>()));
+    let expires_at = chrono::Utc::now() + chrono::Duration::minutes(CHALLENGE_TTL_MINUTES);
+
+    sqlx::query!(
+        r#"
+        INSERT INTO auth_challenges (nonce, wallet_address, expires_at)
+        VALUES ($1, $2, $3)
+        "#,
+        nonce,
+        wallet_address,
+        expires_at
+    )
+    .execute(&state.db.pool)
+    .await?;
+
+    let typed_data = authentication_typed_data(
+        &state.configuration.starknet_chain_id,
+        &wallet_address,
+        &nonce,
+    );
+
+    Ok(Json(ChallengeResponse {
+        nonce,
+        expires_at,
+        typed_data,
+    }))
+}
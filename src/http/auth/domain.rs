@@ -0,0 +1,80 @@
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use starknet::core::types::Felt;
+use uuid::Uuid;
+
+/// Header carrying the wallet address a request acts as.
+pub const WALLET_ADDRESS_HEADER: &str = "x-wallet-address";
+/// Header carrying the nonce issued by `/auth/challenge`.
+pub const WALLET_NONCE_HEADER: &str = "x-wallet-nonce";
+/// Header carrying the SNIP-12 signature as comma separated hex felts, e.g. `0xr,0xs`.
+pub const WALLET_SIGNATURE_HEADER: &str = "x-wallet-signature";
+
+/// How long an issued nonce stays valid.
+pub const CHALLENGE_TTL_MINUTES: i64 = 5;
+/// How long an access token can be used before it has to be refreshed.
+pub const ACCESS_TOKEN_TTL_MINUTES: i64 = 15;
+/// How long a refresh token can be used to obtain new tokens.
+pub const REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ChallengeRequest {
+    #[garde(custom(validate_starknet_address))]
+    pub wallet_address: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChallengeResponse {
+    pub nonce: String,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub typed_data: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RefreshSessionRequest {
+    #[garde(ascii, length(bytes, equal = 64))]
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionResponse {
+    pub session_id: Uuid,
+    pub wallet_address: String,
+    pub access_token: String,
+    pub access_token_expires_at: chrono::DateTime<chrono::Utc>,
+    pub refresh_token: String,
+    pub refresh_token_expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// The session an access token in the `Authorization` header belongs to.
+///
+/// Inserted into the request extensions by [`session_layer`](super::session_layer).
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub id: Uuid,
+    pub wallet_address: String,
+}
+
+pub fn validate_starknet_address(address: &str, _context: &()) -> garde::Result {
+    match parse_wallet_address(address) {
+        Some(_) => Ok(()),
+        None => Err(garde::Error::new("Invalid Starknet address")),
+    }
+}
+
+/// Parses a wallet address into its felt and canonical `0x` + 64 lowercase hex form.
+pub fn parse_wallet_address(address: &str) -> Option<(Felt, String)> {
+    if !address.starts_with("0x")
+        || address.len() != 66
+        || !address.chars().skip(2).all(|c| c.is_ascii_hexdigit())
+    {
+        return None;
+    }
+
+    let felt = Felt::from_hex(address).ok()?;
+    let canonical = felt.to_fixed_hex_string();
+    // Values beyond the field prime would silently wrap around.
+    canonical
+        .eq_ignore_ascii_case(address)
+        .then_some((felt, canonical))
+}
@@ -0,0 +1,105 @@
+use crate::{
+    AppState, Error, Result,
+    chain::{authentication_typed_data, typed_data_hash},
+    http::auth::{
+        Session, WALLET_ADDRESS_HEADER, WALLET_NONCE_HEADER, WALLET_SIGNATURE_HEADER,
+        parse_wallet_address,
+    },
+};
+use axum::{extract::FromRequestParts, http::request::Parts};
+use starknet::core::types::Felt;
+
+/// The wallet a request acts as, authenticated either by a session access token or by a
+/// wallet signature. Handlers must use this address instead of trusting one in the request body.
+#[derive(Debug, Clone)]
+pub struct AuthenticatedWallet(pub String);
+
+impl FromRequestParts<AppState> for AuthenticatedWallet {
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self> {
+        // Already authenticated by a middleware, whose signature nonce cannot be consumed twice.
+        if let Some(wallet) = parts.extensions.get::<AuthenticatedWallet>() {
+            return Ok(wallet.clone());
+        }
+
+        if let Some(session) = parts.extensions.get::<Session>() {
+            return Ok(AuthenticatedWallet(session.wallet_address.clone()));
+        }
+
+        let SignedWallet(wallet_address) = SignedWallet::from_request_parts(parts, state).await?;
+        Ok(AuthenticatedWallet(wallet_address))
+    }
+}
+
+/// A wallet that proved control of its address with a signature on the current request.
+///
+/// Extracting it consumes the nonce in the `x-wallet-nonce` header and checks the
+/// `x-wallet-signature` header against the SNIP-12 `Authentication` message for that
+/// nonce. Session tokens are not accepted.
+#[derive(Debug, Clone)]
+pub struct SignedWallet(pub String);
+
+impl FromRequestParts<AppState> for SignedWallet {
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self> {
+        let (account, wallet_address) = parse_wallet_address(header(parts, WALLET_ADDRESS_HEADER)?)
+            .ok_or(Error::Unauthorized)?;
+        let nonce = header(parts, WALLET_NONCE_HEADER)?.to_owned();
+        let signature = header(parts, WALLET_SIGNATURE_HEADER)?
+            .split(',')
+            .map(|felt| Felt::from_hex(felt.trim()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| Error::Unauthorized)?;
+
+        // Consume the nonce before verifying so a signature can never be used twice,
+        // even by concurrent requests.
+        let consumed = sqlx::query_scalar!(
+            r#"
+            UPDATE auth_challenges
+            SET consumed_at = NOW()
+            WHERE nonce = $1
+              AND wallet_address = $2
+              AND consumed_at IS NULL
+              AND expires_at > NOW()
+            RETURNING nonce
+            "#,
+            nonce,
+            wallet_address
+        )
+        .fetch_optional(&state.db.pool)
+        .await?;
+
+        if consumed.is_none() {
+            tracing::warn!(wallet = %wallet_address, "Unknown, expired or reused nonce");
+            return Err(Error::Unauthorized);
+        }
+
+        let typed_data = authentication_typed_data(
+            &state.configuration.starknet_chain_id,
+            &wallet_address,
+            &nonce,
+        );
+        let hash = typed_data_hash(typed_data, account)?;
+
+        if !state
+            .signature_verifier
+            .verify(account, hash, &signature)
+            .await?
+        {
+            tracing::warn!(wallet = %wallet_address, "Invalid wallet signature");
+            return Err(Error::Unauthorized);
+        }
+
+        Ok(SignedWallet(wallet_address))
+    }
+}
+
+fn header<'a>(parts: &'a Parts, name: &str) -> Result<&'a str> {
+    parts
+        .headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .ok_or(Error::Unauthorized)
+}
@@ -0,0 +1,41 @@
+use crate::{
+    AppState, Result,
+    http::auth::{SessionResponse, SignedWallet, session::SessionTokens},
+};
+use axum::{Json, extract::State, http::StatusCode};
+
+#[tracing::instrument(name = "login_handler", skip(state))]
+pub async fn login_handler(
+    State(state): State<AppState>,
+    SignedWallet(wallet_address): SignedWallet,
+) -> Result<(StatusCode, Json<SessionResponse>)> {
+    let tokens = SessionTokens::generate();
+
+    let session_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO sessions (
+            wallet_address,
+            access_token_hash,
+            access_token_expires_at,
+            refresh_token_hash,
+            refresh_token_expires_at
+        )
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id
+        "#,
+        wallet_address,
+        tokens.access_token_hash(),
+        tokens.access_token_expires_at,
+        tokens.refresh_token_hash(),
+        tokens.refresh_token_expires_at
+    )
+    .fetch_one(&state.db.pool)
+    .await?;
+
+    tracing::info!(session_id = %session_id, wallet = %wallet_address, "Session created");
+
+    Ok((
+        StatusCode::CREATED,
+        Json(tokens.into_response(session_id, wallet_address)),
+    ))
+}
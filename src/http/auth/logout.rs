@@ -0,0 +1,48 @@
+use crate::{
+    AppState, Result,
+    http::auth::{AuthenticatedWallet, Session},
+};
+use axum::{extract::State, http::StatusCode};
+
+#[tracing::instrument(name = "logout_handler", skip(state))]
+pub async fn logout_handler(State(state): State<AppState>, session: Session) -> Result<StatusCode> {
+    sqlx::query!(
+        r#"
+        UPDATE sessions
+        SET revoked_at = NOW()
+        WHERE id = $1 AND revoked_at IS NULL
+        "#,
+        session.id
+    )
+    .execute(&state.db.pool)
+    .await?;
+
+    tracing::info!(session_id = %session.id, "Session revoked");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(name = "logout_all_handler", skip(state))]
+pub async fn logout_all_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(wallet_address): AuthenticatedWallet,
+) -> Result<StatusCode> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE sessions
+        SET revoked_at = NOW()
+        WHERE wallet_address = $1 AND revoked_at IS NULL
+        "#,
+        wallet_address
+    )
+    .execute(&state.db.pool)
+    .await?;
+
+    tracing::info!(
+        wallet = %wallet_address,
+        revoked = result.rows_affected(),
+        "All sessions revoked"
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,23 @@
+mod challenge;
+mod domain;
+mod extractor;
+mod login;
+mod logout;
+mod refresh;
+mod session;
+
+use axum::{Router, routing::post};
+pub use domain::*;
+pub use extractor::*;
+pub use session::session_layer;
+
+use crate::AppState;
+
+pub(crate) fn router() -> Router<AppState> {
+    Router::new()
+        .route("/auth/challenge", post(challenge::challenge_handler))
+        .route("/auth/login", post(login::login_handler))
+        .route("/auth/refresh", post(refresh::refresh_handler))
+        .route("/auth/logout", post(logout::logout_handler))
+        .route("/auth/logout_all", post(logout::logout_all_handler))
+}
@@ -0,0 +1,49 @@
+use crate::{
+    AppState, Error, Result,
+    http::auth::{
+        RefreshSessionRequest, SessionResponse,
+        session::{SessionTokens, hash_token},
+    },
+};
+use axum::{Json, extract::State};
+use garde::Validate;
+
+#[tracing::instrument(name = "refresh_handler", skip(state, payload))]
+pub async fn refresh_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<RefreshSessionRequest>,
+) -> Result<Json<SessionResponse>> {
+    payload.validate()?;
+    let tokens = SessionTokens::generate();
+
+    // Rotating both hashes in place means the presented refresh token (and the access
+    // token issued with it) stop working the moment new ones are handed out.
+    let session = sqlx::query!(
+        r#"
+        UPDATE sessions
+        SET
+            access_token_hash = $2,
+            access_token_expires_at = $3,
+            refresh_token_hash = $4,
+            refresh_token_expires_at = $5
+        WHERE refresh_token_hash = $1
+          AND revoked_at IS NULL
+          AND refresh_token_expires_at > NOW()
+        RETURNING id, wallet_address
+        "#,
+        hash_token(&payload.refresh_token),
+        tokens.access_token_hash(),
+        tokens.access_token_expires_at,
+        tokens.refresh_token_hash(),
+        tokens.refresh_token_expires_at
+    )
+    .fetch_optional(&state.db.pool)
+    .await?
+    .ok_or(Error::Unauthorized)?;
+
+    tracing::info!(session_id = %session.id, "Session refreshed");
+
+    Ok(Json(
+        tokens.into_response(session.id, session.wallet_address),
+    ))
+}
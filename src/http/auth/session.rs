@@ -0,0 +1,110 @@
+use crate::{
+    AppState, Error, Result,
+    http::auth::{ACCESS_TOKEN_TTL_MINUTES, REFRESH_TOKEN_TTL_DAYS, Session, SessionResponse},
+};
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::{header::AUTHORIZATION, request::Parts},
+    middleware::Next,
+    response::Response,
+};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Resolves `Authorization: Token <access token>` into a [`Session`].
+///
+/// Requests without the header pass through untouched so signature authentication and
+/// public routes keep working. A header with an unknown, expired or revoked token is
+/// rejected outright rather than silently treated as anonymous.
+pub async fn session_layer(
+    State(state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response> {
+    if let Some(header) = req.headers().get(AUTHORIZATION) {
+        let token = header
+            .to_str()
+            .ok()
+            .and_then(|value| value.strip_prefix("Token "))
+            .ok_or(Error::Unauthorized)?;
+
+        let session = sqlx::query_as!(
+            Session,
+            r#"
+            SELECT id, wallet_address
+            FROM sessions
+            WHERE access_token_hash = $1
+              AND revoked_at IS NULL
+              AND access_token_expires_at > NOW()
+            "#,
+            hash_token(token.trim())
+        )
+        .fetch_optional(&state.db.pool)
+        .await?
+        .ok_or(Error::Unauthorized)?;
+
+        req.extensions_mut().insert(session);
+    }
+
+    Ok(next.run(req).await)
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Session {
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self> {
+        parts
+            .extensions
+            .get::<Session>()
+            .cloned()
+            .ok_or(Error::Unauthorized)
+    }
+}
+
+/// A freshly generated access/refresh token pair. Only the hashes are ever persisted.
+pub(crate) struct SessionTokens {
+    pub access_token: String,
+    pub access_token_expires_at: chrono::DateTime<chrono::Utc>,
+    pub refresh_token: String,
+    pub refresh_token_expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl SessionTokens {
+    pub fn generate() -> Self {
+        let now = chrono::Utc::now();
+        Self {
+            access_token: generate_token(),
+            access_token_expires_at: now + chrono::Duration::minutes(ACCESS_TOKEN_TTL_MINUTES),
+            refresh_token: generate_token(),
+            refresh_token_expires_at: now + chrono::Duration::days(REFRESH_TOKEN_TTL_DAYS),
+        }
+    }
+
+    pub fn access_token_hash(&self) -> String {
+        hash_token(&self.access_token)
+    }
+
+    pub fn refresh_token_hash(&self) -> String {
+        hash_token(&self.refresh_token)
+    }
+
+    pub fn into_response(self, session_id: Uuid, wallet_address: String) -> SessionResponse {
+        SessionResponse {
+            session_id,
+            wallet_address,
+            access_token: self.access_token,
+            access_token_expires_at: self.access_token_expires_at,
+            refresh_token: self.refresh_token,
+            refresh_token_expires_at: self.refresh_token_expires_at,
+        }
+    }
+}
+
+fn generate_token() -> String {
+    hex::encode(rand::rng().random::<[u8; 32]>())
+}
+
+pub(crate) fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
@@ -1,67 +1,113 @@
 use crate::{
     AppState, Error, Result,
-    http::escrow::{AllocateBountyRequest, generate_transaction_hash},
+    http::{
+        auth::AuthenticatedWallet,
+        escrow::{AllocateBountyRequest, generate_transaction_hash},
+        project::{ProjectRole, ensure_project_role},
+    },
+    ledger::{LedgerAccount, add_bounty_funds, debit_balance, record_transfer},
 };
 use axum::{Json, extract::State, http::StatusCode};
+use bigdecimal::{BigDecimal, Zero};
 use garde::Validate;
 
 #[tracing::instrument(name = "allocate_bounty_handler", skip(state, payload))]
 pub async fn allocate_bounty_handler(
     State(state): State<AppState>,
+    AuthenticatedWallet(wallet_address): AuthenticatedWallet,
     Json(payload): Json<AllocateBountyRequest>,
 ) -> Result<StatusCode> {
     payload.validate()?;
     let tx_hash = generate_transaction_hash();
     let mut tx = state.db.pool.begin().await?;
 
-    let result = sqlx::query_scalar!(
+    let project = sqlx::query!(
         r#"
-        WITH escrow_update AS (
-            UPDATE escrow_users
-            SET balance = balance - $1,
-                updated_at = NOW()
-            WHERE wallet_address = $2
-              AND balance >= $1
-            RETURNING 1
-        ),
-        project_update AS (
+        SELECT id, bounty_amount, bounty_currency
+        FROM projects
+        WHERE contract_address = $1
+        FOR UPDATE
+        "#,
+        payload.project_contract_address
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(project) = project else {
+        tracing::error!("Failed to allocate bounty: project not found");
+        return Err(Error::InvalidRequest("Project not found".to_string()));
+    };
+    ensure_project_role(
+        &mut *tx,
+        project.id,
+        &wallet_address,
+        ProjectRole::Maintainer,
+    )
+    .await?;
+
+    // A bounty that still holds funds cannot change currency under the researchers' feet.
+    if let Some(currency) = &project.bounty_currency
+        && currency != &payload.currency
+        && project.bounty_amount.unwrap_or_default() > BigDecimal::zero()
+    {
+        tracing::warn!(
+            project_id = %project.id,
+            bounty_currency = %currency,
+            currency = %payload.currency,
+            "Bounty currency mismatch"
+        );
+        return Err(Error::conflict(
+            "bounty_currency_mismatch",
+            format!("the project's bounty is held in {currency}"),
+        ));
+    }
+
+    if !debit_balance(&mut tx, &wallet_address, &payload.currency, &payload.amount).await? {
+        tracing::error!("Failed to allocate bounty: insufficient balance");
+        return Err(Error::InvalidRequest("Insufficient balance".to_string()));
+    }
+
+    let transaction_id = sqlx::query_scalar!(
+        r#"
+        WITH project_update AS (
             UPDATE projects
             SET bounty_amount = COALESCE(bounty_amount, 0) + $1,
                 bounty_currency = $3,
                 bounty_expiry_date = $4,
                 updated_at = NOW()
-            WHERE contract_address = $5
-              AND EXISTS (SELECT 1 FROM escrow_update)
+            WHERE id = $5
             RETURNING id
-        ),
-        transaction_insert AS (
-            INSERT INTO escrow_transactions (
-                wallet_address, project_id, type, amount, currency,
-                transaction_hash, status, notes
-            )
-            SELECT $2, id, 'bounty_allocation', $1, $3, $6, 'completed', $7
-            FROM project_update
         )
-        SELECT COUNT(*) FROM escrow_update
+        INSERT INTO escrow_transactions (
+            wallet_address, project_id, type, amount, currency,
+            transaction_hash, status, notes
+        )
+        SELECT $2, id, 'bounty_allocation', $1, $3, $6, 'completed', $7
+        FROM project_update
+        RETURNING id
         "#,
         payload.amount,
-        payload.wallet_address,
+        wallet_address,
         payload.currency,
         payload.bounty_expiry_date,
-        payload.project_contract_address,
+        project.id,
         tx_hash,
         "Bounty allocated to project via escrow"
     )
     .fetch_one(&mut *tx)
     .await?;
 
-    if result.unwrap_or(0) == 0 {
-        tracing::error!("Failed to allocate bounty: insufficient balance or project not found");
-        tx.rollback().await?;
-        return Err(Error::InvalidRequest(
-            "Insufficient balance or project not found".to_string(),
-        ));
-    }
+    add_bounty_funds(&mut tx, project.id, &wallet_address, &payload.amount).await?;
+    record_transfer(
+        &mut tx,
+        LedgerAccount::User(&wallet_address),
+        LedgerAccount::ProjectPool(project.id),
+        &payload.currency,
+        &payload.amount,
+        Some(transaction_id),
+        "Bounty allocation",
+    )
+    .await?;
 
     tx.commit().await?;
     Ok(StatusCode::OK)
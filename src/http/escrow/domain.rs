@@ -6,8 +6,6 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct AllocateBountyRequest {
-    #[garde(custom(validate_starknet_address))]
-    pub wallet_address: String,
     #[garde(custom(validate_starknet_address))]
     pub project_contract_address: String,
     #[garde(custom(validate_amount))]
@@ -1,14 +1,15 @@
 mod allocate_bounty;
 mod domain;
 
-use axum::{Router, routing::post};
+use axum::{Router, middleware::from_fn_with_state, routing::post};
 pub use domain::*;
 
-use crate::AppState;
+use crate::{AppState, http::idempotency::idempotency_layer};
 
-pub(crate) fn router() -> Router<AppState> {
+pub(crate) fn router(app_state: &AppState) -> Router<AppState> {
     Router::new().route(
         "/allocate_bounty",
-        post(allocate_bounty::allocate_bounty_handler),
+        post(allocate_bounty::allocate_bounty_handler)
+            .layer(from_fn_with_state(app_state.clone(), idempotency_layer)),
     )
 }
@@ -0,0 +1,227 @@
+use crate::{
+    AppState, Error, Result, http::auth::AuthenticatedWallet, jobs::IDEMPOTENCY_KEY_TTL_HOURS,
+};
+use anyhow::Context;
+use axum::{
+    body::{Body, Bytes, to_bytes},
+    extract::{FromRequestParts, Request, State},
+    http::{HeaderValue, StatusCode, header::CONTENT_TYPE},
+    middleware::Next,
+    response::Response,
+};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Set on responses replayed from an earlier request with the same key.
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Largest request body that is fingerprinted.
+const MAX_REQUEST_BYTES: usize = 1024 * 1024;
+
+/// Makes a route safe to retry when the client sends an `Idempotency-Key` header.
+///
+/// Keys are scoped to the authenticated wallet. The first request under a key is executed and its
+/// response stored; retries with the same payload get that response back without running the
+/// handler again. Reusing a key for a different request is rejected with `422`, and retrying while
+/// the first request is still running with `409`. Server errors are not stored, so a request that
+/// failed that way, or whose handler panicked, can be retried under the same key. Expired keys are
+/// deleted by [`spawn_idempotency_key_sweep_job`](crate::jobs::spawn_idempotency_key_sweep_job).
+pub async fn idempotency_layer(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Result<Response> {
+    let Some(key) = req.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(next.run(req).await);
+    };
+    let key = key
+        .to_str()
+        .ok()
+        .filter(|key| !key.is_empty() && key.len() <= 255)
+        .ok_or_else(|| {
+            Error::InvalidRequest(
+                "Idempotency-Key must be between 1 and 255 visible ASCII characters".to_string(),
+            )
+        })?
+        .to_owned();
+
+    // The caller is authenticated here rather than in the handler, because keys belong to a wallet.
+    // The handler picks the wallet up from the request extensions.
+    let (mut parts, body) = req.into_parts();
+    let wallet = AuthenticatedWallet::from_request_parts(&mut parts, &state).await?;
+    parts.extensions.insert(wallet.clone());
+    let AuthenticatedWallet(wallet_address) = wallet;
+
+    let body = to_bytes(body, MAX_REQUEST_BYTES)
+        .await
+        .map_err(|_| Error::InvalidRequest("Request body is too large".to_string()))?;
+    let mut hasher = Sha256::new();
+    hasher.update(parts.method.as_str());
+    hasher.update(b"\n");
+    hasher.update(parts.uri.path());
+    hasher.update(b"\n");
+    hasher.update(&body);
+    let request_hash = hex::encode(hasher.finalize());
+
+    let pool = state.db.pool;
+    // A key that expired but was not swept yet is claimed anew. The sweep job deletes the rest.
+    let claimed = sqlx::query_scalar!(
+        r#"
+        INSERT INTO idempotency_keys (wallet_address, idempotency_key, request_hash)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (wallet_address, idempotency_key) DO UPDATE
+        SET request_hash = EXCLUDED.request_hash,
+            response_status = NULL,
+            response_content_type = NULL,
+            response_body = NULL,
+            created_at = NOW(),
+            completed_at = NULL
+        WHERE idempotency_keys.created_at < NOW() - make_interval(hours => $4)
+        RETURNING idempotency_key
+        "#,
+        wallet_address,
+        key,
+        request_hash,
+        IDEMPOTENCY_KEY_TTL_HOURS
+    )
+    .fetch_optional(&pool)
+    .await?;
+
+    if claimed.is_none() {
+        return replay(&pool, &wallet_address, &key, &request_hash).await;
+    }
+
+    // Run to completion even if the client gives up or the request times out, so the outcome of a
+    // handler that already moved funds is always stored.
+    let req = Request::from_parts(parts, Body::from(body));
+    let task = tokio::spawn({
+        let (pool, wallet_address, key) = (pool.clone(), wallet_address.clone(), key.clone());
+        async move {
+            let response = next.run(req).await;
+            store(&pool, &wallet_address, &key, response).await
+        }
+    });
+    match task.await {
+        Ok(response) => response,
+        // A panicking handler stored nothing, so the key is released for the client to retry.
+        Err(e) => {
+            release(&pool, &wallet_address, &key).await?;
+            Err(anyhow::Error::new(e)
+                .context("idempotent request panicked")
+                .into())
+        }
+    }
+}
+
+/// Answers a request under a key that was used before.
+async fn replay(
+    pool: &PgPool,
+    wallet_address: &str,
+    key: &str,
+    request_hash: &str,
+) -> Result<Response> {
+    let stored = sqlx::query!(
+        r#"
+        SELECT request_hash, response_status, response_content_type, response_body
+        FROM idempotency_keys
+        WHERE wallet_address = $1 AND idempotency_key = $2
+        "#,
+        wallet_address,
+        key
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    // Deleted because the first request failed in the meantime.
+    let Some(stored) = stored else {
+        return Err(Error::conflict(
+            "idempotency_key_in_use",
+            "the request under this Idempotency-Key failed, retry it",
+        ));
+    };
+
+    if stored.request_hash != request_hash {
+        tracing::warn!(wallet = %wallet_address, key, "Idempotency-Key reused for another request");
+        return Err(Error::unprocessable_entity([(
+            "idempotency_key",
+            "was already used for a different request",
+        )]));
+    }
+
+    let Some(status) = stored.response_status else {
+        return Err(Error::conflict(
+            "idempotency_key_in_use",
+            "a request with this Idempotency-Key is still being processed",
+        ));
+    };
+
+    let mut response = Response::builder()
+        .status(StatusCode::from_u16(status as u16).context("invalid stored status")?)
+        .header(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    if let Some(content_type) = stored.response_content_type {
+        response = response.header(CONTENT_TYPE, content_type);
+    }
+    Ok(response
+        .body(Body::from(stored.response_body.unwrap_or_default()))
+        .context("failed to build replayed response")?)
+}
+
+/// Stores `response` under the key, or releases the key if the request failed on our side.
+async fn store(
+    pool: &PgPool,
+    wallet_address: &str,
+    key: &str,
+    response: Response,
+) -> Result<Response> {
+    if response.status().is_server_error() {
+        release(pool, wallet_address, key).await?;
+        return Ok(response);
+    }
+
+    let (parts, body) = response.into_parts();
+    let body: Bytes = to_bytes(body, usize::MAX)
+        .await
+        .context("failed to read response body")?;
+    let content_type = parts
+        .headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+
+    sqlx::query!(
+        r#"
+        UPDATE idempotency_keys
+        SET response_status = $3,
+            response_content_type = $4,
+            response_body = $5,
+            completed_at = NOW()
+        WHERE wallet_address = $1 AND idempotency_key = $2
+        "#,
+        wallet_address,
+        key,
+        parts.status.as_u16() as i16,
+        content_type,
+        body.as_ref()
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+/// Forgets the key, so the request can be retried under it.
+async fn release(pool: &PgPool, wallet_address: &str, key: &str) -> Result<()> {
+    sqlx::query!(
+        r#"
+        DELETE FROM idempotency_keys
+        WHERE wallet_address = $1 AND idempotency_key = $2
+        "#,
+        wallet_address,
+        key
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use axum::{Json, extract::State};
+
+use crate::{
+    AppState, Result,
+    http::role::{Admin, RequireRole},
+    ledger::{LedgerCheck, check_ledger},
+};
+
+/// GET /admin/ledger/check
+///
+/// Runs the ledger invariant checks and reports every violation found.
+#[tracing::instrument(name = "check_ledger_handler", skip(state))]
+pub async fn check_ledger_handler(
+    State(state): State<AppState>,
+    _admin: RequireRole<Admin>,
+) -> Result<Json<LedgerCheck>> {
+    let check = check_ledger(&state.db.pool).await?;
+    Ok(Json(check))
+}
@@ -0,0 +1,18 @@
+mod check_ledger;
+mod reconciliation_report;
+
+use axum::{Router, routing::get};
+
+use crate::AppState;
+
+pub(crate) fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/admin/ledger/check",
+            get(check_ledger::check_ledger_handler),
+        )
+        .route(
+            "/admin/reconciliation",
+            get(reconciliation_report::reconciliation_report_handler),
+        )
+}
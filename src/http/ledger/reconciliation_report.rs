@@ -0,0 +1,22 @@
+use axum::{Json, extract::State};
+
+use crate::{
+    AppState, Error, Result,
+    http::role::{Admin, RequireRole},
+    jobs::{ReconciliationReport, latest_reconciliation},
+};
+
+/// GET /admin/reconciliation
+///
+/// The latest reconciliation of escrow balances and project bounties against their transactions,
+/// with every mismatch it recorded. `404` until the job ran once.
+#[tracing::instrument(name = "reconciliation_report_handler", skip(state))]
+pub async fn reconciliation_report_handler(
+    State(state): State<AppState>,
+    _admin: RequireRole<Admin>,
+) -> Result<Json<ReconciliationReport>> {
+    let report = latest_reconciliation(&state.db.pool)
+        .await?
+        .ok_or(Error::NotFound)?;
+    Ok(Json(report))
+}
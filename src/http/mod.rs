@@ -3,20 +3,38 @@ use crate::{
     Config, cors_layer, normalize_path_layer, propagate_request_id_layer, request_id_layer,
     timeout_layer,
 };
-use crate::{Configuration, db::Db};
+use crate::{
+    Configuration, Environment,
+    chain::{RpcConfirmationSource, RpcSignatureVerifier, SignatureVerifier},
+    db::Db,
+    jobs::{
+        ChainWatcher, PayoutWorker, spawn_bounty_expiry_job, spawn_chain_watcher,
+        spawn_confirmation_job, spawn_idempotency_key_sweep_job, spawn_payout_worker,
+        spawn_reconciliation_job,
+    },
+    verification::OwnershipVerifier,
+};
 use anyhow::Context;
-use axum::Router;
+use axum::{Router, middleware::from_fn_with_state};
 use std::sync::Arc;
 use tokio::{net::TcpListener, signal};
 
 pub use crate::error::{Error, ResultExt};
+pub(crate) use escrow::generate_transaction_hash;
+pub use idempotency::{IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER};
+pub(crate) use transaction::validate_currency;
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
+mod auth;
 mod escrow;
 mod health_check;
+mod idempotency;
+mod ledger;
 pub mod newsletter;
 mod project;
 mod report;
+pub mod role;
+mod search;
 mod support_ticket;
 mod transaction;
 mod types;
@@ -26,11 +44,43 @@ mod validator;
 pub struct AppState {
     pub db: Db,
     pub configuration: Config,
+    pub signature_verifier: Arc<dyn SignatureVerifier>,
+    pub ownership_verifier: Arc<OwnershipVerifier>,
 }
 
 pub async fn serve(configuration: Arc<Configuration>, db: Db) -> anyhow::Result<()> {
     let addr = configuration.listen_address;
-    let app_state = AppState { configuration, db };
+    let signature_verifier = Arc::new(RpcSignatureVerifier::new(&configuration.starknet_rpc_url)?);
+    let ownership_verifier = Arc::new(OwnershipVerifier::from_configuration(
+        &configuration,
+        signature_verifier.clone(),
+    )?);
+    let confirmation_source =
+        Arc::new(RpcConfirmationSource::new(&configuration.starknet_rpc_url)?);
+    spawn_confirmation_job(db.pool.clone(), confirmation_source);
+    // Deposits are only credited from the escrow contract's events.
+    match ChainWatcher::new(db.pool.clone(), &configuration)? {
+        Some(chain_watcher) => {
+            spawn_chain_watcher(chain_watcher);
+        }
+        None if matches!(configuration.env, Environment::Production) => {
+            anyhow::bail!("ESCROW_CONTRACT_ADDRESS must be set for deposits to be credited.");
+        }
+        None => tracing::warn!("ESCROW_CONTRACT_ADDRESS is not set, deposits stay pending"),
+    }
+    if let Some(payout_worker) = PayoutWorker::from_configuration(db.pool.clone(), &configuration)?
+    {
+        spawn_payout_worker(payout_worker);
+    }
+    spawn_reconciliation_job(db.pool.clone());
+    spawn_bounty_expiry_job(db.pool.clone());
+    spawn_idempotency_key_sweep_job(db.pool.clone());
+    let app_state = AppState {
+        configuration,
+        db,
+        signature_verifier,
+        ownership_verifier,
+    };
     let app = api_router(app_state);
     tracing::info!("Listening for requests on {}", addr);
     let listener = TcpListener::bind(addr).await?;
@@ -50,13 +100,17 @@ pub fn api_router(app_state: AppState) -> Router {
 
     Router::new()
         .merge(health_check::router())
+        .merge(auth::router())
         .merge(project::router())
-        .merge(transaction::router())
+        .merge(transaction::router(&app_state))
         .merge(support_ticket::router())
-        .merge(escrow::router())
+        .merge(escrow::router(&app_state))
         .merge(newsletter::router())
         .merge(validator::router())
         .merge(report::router())
+        .merge(role::router())
+        .merge(ledger::router())
+        .layer(from_fn_with_state(app_state.clone(), auth::session_layer))
         .layer(trace_layer)
         .layer(request_id_layer)
         .layer(propagate_request_id_layer)
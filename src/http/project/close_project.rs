@@ -1,81 +1,100 @@
-use crate::{AppState, Error, Result, http::project::ClosedProjectRequest};
+use crate::{
+    AppState, Error, Result,
+    http::{
+        auth::AuthenticatedWallet,
+        project::{ClosedProjectRequest, ProjectRole, shared::ensure_project_role},
+    },
+    jobs::refund_bounty,
+};
 use axum::{Json, extract::State, http::StatusCode};
+use bigdecimal::{BigDecimal, Zero};
 use garde::Validate;
 
+/// POST /closed_project
+///
+/// Closes the project for its owner or a maintainer. What is left of the bounty is refunded to
+/// those who funded it, each up to their share, and any rest to the owner, never to the maintainer
+/// closing it. A project is only closed once all its reports are rejected or closed.
 #[tracing::instrument(name = "close_project", skip(state, payload))]
 pub async fn close_project_handler(
     state: State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
     Json(payload): Json<ClosedProjectRequest>,
 ) -> Result<axum::http::StatusCode> {
     payload.validate()?;
     let mut tx = state.db.pool.begin().await?;
 
-    let result = sqlx::query!(
+    let project = sqlx::query!(
         r#"
-            WITH project AS (
-                SELECT
-                    p.owner_address,
-                    p.bounty_amount,
-                    p.closed_at,
-                    EXISTS (SELECT 1 FROM escrow_users u WHERE u.wallet_address = $1) AS user_exists
-                FROM projects p
-                WHERE p.id = $2
-                FOR UPDATE
-            ),
-            escrow_update AS (
-                UPDATE escrow_users
-                SET balance = COALESCE(balance, 0) + (
-                    SELECT COALESCE(bounty_amount, 0)
-                    FROM project
-                    WHERE user_exists
-                        AND owner_address = $1
-                        AND closed_at IS NULL
-                        AND COALESCE(bounty_amount, 0) > 0
-                )
-                WHERE wallet_address = $1
-                AND (SELECT user_exists FROM project)
-                AND (SELECT COALESCE(bounty_amount, 0) > 0 FROM project)
-                AND (SELECT closed_at IS NULL FROM project)
-                RETURNING 1
-            ),
-            project_update AS (
-                UPDATE projects
-                SET closed_at = now()
-                WHERE id = $2
-                AND EXISTS (SELECT 1 FROM project
-                            WHERE owner_address = $1
-                            AND user_exists
-                            AND closed_at IS NULL)
-                RETURNING 1
-            )
-            SELECT
-                (SELECT owner_address FROM project) AS owner_address,
-                (SELECT closed_at FROM project) AS closed_at,
-                (SELECT user_exists FROM project) AS user_exists,
-                (SELECT COUNT(*) FROM project_update) AS projects_updated
-            "#,
-        payload.owner_address,
+        SELECT owner_address, bounty_amount, bounty_currency, closed_at
+        FROM projects
+        WHERE id = $1
+        FOR UPDATE
+        "#,
         payload.project_id
     )
     .fetch_optional(&mut *tx)
-    .await?;
+    .await?
+    .ok_or(Error::NotFound)?;
 
-    let Some(result) = result else {
-        return Err(Error::NotFound);
-    };
+    ensure_project_role(
+        &mut *tx,
+        payload.project_id,
+        &caller,
+        ProjectRole::Maintainer,
+    )
+    .await?;
+    if project.closed_at.is_some() {
+        return Err(Error::Forbidden);
+    }
 
-    match (result.owner_address, result.user_exists, result.closed_at) {
-        (Some(owner_address), _, _) if owner_address != payload.owner_address => {
-            return Err(Error::Unauthorized);
-        }
-        (_, Some(false), _) | (_, _, Some(_)) => return Err(Error::Forbidden),
-        _ => {}
-    };
+    // An accepted report still has to be paid from the bounty, and one in review may be accepted.
+    let has_open_reports = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM research_report
+            WHERE project_id = $1 AND status NOT IN ('rejected', 'closed')
+        ) AS "exists!"
+        "#,
+        payload.project_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if has_open_reports {
+        return Err(Error::conflict(
+            "project_has_open_reports",
+            "every report must be rejected or closed before the project is closed",
+        ));
+    }
 
-    if result.projects_updated.unwrap_or(0) == 0 {
-        return Err(Error::NotFound);
+    // Whatever is left of the bounty goes back to its funders, in the currency it was allocated in.
+    if let (Some(amount), Some(currency)) = (&project.bounty_amount, &project.bounty_currency)
+        && amount > &BigDecimal::zero()
+    {
+        refund_bounty(
+            &mut tx,
+            payload.project_id,
+            &project.owner_address,
+            currency,
+            amount,
+            "Bounty refunded on project close",
+        )
+        .await?;
     }
 
+    sqlx::query!(
+        r#"
+        UPDATE projects
+        SET closed_at = now(),
+            bounty_amount = CASE WHEN bounty_amount IS NULL THEN NULL ELSE 0 END
+        WHERE id = $1
+        "#,
+        payload.project_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
     tx.commit().await?;
     Ok(StatusCode::OK)
 }
@@ -1,10 +1,23 @@
-use crate::{AppState, Error, Result, ResultExt, http::project::CreateProjectRequest};
+use crate::{
+    AppState, Error, Result, ResultExt,
+    http::{
+        auth::AuthenticatedWallet,
+        escrow::generate_transaction_hash,
+        project::{CreateProjectRequest, normalize_tags, shared::set_project_tags},
+    },
+    ledger::{LedgerAccount, add_bounty_funds, debit_balance, record_transfer},
+};
 use axum::{Json, extract::State, http::StatusCode};
 use garde::Validate;
 
+/// POST /create_project
+///
+/// Creates a project owned by the caller. A bounty given here is funded from the owner's escrow
+/// balance, the same way `/allocate_bounty` funds one.
 #[tracing::instrument(name = "create_project", skip(state, payload))]
 pub async fn create_project_handler(
     state: State<AppState>,
+    AuthenticatedWallet(owner_address): AuthenticatedWallet,
     Json(payload): Json<CreateProjectRequest>,
 ) -> Result<(StatusCode, String)> {
     payload.validate()?;
@@ -32,7 +45,7 @@ pub async fn create_project_handler(
         ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
         RETURNING id
         "#,
-        payload.owner_address,
+        owner_address,
         payload.contract_address,
         payload.name,
         payload.description,
@@ -48,46 +61,45 @@ pub async fn create_project_handler(
     .await
     .on_constraint("projects_contract_address_key", |_| Error::Conflict)?;
 
-    if !payload.tags.is_empty() {
-        let tag_ids: Vec<i32> = sqlx::query_scalar!(
+    if let (Some(amount), Some(currency)) = (&payload.bounty_amount, &payload.bounty_currency) {
+        if !debit_balance(&mut tx, &owner_address, currency, amount).await? {
+            tracing::error!("Failed to fund bounty: insufficient balance");
+            return Err(Error::InvalidRequest("Insufficient balance".to_string()));
+        }
+
+        let transaction_id = sqlx::query_scalar!(
             r#"
-            WITH input_tags(tag_name) AS (
-                SELECT unnest($1::text[])
-            ),
-            inserted_tags AS (
-                INSERT INTO tags (name)
-                SELECT tag_name FROM input_tags
-                ON CONFLICT (name) DO NOTHING
-                RETURNING id
-            )
-            SELECT id FROM inserted_tags
-            UNION
-            SELECT t.id FROM tags t
-            JOIN input_tags it ON t.name = it.tag_name
+            INSERT INTO escrow_transactions (
+                wallet_address, project_id, type, amount, currency,
+                transaction_hash, status, notes
+            ) VALUES ($1, $2, 'bounty_allocation', $3, $4, $5, 'completed', $6)
+            RETURNING id
             "#,
-            &payload.tags as &[String]
+            owner_address,
+            project_id,
+            amount,
+            currency,
+            generate_transaction_hash(),
+            "Bounty allocated to project on creation"
         )
-        .fetch_all(&mut *tx)
-        .await?
-        .into_iter()
-        .flatten()
-        .collect();
+        .fetch_one(&mut *tx)
+        .await?;
 
-        if !tag_ids.is_empty() {
-            sqlx::query!(
-                r#"
-                INSERT INTO project_tags (project_id, tag_id)
-                SELECT $1, unnest($2::int[])
-                ON CONFLICT DO NOTHING
-                "#,
-                project_id,
-                &tag_ids
-            )
-            .execute(&mut *tx)
-            .await?;
-        }
+        add_bounty_funds(&mut tx, project_id, &owner_address, amount).await?;
+        record_transfer(
+            &mut tx,
+            LedgerAccount::User(&owner_address),
+            LedgerAccount::ProjectPool(project_id),
+            currency,
+            amount,
+            Some(transaction_id),
+            "Bounty allocation",
+        )
+        .await?;
     }
 
+    set_project_tags(&mut tx, project_id, &normalize_tags(payload.tags)).await?;
+
     tx.commit().await?;
     Ok((
         StatusCode::CREATED,
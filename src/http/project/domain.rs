@@ -4,23 +4,21 @@ use garde::Validate;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::http::report::SeverityLevel;
+
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct ClosedProjectRequest {
     #[garde(skip)]
     pub project_id: Uuid,
-    #[garde(custom(validate_starknet_address))]
-    pub owner_address: String,
 }
 
 #[derive(Debug, Deserialize, Validate)]
 pub struct VerifyProjectRequest {
     #[garde(url)]
     pub repository_url: String,
-    #[garde(custom(validate_starknet_address))]
-    pub owner_address: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, sqlx::FromRow)]
 pub struct ProjectResponse {
     pub id: Uuid,
     pub name: String,
@@ -30,20 +28,244 @@ pub struct ProjectResponse {
     pub is_verified: bool,
     pub verification_date: Option<chrono::DateTime<chrono::Utc>>,
     pub repository_url: Option<String>,
+    pub tags: Vec<String>,
+    pub bounty_amount: Option<BigDecimal>,
+    pub bounty_currency: Option<String>,
+    pub bounty_expiry_date: Option<DateTime<Utc>>,
+    pub status: ProjectStatus,
+    pub paused_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Mirrors the `project_status` Postgres enum. Derived from `closed_at` and `paused_at`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "project_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectStatus {
+    Active,
+    /// Not taking new reports; the bounty stays escrowed and open reports carry on.
+    Paused,
+    Closed,
+}
+
+/// Order of `GET /projects`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectSort {
+    /// Newest first.
+    #[default]
+    CreatedAt,
+    /// Largest bounty first; projects without one come last.
+    BountyAmount,
+    /// Soonest expiring bounty first; projects without one come last.
+    BountyExpiry,
+    /// Best match for `q` first.
+    Relevance,
+}
+
+impl ProjectSort {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CreatedAt => "created_at",
+            Self::BountyAmount => "bounty_amount",
+            Self::BountyExpiry => "bounty_expiry",
+            Self::Relevance => "relevance",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ListProjectsQuery {
+    /// Full-text search over name and description, in web search syntax.
+    #[garde(inner(length(min = 1, max = 200)))]
+    pub q: Option<String>,
+    #[garde(skip)]
+    pub verified: Option<bool>,
+    /// A bounty is active while it has funds left, has not expired and the project is open.
+    #[garde(skip)]
+    pub has_active_bounty: Option<bool>,
+    #[garde(custom(validate_bounty_currency))]
+    pub currency: Option<String>,
+    /// Comma separated tag names; projects must carry all of them.
+    #[garde(inner(length(min = 1, max = 500)))]
+    pub tags: Option<String>,
+    #[garde(inner(custom(validate_starknet_address)))]
+    pub owner_address: Option<String>,
+    #[garde(skip)]
+    pub closed: Option<bool>,
+    #[garde(skip)]
+    pub status: Option<ProjectStatus>,
+    /// Exclusive upper bound on `bounty_expiry_date`.
+    #[garde(skip)]
+    pub expires_before: Option<DateTime<Utc>>,
+    /// Defaults to `relevance` when searching and `created_at` otherwise.
+    #[garde(skip)]
+    pub sort: Option<ProjectSort>,
+    #[garde(inner(custom(validate_project_cursor)))]
+    pub cursor: Option<String>,
+    #[garde(range(min = 1, max = 100))]
+    pub limit: Option<i64>,
+}
+
+impl ListProjectsQuery {
+    pub fn tag_names(&self) -> Option<Vec<String>> {
+        let names = self.tags.as_deref()?.split(',').map(str::to_string);
+        Some(normalize_tags(names))
+    }
+}
+
+/// Trims tag names and drops blank ones and those repeating an earlier name in another case, the
+/// way the `tags` table compares them. The rest are sorted case-insensitively.
+pub fn normalize_tags(tags: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut names: Vec<String> = tags
+        .into_iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    names.sort_by_key(|tag| tag.to_lowercase());
+    names.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    names
+}
+
+/// Position after the last project of a page, as `<sort key>_<id>`. The sort key depends on the
+/// page's sort and is empty for projects sorted last for lack of a bounty, so a cursor is only
+/// meaningful with the sort it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectCursor {
+    pub sort_key: Option<BigDecimal>,
+    pub id: Uuid,
+}
+
+impl ProjectCursor {
+    pub fn encode(&self) -> String {
+        match &self.sort_key {
+            Some(key) => format!("{key}_{}", self.id),
+            None => format!("_{}", self.id),
+        }
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let (key, id) = cursor.split_once('_')?;
+        Some(Self {
+            sort_key: match key {
+                "" => None,
+                key => Some(key.parse().ok()?),
+            },
+            id: id.parse().ok()?,
+        })
+    }
+}
+
+pub fn validate_project_cursor(cursor: &str, _context: &()) -> garde::Result {
+    ProjectCursor::decode(cursor)
+        .map(|_| ())
+        .ok_or_else(|| garde::Error::new("Invalid cursor"))
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ListedProject {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub project: ProjectResponse,
+    /// How well the project matches `q`. Only set when searching.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rank: Option<f32>,
+    /// HTML: the description, escaped, with the matched terms wrapped in `<mark>`. Only set when
+    /// searching.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+    #[serde(skip)]
+    pub sort_key: Option<BigDecimal>,
+}
+
 #[derive(Debug, Serialize)]
-pub struct VerifyProjectResponse {
-    pub message: String,
+pub struct ProjectPage {
+    pub projects: Vec<ListedProject>,
+    /// Pass as `cursor` to fetch the next page. Absent on the last page.
+    pub next_cursor: Option<String>,
+}
+
+/// Mirrors the `project_verification_status` Postgres enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "project_verification_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectVerificationStatus {
+    Pending,
+    Verified,
+    Rejected,
+}
+
+/// Mirrors the `project_verification_method` Postgres enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "project_verification_method", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectVerificationMethod {
+    RepositoryFile,
+    DeployerSignature,
+    Manual,
+}
+
+/// What the owner needs to prove control of the project.
+#[derive(Debug, Serialize)]
+pub struct ProjectVerificationChallenge {
+    pub verification_id: Uuid,
+    pub token: String,
+    pub repository_url: String,
+    /// Commit a file at this path in the repository, holding the token.
+    pub file_path: &'static str,
+    /// Or sign this SNIP-12 message with the account that deployed the contract. Absent when the
+    /// contract address is not a Starknet felt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typed_data: Option<serde_json::Value>,
+}
+
+/// The proof to check against the pending verification.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum CheckVerificationRequest {
+    RepositoryFile,
+    DeployerSignature {
+        deployment_transaction_hash: String,
+        /// Hex felts of the deployer's signature over the challenge's `typed_data`.
+        signature: Vec<String>,
+    },
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ProjectVerification {
+    pub id: Uuid,
     pub project_id: Uuid,
-    pub verified_at: chrono::DateTime<chrono::Utc>,
+    pub token: String,
+    pub repository_url: String,
+    pub status: ProjectVerificationStatus,
+    pub method: Option<ProjectVerificationMethod>,
+    pub reviewed_by: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListProjectVerificationsQuery {
+    /// Defaults to `pending`.
+    pub status: Option<ProjectVerificationStatus>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ApproveVerificationRequest {
+    #[garde(inner(length(min = 1, max = 1000)))]
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RejectVerificationRequest {
+    #[garde(length(min = 1, max = 1000))]
+    pub notes: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct CreateProjectRequest {
-    #[garde(custom(validate_starknet_address))]
-    pub owner_address: String,
     #[garde(custom(validate_starknet_address))]
     pub contract_address: String,
     #[garde(ascii, length(bytes, min = 3, max = 256))]
@@ -58,7 +280,7 @@ pub struct CreateProjectRequest {
     pub project_logo_path: Option<String>,
     #[garde(pattern(r#"^(https?|ftp)://[^\s/$.?#].[^\s]*$"#))]
     pub repository_url: Option<String>,
-    #[garde(length(min = 1), inner(ascii, length(min = 1)))]
+    #[garde(length(min = 1), inner(ascii, length(min = 1, max = 50)))]
     pub tags: Vec<String>,
     #[garde(custom(validate_bounty_amount))]
     pub bounty_amount: Option<BigDecimal>,
@@ -68,6 +290,258 @@ pub struct CreateProjectRequest {
     pub bounty_expiry_date: Option<DateTime<Utc>>,
 }
 
+/// The fields to change; absent ones are left as they are. `tags` replaces the whole set.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateProjectRequest {
+    /// Cannot be changed. Accepted only to reject it explicitly.
+    #[garde(skip)]
+    pub contract_address: Option<String>,
+    #[garde(ascii, length(bytes, min = 3, max = 256))]
+    pub name: Option<String>,
+    #[garde(length(min = 10, max = 500))]
+    pub description: Option<String>,
+    #[garde(pattern(r#"^[^@\s]+@[^@\s]+\.[^@\s]+$|^https?://.+$"#))]
+    pub contact_info: Option<String>,
+    #[garde(pattern(r#"^(https?|ftp)://[^\s/$.?#].[^\s]*$"#))]
+    pub supporting_document_path: Option<String>,
+    #[garde(pattern(r#"^(https?|ftp)://[^\s/$.?#].[^\s]*$"#))]
+    pub project_logo_path: Option<String>,
+    /// Cannot be changed once the project is verified.
+    #[garde(pattern(r#"^(https?|ftp)://[^\s/$.?#].[^\s]*$"#))]
+    pub repository_url: Option<String>,
+    #[garde(length(min = 1), inner(inner(ascii, length(min = 1, max = 50))))]
+    pub tags: Option<Vec<String>>,
+}
+
+/// One edit of a project.
+#[derive(Debug, Serialize)]
+pub struct ProjectRevision {
+    pub revision: i32,
+    pub changed_by: String,
+    /// The changed fields, as `{"<field>": {"from": <old>, "to": <new>}}`.
+    pub changes: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What a project pays out per severity of an accepted report.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct RewardMatrix {
+    #[garde(custom(validate_reward_amount))]
+    pub low: BigDecimal,
+    #[garde(custom(validate_reward_amount))]
+    pub medium: BigDecimal,
+    #[garde(custom(validate_reward_amount))]
+    pub high: BigDecimal,
+    #[garde(custom(validate_reward_amount))]
+    pub critical: BigDecimal,
+}
+
+impl RewardMatrix {
+    pub fn amount(&self, severity: SeverityLevel) -> &BigDecimal {
+        match severity {
+            SeverityLevel::Low => &self.low,
+            SeverityLevel::Medium => &self.medium,
+            SeverityLevel::High => &self.high,
+            SeverityLevel::Critical => &self.critical,
+        }
+    }
+}
+
+/// Mirrors the `project_member_role` Postgres enum. The owner is the project's `owner_address`; the
+/// other roles are granted by invitation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "project_member_role", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectRole {
+    Owner,
+    /// Verifies the project and funds its bounty alongside the owner.
+    Maintainer,
+    /// Reads the project's reports.
+    Viewer,
+}
+
+impl ProjectRole {
+    /// Whether this role may do everything `required` may.
+    pub fn includes(self, required: ProjectRole) -> bool {
+        self.rank() >= required.rank()
+    }
+
+    fn rank(self) -> u8 {
+        match self {
+            ProjectRole::Viewer => 0,
+            ProjectRole::Maintainer => 1,
+            ProjectRole::Owner => 2,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct InviteMemberRequest {
+    #[garde(custom(validate_starknet_address))]
+    pub wallet_address: String,
+    #[garde(custom(validate_invited_role))]
+    pub role: ProjectRole,
+}
+
+/// The owner, or a member or pending invitee of a project.
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ProjectMember {
+    pub wallet_address: String,
+    pub role: ProjectRole,
+    /// Unset for the owner.
+    pub invited_by: Option<String>,
+    pub invited_at: Option<DateTime<Utc>>,
+    /// Unset while the invitation is pending.
+    pub accepted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct TransferOwnershipRequest {
+    #[garde(custom(validate_starknet_address))]
+    pub new_owner_address: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OwnershipTransfer {
+    pub project_id: Uuid,
+    pub owner_address: String,
+    pub pending_owner_address: String,
+}
+
+fn validate_invited_role(role: &ProjectRole, _context: &()) -> garde::Result {
+    if *role == ProjectRole::Owner {
+        return Err(garde::Error::new(
+            "Ownership is transferred, not granted by invitation",
+        ));
+    }
+    Ok(())
+}
+
+/// Body of `POST /projects/{project_id}/bounty/extend`.
+#[derive(Debug, Deserialize, Validate)]
+pub struct ExtendBountyRequest {
+    /// Must be later than both now and the current expiry.
+    #[garde(skip)]
+    pub bounty_expiry_date: DateTime<Utc>,
+}
+
+/// Mirrors the `scope_asset_type` Postgres enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "scope_asset_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ScopeAssetType {
+    SmartContract,
+    Repository,
+    WebUrl,
+}
+
+/// Body of `PUT /projects/{project_id}/scope`. Publishes a new version of the scope.
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetProjectScopeRequest {
+    #[garde(length(min = 1, max = 10000))]
+    pub rules: Option<String>,
+    #[garde(length(min = 1, max = 100), dive)]
+    pub in_scope: Vec<InScopeAsset>,
+    #[serde(default)]
+    #[garde(length(max = 100), dive)]
+    pub out_of_scope: Vec<OutOfScopeItem>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct InScopeAsset {
+    #[garde(skip)]
+    pub asset_type: ScopeAssetType,
+    #[garde(custom(validate_scope_identifier(self.asset_type)))]
+    pub identifier: String,
+    #[garde(length(min = 1, max = 1000))]
+    pub description: Option<String>,
+    #[garde(skip)]
+    pub max_severity: SeverityLevel,
+}
+
+/// Something researchers must not test, such as third-party contracts or denial of service.
+#[derive(Debug, Deserialize, Validate)]
+pub struct OutOfScopeItem {
+    #[garde(skip)]
+    pub asset_type: Option<ScopeAssetType>,
+    #[garde(length(min = 1, max = 500))]
+    pub identifier: String,
+    #[garde(length(min = 1, max = 1000))]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ScopeAsset {
+    pub id: Uuid,
+    pub asset_type: Option<ScopeAssetType>,
+    pub identifier: String,
+    pub description: Option<String>,
+    /// Highest severity a finding against the asset can be accepted with. Only set in scope.
+    pub max_severity: Option<SeverityLevel>,
+}
+
+/// One version of what researchers may and may not test on a project.
+#[derive(Debug, Serialize)]
+pub struct ProjectScope {
+    pub version: i32,
+    pub rules: Option<String>,
+    pub in_scope: Vec<ScopeAsset>,
+    pub out_of_scope: Vec<ScopeAsset>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ProjectScopeQuery {
+    /// Defaults to the current version.
+    #[garde(range(min = 1))]
+    pub version: Option<i32>,
+}
+
+/// `GET /projects/{project_id}`: the project with its current scope, if it has defined one.
+#[derive(Debug, Serialize)]
+pub struct ProjectDetail {
+    #[serde(flatten)]
+    pub project: ProjectResponse,
+    pub scope: Option<ProjectScope>,
+}
+
+/// A tag and the number of projects carrying it.
+#[derive(Debug, Serialize)]
+pub struct TagUsage {
+    pub name: String,
+    pub project_count: i64,
+}
+
+/// Tags to add to a project; ones it already carries are ignored.
+#[derive(Debug, Deserialize, Validate)]
+pub struct AddProjectTagsRequest {
+    #[garde(length(min = 1, max = 20), inner(ascii, length(min = 1, max = 50)))]
+    pub tags: Vec<String>,
+}
+
+/// Folds the `source_tags` into `target_tag`, which is created if it does not exist. Naming an
+/// existing tag in another case renames it to that spelling.
+#[derive(Debug, Deserialize, Validate)]
+pub struct MergeTagsRequest {
+    #[garde(length(min = 1, max = 50), inner(ascii, length(min = 1, max = 50)))]
+    pub source_tags: Vec<String>,
+    #[garde(ascii, length(min = 1, max = 50))]
+    pub target_tag: String,
+}
+
+/// Contract addresses must be Starknet addresses; repositories and web assets must be URLs.
+fn validate_scope_identifier(
+    asset_type: ScopeAssetType,
+) -> impl FnOnce(&String, &()) -> garde::Result {
+    move |identifier, context| match asset_type {
+        ScopeAssetType::SmartContract => validate_starknet_address(identifier, context),
+        ScopeAssetType::Repository | ScopeAssetType::WebUrl => {
+            garde::rules::url::apply(identifier, ())
+        }
+    }
+}
+
 pub fn validate_starknet_address(addr: &str, _context: &()) -> garde::Result {
     if addr.starts_with("0x")
         && addr.len() == 66
@@ -86,21 +560,28 @@ pub fn validate_bounty_amount(amount: &Option<BigDecimal>, _ctx: &()) -> garde::
     Ok(())
 }
 
+pub fn validate_reward_amount(amount: &BigDecimal, _ctx: &()) -> garde::Result {
+    if amount < &BigDecimal::zero() {
+        return Err(garde::Error::new("Reward cannot be Negative"));
+    }
+    Ok(())
+}
+
 pub fn validate_bounty_currency(curr: &Option<String>, _context: &()) -> garde::Result {
     let supported_currencies = ["STRK", "USDC", "USDT"];
-    if let Some(currency) = curr {
-        if !supported_currencies.contains(&currency.as_str()) {
-            return Err(garde::Error::new("Invalid Bounty Currency Provided"));
-        }
+    if let Some(currency) = curr
+        && !supported_currencies.contains(&currency.as_str())
+    {
+        return Err(garde::Error::new("Invalid Bounty Currency Provided"));
     }
     Ok(())
 }
 
 pub fn validate_bounty_expiry_date(date: &Option<DateTime<Utc>>, _context: &()) -> garde::Result {
-    if let Some(date) = date {
-        if date < &Utc::now() {
-            return Err(garde::Error::new("Please Provide Valid Expiry"));
-        }
+    if let Some(date) = date
+        && date < &Utc::now()
+    {
+        return Err(garde::Error::new("Please Provide Valid Expiry"));
     }
     Ok(())
 }
@@ -0,0 +1,102 @@
+use crate::{
+    AppState, Error, Result,
+    http::{
+        auth::AuthenticatedWallet,
+        project::{
+            ExtendBountyRequest, ProjectResponse,
+            shared::{get_project_by_id, record_revision},
+        },
+    },
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use bigdecimal::{BigDecimal, Zero};
+use chrono::Utc;
+use garde::Validate;
+use serde_json::{Map, json};
+use uuid::Uuid;
+
+/// POST /projects/{project_id}/bounty/extend
+///
+/// Pushes back the expiry of the project's bounty, which is refunded to the owner once it passes.
+/// The change is recorded as a revision of the project.
+#[tracing::instrument(name = "extend_bounty_handler", skip(state, payload))]
+pub async fn extend_bounty_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<ExtendBountyRequest>,
+) -> Result<Json<ProjectResponse>> {
+    payload.validate()?;
+
+    let mut tx = state.db.pool.begin().await?;
+    let project = sqlx::query!(
+        r#"
+        SELECT owner_address, bounty_amount, bounty_expiry_date, closed_at
+        FROM projects
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+        project_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(Error::NotFound)?;
+
+    if project.owner_address != caller {
+        tracing::warn!(caller = %caller, "Only the owner can extend a bounty");
+        return Err(Error::Forbidden);
+    }
+    if project.closed_at.is_some() {
+        return Err(Error::conflict(
+            "project_closed",
+            "closed projects have no bounty to extend",
+        ));
+    }
+    if project
+        .bounty_amount
+        .is_none_or(|amount| amount <= BigDecimal::zero())
+    {
+        return Err(Error::conflict(
+            "no_bounty",
+            "the project has no bounty left to extend",
+        ));
+    }
+    if payload.bounty_expiry_date <= Utc::now()
+        || project
+            .bounty_expiry_date
+            .is_some_and(|expiry| payload.bounty_expiry_date <= expiry)
+    {
+        return Err(Error::unprocessable_entity([(
+            "bounty_expiry_date",
+            "must be later than now and than the current expiry",
+        )]));
+    }
+
+    sqlx::query!(
+        "UPDATE projects SET bounty_expiry_date = $2, updated_at = NOW() WHERE id = $1",
+        project_id,
+        payload.bounty_expiry_date
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let mut changes = Map::new();
+    changes.insert(
+        "bounty_expiry_date".to_string(),
+        json!({ "from": project.bounty_expiry_date, "to": payload.bounty_expiry_date }),
+    );
+    let revision = record_revision(&mut tx, project_id, &caller, changes).await?;
+    tx.commit().await?;
+
+    tracing::info!(
+        %project_id,
+        revision,
+        bounty_expiry_date = %payload.bounty_expiry_date,
+        "Bounty extended"
+    );
+
+    Ok(Json(get_project_by_id(&state.db.pool, project_id).await?))
+}
@@ -0,0 +1,147 @@
+use crate::{
+    AppState, Error, Result,
+    http::{
+        project::{ListProjectsQuery, ListedProject, ProjectCursor, ProjectPage, ProjectSort},
+        search::SNIPPET_OPTIONS,
+    },
+};
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use garde::Validate;
+
+/// GET /projects?q=reentrancy&verified=true&has_active_bounty=true&currency=STRK&tags=defi,nft&sort=bounty_amount&cursor=...&limit=20
+///
+/// Lists projects matching every given filter, newest first unless `sort` says otherwise. With
+/// `q`, only projects whose name or description match are listed, best match first, each with its
+/// rank and a highlighted snippet.
+#[tracing::instrument(name = "list_projects_handler", skip(state))]
+pub async fn list_projects_handler(
+    State(state): State<AppState>,
+    Query(params): Query<ListProjectsQuery>,
+) -> Result<Json<ProjectPage>> {
+    params.validate()?;
+
+    let sort = match (params.sort, &params.q) {
+        (Some(ProjectSort::Relevance), None) => {
+            return Err(Error::InvalidRequest(
+                "Sorting by relevance needs a search query".to_string(),
+            ));
+        }
+        (Some(sort), _) => sort,
+        (None, Some(_)) => ProjectSort::Relevance,
+        (None, None) => ProjectSort::default(),
+    };
+    let cursor = params.cursor.as_deref().and_then(ProjectCursor::decode);
+    let limit = params.limit.unwrap_or(20);
+
+    // Every sort is descending on a single numeric key; expiry is negated so the soonest comes
+    // first. Projects without a bounty have no key when sorting by it and come last. Without `q`
+    // the search query is NULL, and so are the rank and snippet.
+    let mut projects = sqlx::query_as::<_, ListedProject>(&format!(
+        r#"
+        WITH search AS (
+            SELECT websearch_to_tsquery('english', $12) AS query
+        ),
+        listed AS (
+            SELECT
+                p.id,
+                p.name,
+                p.owner_address,
+                p.contract_address,
+                p.description,
+                p.is_verified,
+                p.verification_date,
+                p.repository_url,
+                ARRAY(
+                    SELECT t.name::text
+                    FROM project_tags pt
+                    JOIN tags t ON t.id = pt.tag_id
+                    WHERE pt.project_id = p.id
+                    ORDER BY t.name
+                ) AS tags,
+                p.bounty_amount,
+                p.bounty_currency,
+                p.bounty_expiry_date,
+                p.status,
+                p.paused_at,
+                p.closed_at,
+                p.created_at,
+                search.query,
+                ts_rank(p.search_vector, search.query) AS rank,
+                CASE $8
+                    WHEN 'relevance' THEN ts_rank(p.search_vector, search.query)::numeric
+                    WHEN 'bounty_amount' THEN p.bounty_amount
+                    WHEN 'bounty_expiry' THEN -EXTRACT(EPOCH FROM p.bounty_expiry_date)
+                    ELSE EXTRACT(EPOCH FROM p.created_at)
+                END AS sort_key
+            FROM projects p, search
+            WHERE ($12::text IS NULL OR p.search_vector @@ search.query)
+              AND ($1::bool IS NULL OR p.is_verified = $1)
+              AND ($2::bool IS NULL OR (
+                    COALESCE(p.bounty_amount, 0) > 0
+                    AND p.bounty_expiry_date > NOW()
+                    AND p.closed_at IS NULL
+                  ) = $2)
+              AND ($3::text IS NULL OR p.bounty_currency = $3)
+              AND ($4::text[] IS NULL OR (
+                    SELECT COUNT(*)
+                    FROM project_tags pt
+                    JOIN tags t ON t.id = pt.tag_id
+                    WHERE pt.project_id = p.id AND t.name = ANY($4)
+                  ) = cardinality($4))
+              AND ($5::text IS NULL OR p.owner_address = $5)
+              AND ($6::bool IS NULL OR (p.closed_at IS NOT NULL) = $6)
+              AND ($7::timestamptz IS NULL OR p.bounty_expiry_date < $7)
+              AND ($13::project_status IS NULL OR p.status = $13)
+        )
+        SELECT
+            id, name, owner_address, contract_address, description, is_verified,
+            verification_date, repository_url, tags, bounty_amount, bounty_currency,
+            bounty_expiry_date, status, paused_at, closed_at, created_at, sort_key, rank,
+            ts_headline('english', html_escape(description), query, '{SNIPPET_OPTIONS}') AS snippet
+        FROM listed
+        WHERE $10::uuid IS NULL
+           OR ($9::numeric IS NULL AND sort_key IS NULL AND id < $10)
+           OR ($9::numeric IS NOT NULL AND (
+                sort_key < $9 OR (sort_key = $9 AND id < $10) OR sort_key IS NULL
+              ))
+        ORDER BY sort_key DESC NULLS LAST, id DESC
+        LIMIT $11
+        "#
+    ))
+    .bind(params.verified)
+    .bind(params.has_active_bounty)
+    .bind(&params.currency)
+    .bind(params.tag_names())
+    .bind(&params.owner_address)
+    .bind(params.closed)
+    .bind(params.expires_before)
+    .bind(sort.as_str())
+    .bind(cursor.as_ref().and_then(|cursor| cursor.sort_key.clone()))
+    .bind(cursor.as_ref().map(|cursor| cursor.id))
+    .bind(limit + 1)
+    .bind(&params.q)
+    .bind(params.status)
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    let next_cursor = if projects.len() as i64 > limit {
+        projects.truncate(limit as usize);
+        projects.last().map(|last| {
+            ProjectCursor {
+                sort_key: last.sort_key.clone(),
+                id: last.project.id,
+            }
+            .encode()
+        })
+    } else {
+        None
+    };
+
+    Ok(Json(ProjectPage {
+        projects,
+        next_cursor,
+    }))
+}
@@ -1,15 +1,28 @@
 mod close_project;
 mod create_project;
 mod domain;
+mod extend_bounty;
+mod list_projects;
+mod ownership_transfer;
+mod pause_project;
 mod project_detail_view;
+mod project_members;
+mod project_revisions;
+mod project_scope;
+mod project_tags;
+mod reward_matrix;
 mod shared;
+mod tags;
+mod update_project;
+mod verification_review;
 mod verify_project;
 
 use axum::{
     Router,
-    routing::{get, post},
+    routing::{delete, get, post},
 };
 pub use domain::*;
+pub(crate) use shared::{ensure_project_role, project_role};
 
 use crate::AppState;
 
@@ -27,8 +40,84 @@ pub(crate) fn router() -> Router<AppState> {
             "/projects/{project_id}/verify",
             post(verify_project::verify_project),
         )
+        .route(
+            "/projects/{project_id}/verify/check",
+            post(verify_project::check_verification_handler),
+        )
+        .route(
+            "/admin/project_verifications",
+            get(verification_review::list_project_verifications_handler),
+        )
+        .route(
+            "/admin/project_verifications/{verification_id}/approve",
+            post(verification_review::approve_project_verification_handler),
+        )
+        .route(
+            "/admin/project_verifications/{verification_id}/reject",
+            post(verification_review::reject_project_verification_handler),
+        )
+        .route("/projects", get(list_projects::list_projects_handler))
+        .route("/tags", get(tags::list_tags_handler))
+        .route("/admin/tags/merge", post(tags::merge_tags_handler))
         .route(
             "/projects/{project_id}",
-            get(project_detail_view::get_project_detail_view),
+            get(project_detail_view::get_project_detail_view)
+                .patch(update_project::update_project_handler),
+        )
+        .route(
+            "/projects/{project_id}/revisions",
+            get(project_revisions::project_revisions_handler),
+        )
+        .route(
+            "/projects/{project_id}/scope",
+            get(project_scope::get_project_scope_handler)
+                .put(project_scope::set_project_scope_handler),
+        )
+        .route(
+            "/projects/{project_id}/tags",
+            post(project_tags::add_project_tags_handler),
+        )
+        .route(
+            "/projects/{project_id}/tags/{tag}",
+            delete(project_tags::remove_project_tag_handler),
+        )
+        .route(
+            "/projects/{project_id}/members",
+            get(project_members::list_project_members_handler)
+                .post(project_members::invite_project_member_handler),
+        )
+        .route(
+            "/projects/{project_id}/members/accept",
+            post(project_members::accept_project_invitation_handler),
+        )
+        .route(
+            "/projects/{project_id}/members/{wallet_address}",
+            delete(project_members::remove_project_member_handler),
+        )
+        .route(
+            "/projects/{project_id}/ownership_transfer",
+            post(ownership_transfer::transfer_ownership_handler)
+                .delete(ownership_transfer::cancel_ownership_transfer_handler),
+        )
+        .route(
+            "/projects/{project_id}/ownership_transfer/accept",
+            post(ownership_transfer::accept_ownership_transfer_handler),
+        )
+        .route(
+            "/projects/{project_id}/pause",
+            post(pause_project::pause_project_handler),
+        )
+        .route(
+            "/projects/{project_id}/resume",
+            post(pause_project::resume_project_handler),
+        )
+        .route(
+            "/projects/{project_id}/bounty/extend",
+            post(extend_bounty::extend_bounty_handler),
+        )
+        .route(
+            "/projects/{project_id}/rewards",
+            get(reward_matrix::get_reward_matrix_handler)
+                .put(reward_matrix::set_reward_matrix_handler),
         )
 }
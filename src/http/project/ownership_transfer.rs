@@ -0,0 +1,185 @@
+use crate::{
+    AppState, Error, Result,
+    http::{
+        auth::AuthenticatedWallet,
+        project::{
+            OwnershipTransfer, ProjectResponse, TransferOwnershipRequest,
+            shared::{get_project_by_id, record_revision},
+        },
+    },
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use garde::Validate;
+use serde_json::{Map, json};
+use uuid::Uuid;
+
+/// POST /projects/{project_id}/ownership_transfer
+///
+/// The owner offers the project to another wallet, replacing any earlier offer. Nothing changes
+/// until that wallet accepts.
+#[tracing::instrument(name = "transfer_ownership_handler", skip(state, payload))]
+pub async fn transfer_ownership_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<TransferOwnershipRequest>,
+) -> Result<Json<OwnershipTransfer>> {
+    payload.validate()?;
+    if payload.new_owner_address == caller {
+        return Err(Error::unprocessable_entity([(
+            "new_owner_address",
+            "The project already belongs to this wallet",
+        )]));
+    }
+
+    let transfer = sqlx::query_as!(
+        OwnershipTransfer,
+        r#"
+        UPDATE projects
+        SET pending_owner_address = $3, updated_at = NOW()
+        WHERE id = $1 AND owner_address = $2
+        RETURNING id AS project_id, owner_address, pending_owner_address AS "pending_owner_address!"
+        "#,
+        project_id,
+        caller,
+        payload.new_owner_address
+    )
+    .fetch_optional(&state.db.pool)
+    .await?;
+
+    let Some(transfer) = transfer else {
+        get_project_by_id(&state.db.pool, project_id).await?;
+        tracing::warn!(caller = %caller, "Only the owner can transfer a project");
+        return Err(Error::Forbidden);
+    };
+
+    tracing::info!(
+        %project_id,
+        pending_owner_address = %transfer.pending_owner_address,
+        "Ownership transfer offered"
+    );
+    Ok(Json(transfer))
+}
+
+/// POST /projects/{project_id}/ownership_transfer/accept
+///
+/// The wallet the project was offered to takes it over. The previous owner stays on as a maintainer
+/// and keeps their share of the bounty; only what no funder's share covers is refunded to the new
+/// owner.
+#[tracing::instrument(name = "accept_ownership_transfer_handler", skip(state))]
+pub async fn accept_ownership_transfer_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<ProjectResponse>> {
+    let mut tx = state.db.pool.begin().await?;
+    let project = sqlx::query!(
+        "SELECT owner_address, pending_owner_address FROM projects WHERE id = $1 FOR UPDATE",
+        project_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(Error::NotFound)?;
+
+    if project.pending_owner_address.as_deref() != Some(caller.as_str()) {
+        return Err(Error::conflict(
+            "no_pending_transfer",
+            "the project has not been offered to this wallet",
+        ));
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE projects
+        SET owner_address = $2, pending_owner_address = NULL, updated_at = NOW()
+        WHERE id = $1
+        "#,
+        project_id,
+        caller
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        "DELETE FROM project_members WHERE project_id = $1 AND wallet_address = $2",
+        project_id,
+        caller
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        r#"
+        INSERT INTO project_members (project_id, wallet_address, role, invited_by, accepted_at)
+        VALUES ($1, $2, 'maintainer', $3, NOW())
+        ON CONFLICT (project_id, wallet_address) DO NOTHING
+        "#,
+        project_id,
+        project.owner_address,
+        caller
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let mut changes = Map::new();
+    changes.insert(
+        "owner_address".to_string(),
+        json!({ "from": project.owner_address, "to": caller }),
+    );
+    let revision = record_revision(&mut tx, project_id, &caller, changes).await?;
+    tx.commit().await?;
+
+    tracing::info!(
+        %project_id,
+        revision,
+        previous_owner = %project.owner_address,
+        owner_address = %caller,
+        "Ownership transferred"
+    );
+    Ok(Json(get_project_by_id(&state.db.pool, project_id).await?))
+}
+
+/// DELETE /projects/{project_id}/ownership_transfer
+///
+/// The owner withdraws its offer, or the wallet it was made to declines it.
+#[tracing::instrument(name = "cancel_ownership_transfer_handler", skip(state))]
+pub async fn cancel_ownership_transfer_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Path(project_id): Path<Uuid>,
+) -> Result<StatusCode> {
+    let project = sqlx::query!(
+        "SELECT owner_address, pending_owner_address FROM projects WHERE id = $1",
+        project_id
+    )
+    .fetch_optional(&state.db.pool)
+    .await?
+    .ok_or(Error::NotFound)?;
+
+    let Some(pending_owner_address) = project.pending_owner_address else {
+        return Err(Error::conflict(
+            "no_pending_transfer",
+            "the project has no pending ownership transfer",
+        ));
+    };
+    if caller != project.owner_address && caller != pending_owner_address {
+        return Err(Error::Forbidden);
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE projects
+        SET pending_owner_address = NULL, updated_at = NOW()
+        WHERE id = $1 AND pending_owner_address = $2
+        "#,
+        project_id,
+        pending_owner_address
+    )
+    .execute(&state.db.pool)
+    .await?;
+
+    tracing::info!(%project_id, %pending_owner_address, "Ownership transfer cancelled");
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,113 @@
+use crate::{
+    AppState, Error, Result,
+    http::{
+        auth::AuthenticatedWallet,
+        project::{
+            ProjectResponse, ProjectStatus,
+            shared::{get_project_by_id, record_revision},
+        },
+    },
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use serde_json::{Map, json};
+use uuid::Uuid;
+
+/// POST /projects/{project_id}/pause
+///
+/// Stops new reports on an active project without closing it. The bounty stays escrowed and reports
+/// already submitted carry on.
+#[tracing::instrument(name = "pause_project_handler", skip(state))]
+pub async fn pause_project_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<ProjectResponse>> {
+    set_status(&state, &caller, project_id, ProjectStatus::Paused).await
+}
+
+/// POST /projects/{project_id}/resume
+///
+/// Lets a paused project take reports again.
+#[tracing::instrument(name = "resume_project_handler", skip(state))]
+pub async fn resume_project_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<ProjectResponse>> {
+    set_status(&state, &caller, project_id, ProjectStatus::Active).await
+}
+
+/// Moves an open project between active and paused, recording the change as a revision.
+async fn set_status(
+    state: &AppState,
+    caller: &str,
+    project_id: Uuid,
+    status: ProjectStatus,
+) -> Result<Json<ProjectResponse>> {
+    let mut tx = state.db.pool.begin().await?;
+    let project = sqlx::query!(
+        r#"
+        SELECT owner_address, status AS "status: ProjectStatus"
+        FROM projects
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+        project_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(Error::NotFound)?;
+
+    if project.owner_address != caller {
+        tracing::warn!(caller = %caller, "Only the owner can pause or resume a project");
+        return Err(Error::Forbidden);
+    }
+    match (project.status, status) {
+        (ProjectStatus::Closed, _) => {
+            return Err(Error::conflict(
+                "project_closed",
+                "closed projects cannot be paused or resumed",
+            ));
+        }
+        (ProjectStatus::Paused, ProjectStatus::Paused) => {
+            return Err(Error::conflict(
+                "project_paused",
+                "the project is already paused",
+            ));
+        }
+        (ProjectStatus::Active, ProjectStatus::Active) => {
+            return Err(Error::conflict(
+                "project_not_paused",
+                "the project is not paused",
+            ));
+        }
+        _ => {}
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE projects
+        SET paused_at = CASE WHEN $2 THEN NOW() END, updated_at = NOW()
+        WHERE id = $1
+        "#,
+        project_id,
+        status == ProjectStatus::Paused
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let mut changes = Map::new();
+    changes.insert(
+        "status".to_string(),
+        json!({ "from": project.status, "to": status }),
+    );
+    let revision = record_revision(&mut tx, project_id, caller, changes).await?;
+    tx.commit().await?;
+
+    tracing::info!(%project_id, revision, ?status, "Project status changed");
+
+    Ok(Json(get_project_by_id(&state.db.pool, project_id).await?))
+}
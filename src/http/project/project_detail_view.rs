@@ -6,14 +6,18 @@ use uuid::Uuid;
 
 use crate::{
     AppState, Error,
-    http::project::{ProjectResponse, shared::get_project_by_id},
+    http::project::{
+        ProjectDetail,
+        shared::{get_project_by_id, get_project_scope},
+    },
 };
 
 #[tracing::instrument(name = "Get Project", skip(state), fields(project_id = %project_id))]
 pub async fn get_project_detail_view(
     State(state): State<AppState>,
     Path(project_id): Path<Uuid>,
-) -> Result<Json<ProjectResponse>, Error> {
+) -> Result<Json<ProjectDetail>, Error> {
     let project = get_project_by_id(&state.db.pool, project_id).await?;
-    Ok(Json(project))
+    let scope = get_project_scope(&state.db.pool, project_id, None).await?;
+    Ok(Json(ProjectDetail { project, scope }))
 }
@@ -0,0 +1,181 @@
+use crate::{
+    AppState, Error, Result,
+    http::{
+        auth::AuthenticatedWallet,
+        project::{
+            InviteMemberRequest, ProjectMember, ProjectRole,
+            shared::{ensure_project_role, project_role},
+        },
+    },
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use garde::Validate;
+use uuid::Uuid;
+
+/// GET /projects/{project_id}/members
+///
+/// The owner first, then members and pending invitations. Only visible to the project's team.
+#[tracing::instrument(name = "list_project_members_handler", skip(state))]
+pub async fn list_project_members_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<Vec<ProjectMember>>> {
+    ensure_project_role(&state.db.pool, project_id, &caller, ProjectRole::Viewer).await?;
+
+    let members = sqlx::query_as!(
+        ProjectMember,
+        r#"
+        SELECT
+            owner_address AS "wallet_address!",
+            'owner'::project_member_role AS "role!: ProjectRole",
+            NULL::varchar AS invited_by,
+            NULL::timestamptz AS invited_at,
+            created_at AS accepted_at
+        FROM projects
+        WHERE id = $1
+        UNION ALL
+        SELECT wallet_address, role, invited_by, invited_at, accepted_at
+        FROM project_members
+        WHERE project_id = $1
+        -- By invitation, the owner first, then by wallet.
+        ORDER BY 4 NULLS FIRST, 1
+        "#,
+        project_id
+    )
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    Ok(Json(members))
+}
+
+/// POST /projects/{project_id}/members
+///
+/// The owner invites a wallet as maintainer or viewer. It joins once it accepts.
+#[tracing::instrument(name = "invite_project_member_handler", skip(state, payload))]
+pub async fn invite_project_member_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<InviteMemberRequest>,
+) -> Result<(StatusCode, Json<ProjectMember>)> {
+    payload.validate()?;
+    ensure_project_role(&state.db.pool, project_id, &caller, ProjectRole::Owner).await?;
+
+    if payload.wallet_address == caller {
+        return Err(Error::conflict(
+            "already_member",
+            "the owner is already on the project",
+        ));
+    }
+
+    let member = sqlx::query_as!(
+        ProjectMember,
+        r#"
+        INSERT INTO project_members (project_id, wallet_address, role, invited_by)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (project_id, wallet_address) DO NOTHING
+        RETURNING
+            wallet_address,
+            role AS "role: ProjectRole",
+            invited_by AS "invited_by?",
+            invited_at AS "invited_at?",
+            accepted_at
+        "#,
+        project_id,
+        payload.wallet_address,
+        payload.role as ProjectRole,
+        caller
+    )
+    .fetch_optional(&state.db.pool)
+    .await?
+    .ok_or_else(|| {
+        Error::conflict(
+            "already_member",
+            "the wallet is already a member or invited",
+        )
+    })?;
+
+    tracing::info!(
+        %project_id,
+        wallet_address = %member.wallet_address,
+        role = ?member.role,
+        "Project member invited"
+    );
+    Ok((StatusCode::CREATED, Json(member)))
+}
+
+/// POST /projects/{project_id}/members/accept
+///
+/// The caller accepts its pending invitation to the project.
+#[tracing::instrument(name = "accept_project_invitation_handler", skip(state))]
+pub async fn accept_project_invitation_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<ProjectMember>> {
+    let member = sqlx::query_as!(
+        ProjectMember,
+        r#"
+        UPDATE project_members
+        SET accepted_at = NOW()
+        WHERE project_id = $1 AND wallet_address = $2 AND accepted_at IS NULL
+        RETURNING
+            wallet_address,
+            role AS "role: ProjectRole",
+            invited_by AS "invited_by?",
+            invited_at AS "invited_at?",
+            accepted_at
+        "#,
+        project_id,
+        caller
+    )
+    .fetch_optional(&state.db.pool)
+    .await?
+    .ok_or_else(|| {
+        Error::conflict(
+            "no_pending_invitation",
+            "the wallet has no pending invitation to the project",
+        )
+    })?;
+
+    tracing::info!(%project_id, wallet_address = %caller, "Project invitation accepted");
+    Ok(Json(member))
+}
+
+/// DELETE /projects/{project_id}/members/{wallet_address}
+///
+/// The owner removes a member or withdraws an invitation. A member may also leave, or decline its
+/// invitation, by removing itself.
+#[tracing::instrument(name = "remove_project_member_handler", skip(state))]
+pub async fn remove_project_member_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Path((project_id, wallet_address)): Path<(Uuid, String)>,
+) -> Result<StatusCode> {
+    if wallet_address != caller
+        && project_role(&state.db.pool, project_id, &caller).await? != Some(ProjectRole::Owner)
+    {
+        tracing::warn!(caller = %caller, "Only the owner can remove other members");
+        return Err(Error::Forbidden);
+    }
+
+    let removed = sqlx::query!(
+        "DELETE FROM project_members WHERE project_id = $1 AND wallet_address = $2",
+        project_id,
+        wallet_address
+    )
+    .execute(&state.db.pool)
+    .await?
+    .rows_affected();
+    if removed == 0 {
+        return Err(Error::NotFound);
+    }
+
+    tracing::info!(%project_id, %wallet_address, "Project member removed");
+    Ok(StatusCode::NO_CONTENT)
+}
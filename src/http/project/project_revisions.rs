@@ -0,0 +1,40 @@
+use crate::{AppState, Error, Result, http::project::ProjectRevision};
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use uuid::Uuid;
+
+/// GET /projects/{project_id}/revisions
+///
+/// Every edit of the project, newest first.
+#[tracing::instrument(name = "project_revisions_handler", skip(state))]
+pub async fn project_revisions_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<Vec<ProjectRevision>>> {
+    let exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1) AS "exists!""#,
+        project_id
+    )
+    .fetch_one(&state.db.pool)
+    .await?;
+    if !exists {
+        return Err(Error::NotFound);
+    }
+
+    let revisions = sqlx::query_as!(
+        ProjectRevision,
+        r#"
+        SELECT revision, changed_by, changes, created_at
+        FROM project_revisions
+        WHERE project_id = $1
+        ORDER BY revision DESC
+        "#,
+        project_id
+    )
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    Ok(Json(revisions))
+}
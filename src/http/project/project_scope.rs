@@ -0,0 +1,142 @@
+use crate::{
+    AppState, Error, Result,
+    http::{
+        auth::AuthenticatedWallet,
+        project::{
+            ProjectScope, ProjectScopeQuery, ScopeAssetType, SetProjectScopeRequest,
+            shared::get_project_scope,
+        },
+        report::SeverityLevel,
+    },
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use garde::Validate;
+use uuid::Uuid;
+
+/// PUT /projects/{project_id}/scope
+///
+/// Publishes a new version of the project's scope. Earlier versions are kept, so reports submitted
+/// against them are still judged by the assets they targeted.
+#[tracing::instrument(name = "set_project_scope_handler", skip(state, payload))]
+pub async fn set_project_scope_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<SetProjectScopeRequest>,
+) -> Result<(StatusCode, Json<ProjectScope>)> {
+    payload.validate()?;
+
+    let mut tx = state.db.pool.begin().await?;
+    let project = sqlx::query!(
+        "SELECT owner_address, closed_at FROM projects WHERE id = $1 FOR UPDATE",
+        project_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(Error::NotFound)?;
+
+    if project.owner_address != caller {
+        tracing::warn!(caller = %caller, "Only the owner can change a project's scope");
+        return Err(Error::Forbidden);
+    }
+    if project.closed_at.is_some() {
+        return Err(Error::conflict(
+            "project_closed",
+            "closed projects cannot change their scope",
+        ));
+    }
+
+    let version = sqlx::query_scalar!(
+        r#"
+        INSERT INTO project_scopes (project_id, version, rules, created_by)
+        SELECT $1, COALESCE(MAX(version), 0) + 1, $2, $3
+        FROM project_scopes
+        WHERE project_id = $1
+        RETURNING version
+        "#,
+        project_id,
+        payload.rules,
+        caller
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let assets = payload
+        .in_scope
+        .iter()
+        .map(|asset| {
+            (
+                true,
+                Some(asset.asset_type),
+                &asset.identifier,
+                &asset.description,
+                Some(asset.max_severity),
+            )
+        })
+        .chain(payload.out_of_scope.iter().map(|item| {
+            (
+                false,
+                item.asset_type,
+                &item.identifier,
+                &item.description,
+                None,
+            )
+        }));
+    let (mut in_scope, mut asset_types, mut identifiers, mut descriptions, mut max_severities) =
+        (Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    for (included, asset_type, identifier, description, max_severity) in assets {
+        in_scope.push(included);
+        asset_types.push(asset_type);
+        identifiers.push(identifier.clone());
+        descriptions.push(description.clone());
+        max_severities.push(max_severity);
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO project_scope_assets (
+            project_id, version, in_scope, asset_type, identifier, description, max_severity
+        )
+        SELECT $1, $2, * FROM UNNEST(
+            $3::bool[], $4::scope_asset_type[], $5::text[], $6::text[], $7::severity_level[]
+        )
+        "#,
+        project_id,
+        version,
+        &in_scope,
+        &asset_types as &[Option<ScopeAssetType>],
+        &identifiers,
+        &descriptions as &[Option<String>],
+        &max_severities as &[Option<SeverityLevel>]
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    tracing::info!(project_id = %project_id, version, "Project scope published");
+
+    let scope = get_project_scope(&state.db.pool, project_id, Some(version))
+        .await?
+        .ok_or(Error::NotFound)?;
+    Ok((StatusCode::CREATED, Json(scope)))
+}
+
+/// GET /projects/{project_id}/scope?version=
+///
+/// The current scope of the project, or the version asked for.
+#[tracing::instrument(name = "get_project_scope_handler", skip(state))]
+pub async fn get_project_scope_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+    Query(query): Query<ProjectScopeQuery>,
+) -> Result<Json<ProjectScope>> {
+    query.validate()?;
+    get_project_scope(&state.db.pool, project_id, query.version)
+        .await?
+        .map(Json)
+        .ok_or(Error::NotFound)
+}
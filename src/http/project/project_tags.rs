@@ -0,0 +1,111 @@
+use crate::{
+    AppState, Error, Result,
+    http::{
+        auth::AuthenticatedWallet,
+        project::{
+            AddProjectTagsRequest, ProjectResponse, normalize_tags,
+            shared::{add_project_tags, get_project_by_id, project_tag_names, record_revision},
+        },
+    },
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use garde::Validate;
+use serde_json::{Map, json};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+/// POST /projects/{project_id}/tags
+///
+/// Adds tags to an open project, leaving the ones it already carries. Records a revision when the
+/// project gains a tag.
+#[tracing::instrument(name = "add_project_tags_handler", skip(state, payload))]
+pub async fn add_project_tags_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<AddProjectTagsRequest>,
+) -> Result<Json<ProjectResponse>> {
+    payload.validate()?;
+    let mut tx = state.db.pool.begin().await?;
+    lock_editable_project(&mut tx, &caller, project_id).await?;
+
+    let before = project_tag_names(&mut *tx, project_id).await?;
+    add_project_tags(&mut tx, project_id, &normalize_tags(payload.tags)).await?;
+    let after = project_tag_names(&mut *tx, project_id).await?;
+    if after != before {
+        let changes =
+            Map::from_iter([("tags".to_string(), json!({ "from": before, "to": after }))]);
+        record_revision(&mut tx, project_id, &caller, changes).await?;
+    }
+
+    tx.commit().await?;
+    Ok(Json(get_project_by_id(&state.db.pool, project_id).await?))
+}
+
+/// DELETE /projects/{project_id}/tags/{tag}
+///
+/// Removes a tag from an open project. The tag matches regardless of case.
+#[tracing::instrument(name = "remove_project_tag_handler", skip(state))]
+pub async fn remove_project_tag_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Path((project_id, tag)): Path<(Uuid, String)>,
+) -> Result<Json<ProjectResponse>> {
+    let mut tx = state.db.pool.begin().await?;
+    lock_editable_project(&mut tx, &caller, project_id).await?;
+
+    let before = project_tag_names(&mut *tx, project_id).await?;
+    let removed = sqlx::query!(
+        r#"
+        DELETE FROM project_tags pt
+        USING tags t
+        WHERE pt.tag_id = t.id AND pt.project_id = $1 AND t.name = $2
+        "#,
+        project_id,
+        tag.trim()
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    if removed == 0 {
+        return Err(Error::NotFound);
+    }
+
+    let after = project_tag_names(&mut *tx, project_id).await?;
+    let changes = Map::from_iter([("tags".to_string(), json!({ "from": before, "to": after }))]);
+    record_revision(&mut tx, project_id, &caller, changes).await?;
+
+    tx.commit().await?;
+    Ok(Json(get_project_by_id(&state.db.pool, project_id).await?))
+}
+
+/// Locks the project for a tag edit, which only its owner may make while it is open.
+async fn lock_editable_project(
+    conn: &mut PgConnection,
+    caller: &str,
+    project_id: Uuid,
+) -> Result<()> {
+    let project = sqlx::query!(
+        "SELECT owner_address, closed_at FROM projects WHERE id = $1 FOR UPDATE",
+        project_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or(Error::NotFound)?;
+
+    if project.owner_address != caller {
+        tracing::warn!(caller = %caller, "Only the owner can edit a project's tags");
+        return Err(Error::Forbidden);
+    }
+    if project.closed_at.is_some() {
+        return Err(Error::conflict(
+            "project_closed",
+            "closed projects cannot be edited",
+        ));
+    }
+
+    Ok(())
+}
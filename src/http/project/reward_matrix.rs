@@ -0,0 +1,93 @@
+use crate::{
+    AppState, Error, Result,
+    http::{auth::AuthenticatedWallet, project::RewardMatrix, report::SeverityLevel},
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use bigdecimal::BigDecimal;
+use garde::Validate;
+use uuid::Uuid;
+
+/// PUT /projects/{project_id}/rewards
+///
+/// Replaces the payout per severity of a project. Only the project owner may change it.
+#[tracing::instrument(name = "set_reward_matrix_handler", skip(state, payload))]
+pub async fn set_reward_matrix_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<RewardMatrix>,
+) -> Result<Json<RewardMatrix>> {
+    payload.validate()?;
+
+    let owner_address = sqlx::query_scalar!(
+        "SELECT owner_address FROM projects WHERE id = $1",
+        project_id
+    )
+    .fetch_optional(&state.db.pool)
+    .await?
+    .ok_or(Error::NotFound)?;
+
+    if owner_address != caller {
+        tracing::warn!(project_id = %project_id, caller = %caller, "Only the project owner can set rewards");
+        return Err(Error::Forbidden);
+    }
+
+    let severities = [
+        SeverityLevel::Low,
+        SeverityLevel::Medium,
+        SeverityLevel::High,
+        SeverityLevel::Critical,
+    ];
+    let amounts: Vec<BigDecimal> = severities
+        .iter()
+        .map(|severity| payload.amount(*severity).clone())
+        .collect();
+
+    sqlx::query!(
+        r#"
+        INSERT INTO project_rewards (project_id, severity, amount)
+        SELECT $1, * FROM UNNEST($2::severity_level[], $3::numeric[])
+        ON CONFLICT (project_id, severity) DO UPDATE SET amount = EXCLUDED.amount
+        "#,
+        project_id,
+        &severities as &[SeverityLevel],
+        &amounts
+    )
+    .execute(&state.db.pool)
+    .await?;
+
+    tracing::info!(project_id = %project_id, "Reward matrix updated");
+
+    Ok(Json(payload))
+}
+
+/// GET /projects/{project_id}/rewards
+#[tracing::instrument(name = "get_reward_matrix_handler", skip(state))]
+pub async fn get_reward_matrix_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<RewardMatrix>> {
+    let matrix = sqlx::query_as!(
+        RewardMatrix,
+        r#"
+        SELECT
+            low.amount AS "low!",
+            medium.amount AS "medium!",
+            high.amount AS "high!",
+            critical.amount AS "critical!"
+        FROM project_rewards low
+        JOIN project_rewards medium ON medium.project_id = low.project_id AND medium.severity = 'medium'
+        JOIN project_rewards high ON high.project_id = low.project_id AND high.severity = 'high'
+        JOIN project_rewards critical ON critical.project_id = low.project_id AND critical.severity = 'critical'
+        WHERE low.project_id = $1 AND low.severity = 'low'
+        "#,
+        project_id
+    )
+    .fetch_optional(&state.db.pool)
+    .await?;
+
+    matrix.map(Json).ok_or(Error::NotFound)
+}
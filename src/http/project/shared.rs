@@ -1,5 +1,15 @@
-use crate::{Error, Result, http::project::ProjectResponse};
-use sqlx::PgPool;
+use crate::{
+    Error, Result,
+    http::{
+        project::{
+            ProjectResponse, ProjectRole, ProjectScope, ProjectStatus, ProjectVerification,
+            ProjectVerificationMethod, ProjectVerificationStatus, ScopeAsset, ScopeAssetType,
+        },
+        report::SeverityLevel,
+    },
+};
+use serde_json::{Map, Value};
+use sqlx::{PgConnection, PgExecutor, PgPool};
 use uuid::Uuid;
 
 pub async fn get_project_by_id(pool: &PgPool, project_id: Uuid) -> Result<ProjectResponse> {
@@ -15,6 +25,19 @@ pub async fn get_project_by_id(pool: &PgPool, project_id: Uuid) -> Result<Projec
             is_verified,
             verification_date,
             repository_url,
+            ARRAY(
+                SELECT t.name::text
+                FROM project_tags pt
+                JOIN tags t ON t.id = pt.tag_id
+                WHERE pt.project_id = projects.id
+                ORDER BY t.name
+            ) AS "tags!",
+            bounty_amount,
+            bounty_currency,
+            bounty_expiry_date,
+            status AS "status: ProjectStatus",
+            paused_at,
+            closed_at,
             created_at
         FROM projects
         WHERE id = $1
@@ -26,3 +49,293 @@ pub async fn get_project_by_id(pool: &PgPool, project_id: Uuid) -> Result<Projec
 
     project.ok_or(Error::NotFound)
 }
+
+/// The wallet's role on the project, or `None` if it is not a member. Pending invitations do not
+/// count. Fails with 404 if the project does not exist.
+pub async fn project_role<'c>(
+    executor: impl PgExecutor<'c>,
+    project_id: Uuid,
+    wallet_address: &str,
+) -> Result<Option<ProjectRole>> {
+    let role = sqlx::query_scalar!(
+        r#"
+        SELECT
+            CASE
+                WHEN p.owner_address = $2 THEN 'owner'::project_member_role
+                ELSE m.role
+            END AS "role: ProjectRole"
+        FROM projects p
+        LEFT JOIN project_members m
+            ON m.project_id = p.id AND m.wallet_address = $2 AND m.accepted_at IS NOT NULL
+        WHERE p.id = $1
+        "#,
+        project_id,
+        wallet_address
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    role.ok_or(Error::NotFound)
+}
+
+/// Fails with 403 unless the wallet's role on the project includes `required`.
+pub async fn ensure_project_role<'c>(
+    executor: impl PgExecutor<'c>,
+    project_id: Uuid,
+    wallet_address: &str,
+    required: ProjectRole,
+) -> Result<ProjectRole> {
+    match project_role(executor, project_id, wallet_address).await? {
+        Some(role) if role.includes(required) => Ok(role),
+        role => {
+            tracing::warn!(
+                %project_id,
+                wallet_address,
+                ?role,
+                ?required,
+                "Project role does not allow the action"
+            );
+            Err(Error::Forbidden)
+        }
+    }
+}
+
+/// The given version of the project's scope, or the current one. `None` when there is no such version.
+pub async fn get_project_scope(
+    pool: &PgPool,
+    project_id: Uuid,
+    version: Option<i32>,
+) -> Result<Option<ProjectScope>> {
+    let Some(scope) = sqlx::query!(
+        r#"
+        SELECT version, rules, created_by, created_at
+        FROM project_scopes
+        WHERE project_id = $1 AND ($2::int IS NULL OR version = $2)
+        ORDER BY version DESC
+        LIMIT 1
+        "#,
+        project_id,
+        version
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let assets = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            in_scope,
+            asset_type AS "asset_type: ScopeAssetType",
+            identifier,
+            description,
+            max_severity AS "max_severity: SeverityLevel"
+        FROM project_scope_assets
+        WHERE project_id = $1 AND version = $2
+        ORDER BY identifier, id
+        "#,
+        project_id,
+        scope.version
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let (mut in_scope, mut out_of_scope) = (Vec::new(), Vec::new());
+    for asset in assets {
+        let list = if asset.in_scope {
+            &mut in_scope
+        } else {
+            &mut out_of_scope
+        };
+        list.push(ScopeAsset {
+            id: asset.id,
+            asset_type: asset.asset_type,
+            identifier: asset.identifier,
+            description: asset.description,
+            max_severity: asset.max_severity,
+        });
+    }
+
+    Ok(Some(ProjectScope {
+        version: scope.version,
+        rules: scope.rules,
+        in_scope,
+        out_of_scope,
+        created_by: scope.created_by,
+        created_at: scope.created_at,
+    }))
+}
+
+/// Records an edit of the project as its next revision. The caller must hold the project row's lock,
+/// so revision numbers cannot race.
+pub async fn record_revision(
+    conn: &mut PgConnection,
+    project_id: Uuid,
+    changed_by: &str,
+    changes: Map<String, Value>,
+) -> Result<i32> {
+    let revision = sqlx::query_scalar!(
+        r#"
+        INSERT INTO project_revisions (project_id, revision, changed_by, changes)
+        SELECT $1, COALESCE(MAX(revision), 0) + 1, $2, $3
+        FROM project_revisions
+        WHERE project_id = $1
+        RETURNING revision
+        "#,
+        project_id,
+        changed_by,
+        Value::Object(changes)
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(revision)
+}
+
+/// Replaces the project's tags, creating the ones that do not exist yet.
+pub async fn set_project_tags(
+    conn: &mut PgConnection,
+    project_id: Uuid,
+    tags: &[String],
+) -> Result<()> {
+    sqlx::query!("DELETE FROM project_tags WHERE project_id = $1", project_id)
+        .execute(&mut *conn)
+        .await?;
+
+    add_project_tags(conn, project_id, tags).await
+}
+
+/// Adds tags to the project, creating the ones that do not exist yet. Names match existing tags
+/// regardless of case.
+pub async fn add_project_tags(
+    conn: &mut PgConnection,
+    project_id: Uuid,
+    tags: &[String],
+) -> Result<()> {
+    if tags.is_empty() {
+        return Ok(());
+    }
+
+    let tag_ids: Vec<i32> = sqlx::query_scalar!(
+        r#"
+        WITH input_tags(tag_name) AS (
+            SELECT unnest($1::text[])
+        ),
+        inserted_tags AS (
+            INSERT INTO tags (name)
+            SELECT tag_name FROM input_tags
+            ON CONFLICT (name) DO NOTHING
+            RETURNING id
+        )
+        SELECT id FROM inserted_tags
+        UNION
+        SELECT t.id FROM tags t
+        JOIN input_tags it ON t.name = it.tag_name
+        "#,
+        tags
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if !tag_ids.is_empty() {
+        sqlx::query!(
+            r#"
+            INSERT INTO project_tags (project_id, tag_id)
+            SELECT $1, unnest($2::int[])
+            ON CONFLICT DO NOTHING
+            "#,
+            project_id,
+            &tag_ids
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// The names of the project's tags, sorted.
+pub async fn project_tag_names<'c>(
+    executor: impl PgExecutor<'c>,
+    project_id: Uuid,
+) -> Result<Vec<String>> {
+    let names = sqlx::query_scalar!(
+        r#"
+        SELECT t.name::text AS "name!"
+        FROM project_tags pt
+        JOIN tags t ON t.id = pt.tag_id
+        WHERE pt.project_id = $1
+        ORDER BY t.name
+        "#,
+        project_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(names)
+}
+
+/// Settles a pending verification, marking its project verified when it passes.
+pub async fn decide_verification(
+    conn: &mut PgConnection,
+    verification_id: Uuid,
+    status: ProjectVerificationStatus,
+    method: Option<ProjectVerificationMethod>,
+    reviewed_by: Option<&str>,
+    notes: Option<&str>,
+) -> Result<ProjectVerification> {
+    let verification = sqlx::query_as!(
+        ProjectVerification,
+        r#"
+        UPDATE project_verifications
+        SET status = $2, method = $3, reviewed_by = $4, notes = $5, decided_at = NOW()
+        WHERE id = $1 AND status = 'pending'
+        RETURNING
+            id,
+            project_id,
+            token,
+            repository_url,
+            status AS "status: ProjectVerificationStatus",
+            method AS "method: ProjectVerificationMethod",
+            reviewed_by,
+            notes,
+            created_at,
+            decided_at
+        "#,
+        verification_id,
+        status as ProjectVerificationStatus,
+        method as Option<ProjectVerificationMethod>,
+        reviewed_by,
+        notes
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| {
+        Error::conflict(
+            "verification_not_pending",
+            "the verification was already decided",
+        )
+    })?;
+
+    if verification.status == ProjectVerificationStatus::Verified {
+        sqlx::query!(
+            r#"
+            UPDATE projects
+            SET is_verified = true, verification_date = $2, repository_url = $3
+            WHERE id = $1
+            "#,
+            verification.project_id,
+            verification.decided_at,
+            verification.repository_url
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(verification)
+}
@@ -0,0 +1,115 @@
+use crate::{
+    AppState, Error, Result,
+    http::{
+        project::{MergeTagsRequest, TagUsage, normalize_tags},
+        role::{Admin, RequireRole},
+    },
+};
+use axum::{Json, extract::State};
+use garde::Validate;
+
+/// GET /tags
+///
+/// Every tag with the number of projects carrying it, most used first.
+#[tracing::instrument(name = "list_tags_handler", skip(state))]
+pub async fn list_tags_handler(State(state): State<AppState>) -> Result<Json<Vec<TagUsage>>> {
+    let tags = sqlx::query_as!(
+        TagUsage,
+        r#"
+        SELECT t.name::text AS "name!", COUNT(pt.project_id) AS "project_count!"
+        FROM tags t
+        LEFT JOIN project_tags pt ON pt.tag_id = t.id
+        GROUP BY t.id
+        ORDER BY 2 DESC, t.name
+        "#
+    )
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    Ok(Json(tags))
+}
+
+/// POST /admin/tags/merge
+///
+/// Moves every project carrying one of the source tags onto the target tag and deletes the
+/// sources.
+#[tracing::instrument(name = "merge_tags_handler", skip(state, payload))]
+pub async fn merge_tags_handler(
+    State(state): State<AppState>,
+    RequireRole(admin, _): RequireRole<Admin>,
+    Json(payload): Json<MergeTagsRequest>,
+) -> Result<Json<TagUsage>> {
+    payload.validate()?;
+    let source_tags = normalize_tags(payload.source_tags);
+    let target_tag = payload.target_tag.trim();
+    if target_tag.is_empty() {
+        return Err(Error::unprocessable_entity([(
+            "target_tag",
+            "Target tag cannot be blank",
+        )]));
+    }
+
+    let mut tx = state.db.pool.begin().await?;
+    let source_ids = sqlx::query_scalar!(
+        "SELECT id FROM tags WHERE name = ANY($1) ORDER BY id FOR UPDATE",
+        &source_tags
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    if source_ids.len() != source_tags.len() {
+        return Err(Error::unprocessable_entity([(
+            "source_tags",
+            "Every source tag must exist",
+        )]));
+    }
+
+    // Naming a tag that exists in another case renames it to this spelling.
+    let target_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO tags (name) VALUES ($1)
+        ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name
+        RETURNING id
+        "#,
+        target_tag
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO project_tags (project_id, tag_id)
+        SELECT project_id, $1 FROM project_tags WHERE tag_id = ANY($2)
+        ON CONFLICT DO NOTHING
+        "#,
+        target_id,
+        &source_ids
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        "DELETE FROM tags WHERE id = ANY($1) AND id <> $2",
+        &source_ids,
+        target_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let merged = sqlx::query_as!(
+        TagUsage,
+        r#"
+        SELECT
+            t.name::text AS "name!",
+            (SELECT COUNT(*) FROM project_tags pt WHERE pt.tag_id = t.id) AS "project_count!"
+        FROM tags t
+        WHERE t.id = $1
+        "#,
+        target_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    tracing::info!(%admin, ?source_tags, target_tag = %merged.name, "Tags merged");
+
+    Ok(Json(merged))
+}
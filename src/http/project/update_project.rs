@@ -0,0 +1,201 @@
+use crate::{
+    AppState, Error, Result,
+    http::{
+        auth::AuthenticatedWallet,
+        project::{
+            ProjectResponse, UpdateProjectRequest, normalize_tags,
+            shared::{get_project_by_id, record_revision, set_project_tags},
+        },
+    },
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use garde::Validate;
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use uuid::Uuid;
+
+/// PATCH /projects/{project_id}
+///
+/// Lets the owner edit an open project. The contract address never changes, and the repository URL
+/// no longer does once the project is verified. Every edit that changes something is recorded as a
+/// revision.
+#[tracing::instrument(name = "update_project_handler", skip(state, payload))]
+pub async fn update_project_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<UpdateProjectRequest>,
+) -> Result<Json<ProjectResponse>> {
+    payload.validate()?;
+    if payload.contract_address.is_some() {
+        return Err(Error::unprocessable_entity([(
+            "contract_address",
+            "Contract address cannot be changed",
+        )]));
+    }
+
+    let mut tx = state.db.pool.begin().await?;
+    let mut current = sqlx::query!(
+        r#"
+        SELECT
+            owner_address,
+            name,
+            description,
+            contact_info,
+            supporting_document_path,
+            project_logo_path,
+            repository_url,
+            is_verified,
+            closed_at,
+            ARRAY(
+                SELECT t.name::text
+                FROM project_tags pt
+                JOIN tags t ON t.id = pt.tag_id
+                WHERE pt.project_id = projects.id
+                ORDER BY t.name
+            ) AS "tags!"
+        FROM projects
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+        project_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(Error::NotFound)?;
+
+    if current.owner_address != caller {
+        tracing::warn!(caller = %caller, "Only the owner can edit a project");
+        return Err(Error::Forbidden);
+    }
+    if current.closed_at.is_some() {
+        return Err(Error::conflict(
+            "project_closed",
+            "closed projects cannot be edited",
+        ));
+    }
+    if current.is_verified
+        && payload
+            .repository_url
+            .as_ref()
+            .is_some_and(|url| current.repository_url.as_ref() != Some(url))
+    {
+        return Err(Error::unprocessable_entity([(
+            "repository_url",
+            "Repository URL cannot be changed once the project is verified",
+        )]));
+    }
+
+    // Tags the project already carries keep their spelling, so a change of case alone is no edit.
+    // Both sides are sorted the way `normalize_tags` sorts, as the collation orders some symbols
+    // differently.
+    current.tags.sort_by_key(|tag| tag.to_lowercase());
+    let tags = payload.tags.map(|tags| {
+        normalize_tags(tags)
+            .into_iter()
+            .map(|tag| {
+                current
+                    .tags
+                    .iter()
+                    .find(|existing| existing.eq_ignore_ascii_case(&tag))
+                    .cloned()
+                    .unwrap_or(tag)
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let mut changes = Map::new();
+    diff(&mut changes, "name", &current.name, payload.name.as_ref());
+    diff(
+        &mut changes,
+        "description",
+        &current.description,
+        payload.description.as_ref(),
+    );
+    diff(
+        &mut changes,
+        "contact_info",
+        &current.contact_info,
+        payload.contact_info.as_ref(),
+    );
+    diff(
+        &mut changes,
+        "supporting_document_path",
+        &current.supporting_document_path.as_deref(),
+        payload
+            .supporting_document_path
+            .as_deref()
+            .map(Some)
+            .as_ref(),
+    );
+    diff(
+        &mut changes,
+        "project_logo_path",
+        &current.project_logo_path.as_deref(),
+        payload.project_logo_path.as_deref().map(Some).as_ref(),
+    );
+    diff(
+        &mut changes,
+        "repository_url",
+        &current.repository_url.as_deref(),
+        payload.repository_url.as_deref().map(Some).as_ref(),
+    );
+    diff(&mut changes, "tags", &current.tags, tags.as_ref());
+
+    if changes.is_empty() {
+        tx.commit().await?;
+        return Ok(Json(get_project_by_id(&state.db.pool, project_id).await?));
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE projects
+        SET
+            name = COALESCE($2, name),
+            description = COALESCE($3, description),
+            contact_info = COALESCE($4, contact_info),
+            supporting_document_path = COALESCE($5, supporting_document_path),
+            project_logo_path = COALESCE($6, project_logo_path),
+            repository_url = COALESCE($7, repository_url),
+            updated_at = NOW()
+        WHERE id = $1
+        "#,
+        project_id,
+        payload.name,
+        payload.description,
+        payload.contact_info,
+        payload.supporting_document_path,
+        payload.project_logo_path,
+        payload.repository_url
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if let Some(tags) = tags.as_ref().filter(|_| changes.contains_key("tags")) {
+        set_project_tags(&mut tx, project_id, tags).await?;
+    }
+
+    let revision = record_revision(&mut tx, project_id, &caller, changes).await?;
+
+    tx.commit().await?;
+    tracing::info!(%project_id, revision, "Project updated");
+
+    Ok(Json(get_project_by_id(&state.db.pool, project_id).await?))
+}
+
+/// Records `field` in `changes` when `to` is given and differs from `from`.
+fn diff<T: PartialEq + Serialize>(
+    changes: &mut Map<String, Value>,
+    field: &str,
+    from: &T,
+    to: Option<&T>,
+) {
+    if let Some(to) = to
+        && to != from
+    {
+        changes.insert(field.to_string(), json!({ "from": from, "to": to }));
+    }
+}
@@ -0,0 +1,113 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use garde::Validate;
+use uuid::Uuid;
+
+use crate::{
+    AppState, Result,
+    http::{
+        project::{
+            ApproveVerificationRequest, ListProjectVerificationsQuery, ProjectVerification,
+            ProjectVerificationMethod, ProjectVerificationStatus, RejectVerificationRequest,
+            shared::decide_verification,
+        },
+        role::{Admin, RequireRole},
+    },
+};
+
+/// Verifications returned by one listing, oldest first.
+const MAX_LISTED_VERIFICATIONS: i64 = 100;
+
+/// GET /admin/project_verifications
+///
+/// Project verifications, by default those still waiting for a proof or a review.
+#[tracing::instrument(name = "list_project_verifications_handler", skip(state))]
+pub async fn list_project_verifications_handler(
+    State(state): State<AppState>,
+    _admin: RequireRole<Admin>,
+    Query(query): Query<ListProjectVerificationsQuery>,
+) -> Result<Json<Vec<ProjectVerification>>> {
+    let status = query.status.unwrap_or(ProjectVerificationStatus::Pending);
+    let verifications = sqlx::query_as!(
+        ProjectVerification,
+        r#"
+        SELECT
+            id,
+            project_id,
+            token,
+            repository_url,
+            status AS "status: ProjectVerificationStatus",
+            method AS "method: ProjectVerificationMethod",
+            reviewed_by,
+            notes,
+            created_at,
+            decided_at
+        FROM project_verifications
+        WHERE status = $1
+        ORDER BY created_at, id
+        LIMIT $2
+        "#,
+        status as ProjectVerificationStatus,
+        MAX_LISTED_VERIFICATIONS
+    )
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    Ok(Json(verifications))
+}
+
+/// POST /admin/project_verifications/{verification_id}/approve
+///
+/// Verifies the project without a proof, for owners who cannot provide one.
+#[tracing::instrument(name = "approve_project_verification_handler", skip(state, payload))]
+pub async fn approve_project_verification_handler(
+    State(state): State<AppState>,
+    RequireRole(admin, _): RequireRole<Admin>,
+    Path(verification_id): Path<Uuid>,
+    Json(payload): Json<ApproveVerificationRequest>,
+) -> Result<Json<ProjectVerification>> {
+    payload.validate()?;
+    let mut tx = state.db.pool.begin().await?;
+    let verification = decide_verification(
+        &mut tx,
+        verification_id,
+        ProjectVerificationStatus::Verified,
+        Some(ProjectVerificationMethod::Manual),
+        Some(&admin),
+        payload.notes.as_deref(),
+    )
+    .await?;
+    tx.commit().await?;
+    tracing::info!(%verification_id, "Project verification approved");
+
+    Ok(Json(verification))
+}
+
+/// POST /admin/project_verifications/{verification_id}/reject
+///
+/// Rejects the verification. The owner has to request a new token to try again.
+#[tracing::instrument(name = "reject_project_verification_handler", skip(state, payload))]
+pub async fn reject_project_verification_handler(
+    State(state): State<AppState>,
+    RequireRole(admin, _): RequireRole<Admin>,
+    Path(verification_id): Path<Uuid>,
+    Json(payload): Json<RejectVerificationRequest>,
+) -> Result<Json<ProjectVerification>> {
+    payload.validate()?;
+    let mut tx = state.db.pool.begin().await?;
+    let verification = decide_verification(
+        &mut tx,
+        verification_id,
+        ProjectVerificationStatus::Rejected,
+        None,
+        Some(&admin),
+        Some(&payload.notes),
+    )
+    .await?;
+    tx.commit().await?;
+    tracing::warn!(%verification_id, "Project verification rejected");
+
+    Ok(Json(verification))
+}
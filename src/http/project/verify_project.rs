@@ -1,38 +1,46 @@
 use crate::{
     AppState, Error, Result,
-    http::project::{VerifyProjectRequest, VerifyProjectResponse, shared::get_project_by_id},
+    chain::project_verification_typed_data,
+    http::{
+        auth::AuthenticatedWallet,
+        project::{
+            CheckVerificationRequest, ProjectResponse, ProjectRole, ProjectVerification,
+            ProjectVerificationChallenge, ProjectVerificationMethod, ProjectVerificationStatus,
+            VerifyProjectRequest,
+            shared::{decide_verification, ensure_project_role, get_project_by_id},
+        },
+    },
+    verification::{OwnershipProof, VERIFICATION_FILE, Verdict},
 };
 use axum::{
     Json,
     extract::{Path, State},
+    http::StatusCode,
 };
 use garde::Validate;
-use sqlx::PgPool;
+use rand::Rng;
+use starknet::core::types::Felt;
 use uuid::Uuid;
 
+/// POST /projects/{project_id}/verify
+///
+/// Issues a token the owner or a maintainer proves control of the project with, replacing any
+/// pending one. The project is verified once the proof passes
+/// `POST /projects/{project_id}/verify/check`, or an admin approves it.
 #[tracing::instrument(name = "Verify Project", skip(state), fields(project_id = %project_id))]
 pub async fn verify_project(
     State(state): State<AppState>,
     Path(project_id): Path<Uuid>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
     Json(request): Json<VerifyProjectRequest>,
-) -> Result<Json<VerifyProjectResponse>> {
+) -> Result<(StatusCode, Json<ProjectVerificationChallenge>)> {
     request.validate()?;
 
     tracing::info!(
         project_id = %project_id,
         "Fetching project details for verification"
     );
-    let project = get_project_by_id(&state.db.pool, project_id).await?;
-
-    if project.owner_address != request.owner_address {
-        tracing::error!(
-            project_id = %project_id,
-            requester_address = %request.owner_address,
-            actual_owner = %project.owner_address,
-            "Verification attempted by non-owner"
-        );
-        return Err(Error::Forbidden);
-    }
+    let project = get_verifiable_project(&state, project_id, &caller).await?;
 
     if project.is_verified {
         tracing::warn!(
@@ -45,55 +53,147 @@ pub async fn verify_project(
         )]));
     }
 
-    let verification_date = chrono::Utc::now();
+    // A felt, so the deployer can sign it.
+    let token = format!("0x{}", hex::encode(rand::rng().random::<[u8; 31]>()));
 
-    match verify_project_in_db(
-        &state.db.pool,
+    let mut tx = state.db.pool.begin().await?;
+    sqlx::query!(
+        "DELETE FROM project_verifications WHERE project_id = $1 AND status = 'pending'",
+        project_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    let verification_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO project_verifications (project_id, token, repository_url)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
         project_id,
-        &request.repository_url,
-        verification_date,
+        token,
+        request.repository_url
     )
-    .await
-    {
-        Ok(_) => {
-            tracing::info!("Project {} successfully verified", project_id);
-            Ok(Json(VerifyProjectResponse {
-                message: "Project successfully verified".to_string(),
-                project_id,
-                verified_at: verification_date,
-            }))
-        }
-        Err(e) => {
-            tracing::error!("Failed to verify project {}: {}", project_id, e);
-            Err(Error::unprocessable_entity([(
-                "verification",
-                "Failed to verify project",
-            )]))
-        }
-    }
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    tracing::info!(%verification_id, "Verification token issued");
+    Ok((
+        StatusCode::CREATED,
+        Json(ProjectVerificationChallenge {
+            verification_id,
+            typed_data: Felt::from_hex(&project.contract_address)
+                .ok()
+                .map(|contract| {
+                    project_verification_typed_data(
+                        &state.configuration.starknet_chain_id,
+                        contract,
+                        &token,
+                    )
+                }),
+            token,
+            repository_url: request.repository_url,
+            file_path: VERIFICATION_FILE,
+        }),
+    ))
 }
 
-async fn verify_project_in_db(
-    pool: &PgPool,
-    project_id: Uuid,
-    repository_url: &str,
-    verification_date: chrono::DateTime<chrono::Utc>,
-) -> Result<()> {
-    sqlx::query!(
+/// POST /projects/{project_id}/verify/check
+///
+/// Checks the team's proof against the pending verification and verifies the project when it
+/// holds. A failed check leaves the verification pending, so the owner can fix it and retry.
+#[tracing::instrument(name = "check_verification_handler", skip(state, payload))]
+pub async fn check_verification_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Json(payload): Json<CheckVerificationRequest>,
+) -> Result<Json<ProjectVerification>> {
+    let project = get_verifiable_project(&state, project_id, &caller).await?;
+
+    let pending = sqlx::query!(
         r#"
-        UPDATE projects
-        SET
-            is_verified = true,
-            verification_date = $2,
-            repository_url = $3
-        WHERE id = $1
+        SELECT id, token, repository_url
+        FROM project_verifications
+        WHERE project_id = $1 AND status = 'pending'
         "#,
-        project_id,
-        verification_date,
-        repository_url
+        project_id
+    )
+    .fetch_optional(&state.db.pool)
+    .await?
+    .ok_or_else(|| {
+        Error::conflict(
+            "no_pending_verification",
+            "request a verification token first",
+        )
+    })?;
+
+    let (proof, method) = match payload {
+        CheckVerificationRequest::RepositoryFile => (
+            OwnershipProof::RepositoryFile,
+            ProjectVerificationMethod::RepositoryFile,
+        ),
+        CheckVerificationRequest::DeployerSignature {
+            deployment_transaction_hash,
+            signature,
+        } => {
+            let invalid = || {
+                Error::unprocessable_entity([(
+                    "deployer_signature",
+                    "Transaction hash and signature must be hex felts",
+                )])
+            };
+            let proof = OwnershipProof::DeployerSignature {
+                deployment_transaction_hash: Felt::from_hex(&deployment_transaction_hash)
+                    .map_err(|_| invalid())?,
+                signature: signature
+                    .iter()
+                    .map(|felt| Felt::from_hex(felt))
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| invalid())?,
+            };
+            (proof, ProjectVerificationMethod::DeployerSignature)
+        }
+    };
+
+    let verdict = state
+        .ownership_verifier
+        .verify(
+            &project.contract_address,
+            &pending.repository_url,
+            &pending.token,
+            &proof,
+        )
+        .await?;
+    if let Verdict::Failed(reason) = verdict {
+        tracing::warn!(verification_id = %pending.id, reason, "Ownership proof failed");
+        return Err(Error::unprocessable_entity([("verification", reason)]));
+    }
+
+    let mut tx = state.db.pool.begin().await?;
+    let verification = decide_verification(
+        &mut tx,
+        pending.id,
+        ProjectVerificationStatus::Verified,
+        Some(method),
+        None,
+        None,
     )
-    .execute(pool)
     .await?;
+    tx.commit().await?;
+
+    tracing::info!("Project {} successfully verified", project_id);
+    Ok(Json(verification))
+}
+
+/// The project, if `caller` owns or maintains it.
+async fn get_verifiable_project(
+    state: &AppState,
+    project_id: Uuid,
+    caller: &str,
+) -> Result<ProjectResponse> {
+    let project = get_project_by_id(&state.db.pool, project_id).await?;
+    ensure_project_role(&state.db.pool, project_id, caller, ProjectRole::Maintainer).await?;
 
-    Ok(())
+    Ok(project)
 }
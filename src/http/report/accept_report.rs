@@ -0,0 +1,161 @@
+use crate::{
+    AppState, Error, Result,
+    http::{
+        report::{
+            AcceptReportRequest, Report, ReportAction, SeverityLevel,
+            shared::{REPORT_COLUMNS, apply_transition, ensure_assigned_validator, lock_report},
+        },
+        role::{RequireRole, Validator},
+    },
+};
+use axum::{Json, extract::State};
+use bigdecimal::{BigDecimal, Zero};
+use garde::Validate;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+#[tracing::instrument(name = "accept_report_handler", skip(state, payload))]
+pub async fn accept_report_handler(
+    State(state): State<AppState>,
+    RequireRole(validator, _): RequireRole<Validator>,
+    Json(payload): Json<AcceptReportRequest>,
+) -> Result<Json<Report>> {
+    payload.validate()?;
+    let mut tx = state.db.pool.begin().await?;
+    let report = lock_report(&mut tx, payload.report_id).await?;
+    ensure_assigned_validator(&report, &validator)?;
+    ensure_within_scope(&mut tx, report.scope_asset_id, payload.severity).await?;
+    let status = apply_transition(
+        &mut tx,
+        &report,
+        ReportAction::Accept,
+        &validator,
+        payload.validator_notes.as_deref(),
+    )
+    .await?;
+    let reward = calculate_reward(&mut tx, report.project_id, payload.severity).await?;
+
+    let report = sqlx::query_as::<_, Report>(&format!(
+        r#"
+        UPDATE research_report
+        SET
+            status = $2,
+            severity = $3,
+            allocated_reward = $4,
+            validator_notes = COALESCE($5, validator_notes),
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING {REPORT_COLUMNS}
+        "#
+    ))
+    .bind(payload.report_id)
+    .bind(status)
+    .bind(payload.severity)
+    .bind(&reward)
+    .bind(&payload.validator_notes)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    tracing::info!(
+        report_id = %report.id,
+        severity = %payload.severity,
+        reward = %reward,
+        "Report accepted"
+    );
+
+    Ok(Json(report))
+}
+
+/// Rejects a severity above the cap of the asset the report targets. Reports filed before projects
+/// had a scope target no asset and are not capped.
+async fn ensure_within_scope(
+    conn: &mut PgConnection,
+    scope_asset_id: Option<Uuid>,
+    severity: SeverityLevel,
+) -> Result<()> {
+    let Some(scope_asset_id) = scope_asset_id else {
+        return Ok(());
+    };
+    let max_severity = sqlx::query_scalar!(
+        r#"SELECT max_severity AS "max_severity: SeverityLevel" FROM project_scope_assets WHERE id = $1"#,
+        scope_asset_id
+    )
+    .fetch_one(conn)
+    .await?;
+
+    match max_severity {
+        Some(max_severity) if severity > max_severity => Err(Error::unprocessable_entity([(
+            "severity",
+            format!("the targeted asset caps findings at {max_severity}"),
+        )])),
+        _ => Ok(()),
+    }
+}
+
+/// Looks up the project's payout for `severity` and caps it by what is left of the bounty once the
+/// rewards of other accepted, not yet disbursed reports are set aside.
+///
+/// Locks the project row so concurrent acceptances cannot hand out the same funds twice.
+async fn calculate_reward(
+    conn: &mut PgConnection,
+    project_id: Uuid,
+    severity: SeverityLevel,
+) -> Result<BigDecimal> {
+    let project = sqlx::query!(
+        r#"
+        SELECT
+            p.bounty_amount,
+            p.bounty_expiry_date,
+            p.closed_at,
+            r.amount AS "reward?",
+            (
+                SELECT COALESCE(SUM(allocated_reward), 0)
+                FROM research_report
+                WHERE project_id = p.id AND status = 'accepted'
+            ) AS "reserved!"
+        FROM projects p
+        LEFT JOIN project_rewards r ON r.project_id = p.id AND r.severity = $2
+        WHERE p.id = $1
+        FOR UPDATE OF p
+        "#,
+        project_id,
+        severity as SeverityLevel
+    )
+    .fetch_one(conn)
+    .await?;
+
+    if project.closed_at.is_some() {
+        return Err(Error::conflict(
+            "project_closed",
+            "the project is closed and no longer pays out rewards",
+        ));
+    }
+
+    let Some(reward) = project.reward else {
+        return Err(Error::conflict(
+            "reward_not_configured",
+            format!("the project has no reward set for {severity} findings"),
+        ));
+    };
+
+    if project
+        .bounty_expiry_date
+        .is_some_and(|expiry| expiry <= chrono::Utc::now())
+    {
+        return Err(Error::conflict(
+            "bounty_expired",
+            "the project's bounty has expired",
+        ));
+    }
+
+    let remaining = project.bounty_amount.unwrap_or_default() - project.reserved;
+    if remaining <= BigDecimal::zero() {
+        return Err(Error::conflict(
+            "bounty_exhausted",
+            "the project's bounty pool is exhausted",
+        ));
+    }
+
+    Ok(reward.min(remaining))
+}
@@ -0,0 +1,42 @@
+use crate::{
+    AppState, Result,
+    http::{
+        report::{
+            Report, ReportAction, ReportActionRequest,
+            shared::{REPORT_COLUMNS, apply_transition, lock_report},
+        },
+        role::{RequireRole, Validator},
+    },
+};
+use axum::{Json, extract::State};
+
+/// A validator claims a freshly submitted report.
+#[tracing::instrument(name = "assign_report_handler", skip(state, payload))]
+pub async fn assign_report_handler(
+    State(state): State<AppState>,
+    RequireRole(validator, _): RequireRole<Validator>,
+    Json(payload): Json<ReportActionRequest>,
+) -> Result<Json<Report>> {
+    let mut tx = state.db.pool.begin().await?;
+    let report = lock_report(&mut tx, payload.report_id).await?;
+    let status = apply_transition(&mut tx, &report, ReportAction::Assign, &validator, None).await?;
+
+    let report = sqlx::query_as::<_, Report>(&format!(
+        r#"
+        UPDATE research_report
+        SET status = $2, validated_by = $3, updated_at = NOW()
+        WHERE id = $1
+        RETURNING {REPORT_COLUMNS}
+        "#
+    ))
+    .bind(payload.report_id)
+    .bind(status)
+    .bind(&validator)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    tracing::info!(report_id = %report.id, validator = %validator, "Report assigned");
+
+    Ok(Json(report))
+}
@@ -0,0 +1,137 @@
+use crate::{
+    AppState, Error, Result,
+    http::{
+        escrow::generate_transaction_hash,
+        report::{
+            Report, ReportAction, ReportActionRequest, ReportStatus,
+            shared::{REPORT_COLUMNS, apply_transition, ensure_assigned_validator, lock_report},
+        },
+        role::{RequireRole, Validator},
+    },
+    ledger::{LedgerAccount, credit_balance, draw_bounty_funds, record_transfer},
+};
+use axum::{Json, extract::State};
+use bigdecimal::{BigDecimal, Zero};
+use sqlx::PgConnection;
+
+/// Ends the lifecycle of an accepted or rejected report.
+///
+/// Closing an accepted report pays its `allocated_reward` out of the project's bounty.
+#[tracing::instrument(name = "close_report_handler", skip(state, payload))]
+pub async fn close_report_handler(
+    State(state): State<AppState>,
+    RequireRole(validator, _): RequireRole<Validator>,
+    Json(payload): Json<ReportActionRequest>,
+) -> Result<Json<Report>> {
+    let mut tx = state.db.pool.begin().await?;
+    let report = lock_report(&mut tx, payload.report_id).await?;
+    ensure_assigned_validator(&report, &validator)?;
+    let status = apply_transition(&mut tx, &report, ReportAction::Close, &validator, None).await?;
+
+    if report.status == ReportStatus::Accepted
+        && let Some(reward) = report
+            .allocated_reward
+            .as_ref()
+            .filter(|reward| **reward > BigDecimal::zero())
+    {
+        disburse_reward(&mut tx, &report, reward).await?;
+    }
+
+    let report = sqlx::query_as::<_, Report>(&format!(
+        r#"
+        UPDATE research_report
+        SET status = $2, updated_at = NOW()
+        WHERE id = $1
+        RETURNING {REPORT_COLUMNS}
+        "#
+    ))
+    .bind(payload.report_id)
+    .bind(status)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    tracing::info!(report_id = %report.id, "Report closed");
+
+    Ok(Json(report))
+}
+
+/// Moves `reward` from the project's bounty to the researcher's escrow balance in the bounty's
+/// currency, opening an escrow account for the researcher if they have none yet.
+async fn disburse_reward(
+    conn: &mut PgConnection,
+    report: &Report,
+    reward: &BigDecimal,
+) -> Result<()> {
+    let tx_hash = generate_transaction_hash();
+
+    let currency = sqlx::query_scalar!(
+        r#"
+        UPDATE projects
+        SET bounty_amount = bounty_amount - $2,
+            updated_at = NOW()
+        WHERE id = $1
+          AND bounty_amount >= $2
+        RETURNING bounty_currency AS "bounty_currency!"
+        "#,
+        report.project_id,
+        reward
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let Some(currency) = currency else {
+        tracing::error!(
+            report_id = %report.id,
+            project_id = %report.project_id,
+            reward = %reward,
+            "Project bounty cannot cover the allocated reward"
+        );
+        return Err(Error::conflict(
+            "bounty_exhausted",
+            "the project's bounty cannot cover the allocated reward",
+        ));
+    };
+
+    draw_bounty_funds(&mut *conn, report.project_id, reward).await?;
+    credit_balance(&mut *conn, &report.reported_by, &currency, reward).await?;
+
+    let transaction_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO escrow_transactions (
+            wallet_address, project_id, type, amount, currency,
+            transaction_hash, status, notes
+        )
+        VALUES ($1, $2, 'bounty_disbursement', $3, $4, $5, 'completed', $6)
+        RETURNING id
+        "#,
+        report.reported_by,
+        report.project_id,
+        reward,
+        currency,
+        tx_hash,
+        format!("Bounty disbursed for report {}", report.id)
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    record_transfer(
+        &mut *conn,
+        LedgerAccount::ProjectPool(report.project_id),
+        LedgerAccount::User(&report.reported_by),
+        &currency,
+        reward,
+        Some(transaction_id),
+        "Bounty disbursement",
+    )
+    .await?;
+
+    tracing::info!(
+        report_id = %report.id,
+        researcher = %report.reported_by,
+        reward = %reward,
+        currency = %currency,
+        "Bounty disbursed"
+    );
+    Ok(())
+}
@@ -1,32 +1,264 @@
+use std::{fmt, str::FromStr};
+
 use garde::Validate;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize, Validate)]
+use crate::Error;
+
+/// Mirrors the `report_status_type` Postgres enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "report_status_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ReportStatus {
+    Submitted,
+    Assigned,
+    InReview,
+    InfoRequested,
+    InfoProvided,
+    Accepted,
+    Rejected,
+    Closed,
+}
+
+impl ReportStatus {
+    pub const ALL: [ReportStatus; 8] = [
+        ReportStatus::Submitted,
+        ReportStatus::Assigned,
+        ReportStatus::InReview,
+        ReportStatus::InfoRequested,
+        ReportStatus::InfoProvided,
+        ReportStatus::Accepted,
+        ReportStatus::Rejected,
+        ReportStatus::Closed,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportStatus::Submitted => "submitted",
+            ReportStatus::Assigned => "assigned",
+            ReportStatus::InReview => "in_review",
+            ReportStatus::InfoRequested => "info_requested",
+            ReportStatus::InfoProvided => "info_provided",
+            ReportStatus::Accepted => "accepted",
+            ReportStatus::Rejected => "rejected",
+            ReportStatus::Closed => "closed",
+        }
+    }
+
+    /// Looks up `action` in [`REPORT_TRANSITIONS`] and returns the resulting status.
+    pub fn transition(self, action: ReportAction) -> Result<ReportStatus, Error> {
+        REPORT_TRANSITIONS
+            .iter()
+            .find(|(candidate, from, _)| *candidate == action && from.contains(&self))
+            .map(|(_, _, to)| *to)
+            .ok_or_else(|| {
+                Error::conflict(
+                    "invalid_report_transition",
+                    format!("cannot {action} a report that is {self}"),
+                )
+            })
+    }
+}
+
+impl fmt::Display for ReportStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ReportStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ReportStatus::ALL
+            .into_iter()
+            .find(|status| status.as_str() == s)
+            .ok_or_else(|| format!("{s} is not a report status"))
+    }
+}
+
+/// Something a participant can do to a report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportAction {
+    Assign,
+    Review,
+    RequestInfo,
+    ProvideInfo,
+    Accept,
+    Reject,
+    Close,
+}
+
+impl fmt::Display for ReportAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ReportAction::Assign => "assign",
+            ReportAction::Review => "review",
+            ReportAction::RequestInfo => "request info on",
+            ReportAction::ProvideInfo => "provide info on",
+            ReportAction::Accept => "accept",
+            ReportAction::Reject => "reject",
+            ReportAction::Close => "close",
+        })
+    }
+}
+
+/// Every legal report transition: the action, the states it may start from and the state it leads to.
+/// Report handlers must go through [`ReportStatus::transition`] instead of checking statuses themselves.
+pub const REPORT_TRANSITIONS: &[(ReportAction, &[ReportStatus], ReportStatus)] = {
+    use ReportStatus::*;
+    &[
+        (ReportAction::Assign, &[Submitted], Assigned),
+        (ReportAction::Review, &[Assigned, InfoProvided], InReview),
+        (ReportAction::RequestInfo, &[InReview], InfoRequested),
+        (ReportAction::ProvideInfo, &[InfoRequested], InfoProvided),
+        (ReportAction::Accept, &[InReview], Accepted),
+        (
+            ReportAction::Reject,
+            &[Submitted, Assigned, InReview, InfoRequested, InfoProvided],
+            Rejected,
+        ),
+        (ReportAction::Close, &[Accepted, Rejected], Closed),
+    ]
+};
+
+/// Mirrors the `severity_level` Postgres enum.
+///
+/// Ordered from least to most severe, like the Postgres enum.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, sqlx::Type,
+)]
+#[sqlx(type_name = "severity_level", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SeverityLevel {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl fmt::Display for SeverityLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SeverityLevel::Low => "low",
+            SeverityLevel::Medium => "medium",
+            SeverityLevel::High => "high",
+            SeverityLevel::Critical => "critical",
+        })
+    }
+}
+
+/// Mirrors the `rejection_reason` Postgres enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "rejection_reason", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RejectionReason {
+    DuplicateReport,
+    IncompleteInformation,
+    AlreadyKnown,
+    OutOfScope,
+}
+
+#[derive(Debug, Deserialize, Validate)]
 pub struct RejectReportRequest {
     #[garde(skip)]
     pub report_id: Uuid,
-    #[garde(custom(validate_rejection_reason))]
-    pub reason: String,
+    #[garde(skip)]
+    pub reason: RejectionReason,
     #[garde(ascii, length(max = 1000))]
     pub validator_notes: Option<String>,
-    #[garde(custom(validate_starknet_address))]
-    pub validated_by: String,
 }
 
 #[derive(Debug, Serialize)]
 pub struct RejectReportResponse {
     pub message: String,
     pub report_id: Uuid,
-    pub status: String,
-    pub reason: String,
+    pub status: ReportStatus,
+    pub reason: RejectionReason,
     pub validator_notes: Option<String>,
     pub validated_by: String,
     pub rejected_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, sqlx::FromRow)]
-#[allow(dead_code)] // Fields are part of database schema and will be used in future functionality
+#[derive(Debug, Deserialize, Validate)]
+pub struct SubmitReportRequest {
+    #[garde(skip)]
+    pub project_id: Uuid,
+    #[garde(length(min = 3, max = 256))]
+    pub title: String,
+    #[garde(length(min = 50, max = 10000))]
+    pub body: String,
+    /// In-scope asset of the project's current scope that the finding concerns.
+    #[garde(skip)]
+    pub scope_asset_id: Uuid,
+}
+
+/// Body of the transitions that only need to know which report to act on.
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReportActionRequest {
+    #[garde(skip)]
+    pub report_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RequestInfoRequest {
+    #[garde(skip)]
+    pub report_id: Uuid,
+    #[garde(length(min = 1, max = 1000))]
+    pub validator_notes: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ProvideInfoRequest {
+    #[garde(skip)]
+    pub report_id: Uuid,
+    #[garde(length(min = 1, max = 1000))]
+    pub researcher_response: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AcceptReportRequest {
+    #[garde(skip)]
+    pub report_id: Uuid,
+    #[garde(skip)]
+    pub severity: SeverityLevel,
+    #[garde(length(max = 1000))]
+    pub validator_notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ListReportsQuery {
+    #[garde(custom(validate_query_status))]
+    pub status: Option<String>, // comma-separated
+    #[garde(range(min = 1, max = 50))]
+    pub limit: Option<i64>,
+    #[garde(range(min = 0))]
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SearchReportsQuery {
+    /// Full-text search over title and body, in web search syntax.
+    #[garde(length(min = 1, max = 200))]
+    pub q: String,
+    #[garde(range(min = 1, max = 50))]
+    pub limit: Option<i64>,
+    #[garde(range(min = 0))]
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ReportSearchHit {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub report: Report,
+    pub rank: f32,
+    /// HTML: the body, escaped, with the matched terms wrapped in `<mark>`.
+    pub snippet: String,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
 pub struct Report {
     pub id: Uuid,
     pub title: String,
@@ -34,38 +266,35 @@ pub struct Report {
     pub body: String,
     pub reported_by: String,
     pub validated_by: Option<String>,
-    pub status: String,           // Cast from enum to string in query
-    pub severity: Option<String>, // Cast from enum to string in query
-    pub allocated_reward: Option<sqlx::types::BigDecimal>, // Use BigDecimal for numeric fields
-    pub reason: Option<String>,   // Cast from enum to string in query
+    pub status: ReportStatus,
+    pub severity: Option<SeverityLevel>,
+    pub allocated_reward: Option<sqlx::types::BigDecimal>,
+    pub reason: Option<RejectionReason>,
     pub validator_notes: Option<String>,
     pub researcher_response: Option<String>,
+    pub scope_asset_id: Option<Uuid>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-pub fn validate_rejection_reason(reason: &str, _context: &()) -> garde::Result {
-    let valid_reasons = [
-        "duplicate_report",
-        "incomplete_information",
-        "already_known",
-        "out_of_scope",
-    ];
-
-    if valid_reasons.contains(&reason) {
-        Ok(())
-    } else {
-        Err(garde::Error::new("Invalid rejection reason"))
-    }
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ReportHistoryEntry {
+    pub id: Uuid,
+    pub from_status: Option<ReportStatus>,
+    pub to_status: ReportStatus,
+    pub changed_by: String,
+    pub notes: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
-pub fn validate_starknet_address(address: &str, _context: &()) -> garde::Result {
-    if address.starts_with("0x")
-        && address.len() == 66
-        && address.chars().skip(2).all(|c| c.is_ascii_hexdigit())
-    {
-        Ok(())
-    } else {
-        Err(garde::Error::new("Invalid Starknet address"))
+pub fn validate_query_status(statuses: &Option<String>, _context: &()) -> garde::Result {
+    if let Some(statuses) = statuses {
+        for status in statuses.split(',') {
+            status
+                .trim()
+                .parse::<ReportStatus>()
+                .map_err(garde::Error::new)?;
+        }
     }
+    Ok(())
 }
@@ -0,0 +1,28 @@
+use crate::{
+    AppState, Result,
+    http::{
+        auth::AuthenticatedWallet,
+        report::{
+            Report,
+            shared::{ensure_can_view, get_report_by_id},
+        },
+    },
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "get_report_handler", skip(state))]
+pub async fn get_report_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Path(report_id): Path<Uuid>,
+) -> Result<Json<Report>> {
+    let mut conn = state.db.pool.acquire().await?;
+    let report = get_report_by_id(&mut conn, report_id).await?;
+    ensure_can_view(&state.db.pool, &report, &caller).await?;
+
+    Ok(Json(report))
+}
@@ -0,0 +1,53 @@
+use crate::{
+    AppState, Result,
+    http::{
+        auth::AuthenticatedWallet,
+        report::{ListReportsQuery, Report, shared::REPORT_COLUMNS},
+    },
+};
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use garde::Validate;
+
+/// GET /reports?status=submitted,in_review&limit=20&offset=0
+///
+/// Lists the reports submitted by the authenticated researcher, newest first.
+#[tracing::instrument(name = "list_reports_handler", skip(state))]
+pub async fn list_reports_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(reported_by): AuthenticatedWallet,
+    Query(params): Query<ListReportsQuery>,
+) -> Result<Json<Vec<Report>>> {
+    params.validate()?;
+
+    let statuses: Vec<String> = params
+        .status
+        .as_ref()
+        .map(|s| {
+            s.split(',')
+                .map(|status| status.trim().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let reports = sqlx::query_as::<_, Report>(&format!(
+        r#"
+        SELECT {REPORT_COLUMNS}
+        FROM research_report
+        WHERE reported_by = $1
+          AND ($2 = '{{}}' OR status::text = ANY($2))
+        ORDER BY created_at DESC, id DESC
+        LIMIT $3 OFFSET $4
+        "#
+    ))
+    .bind(&reported_by)
+    .bind(&statuses)
+    .bind(params.limit.unwrap_or(10))
+    .bind(params.offset.unwrap_or(0))
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    Ok(Json(reports))
+}
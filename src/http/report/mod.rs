@@ -1,11 +1,50 @@
+mod accept_report;
+mod assign_report;
+mod close_report;
 mod domain;
+mod get_report;
+mod list_reports;
+mod provide_info;
 mod reject_report;
+mod report_history;
+mod request_info;
+mod review_report;
+mod search_reports;
+mod shared;
+mod submit_report;
 
-use axum::{Router, routing::post};
+use axum::{
+    Router,
+    routing::{get, post},
+};
 pub use domain::*;
 
 use crate::AppState;
 
 pub(crate) fn router() -> Router<AppState> {
-    Router::new().route("/report/reject", post(reject_report::reject_report))
+    Router::new()
+        .route("/report/submit", post(submit_report::submit_report_handler))
+        .route("/report/assign", post(assign_report::assign_report_handler))
+        .route("/report/review", post(review_report::review_report_handler))
+        .route(
+            "/report/request_info",
+            post(request_info::request_info_handler),
+        )
+        .route(
+            "/report/provide_info",
+            post(provide_info::provide_info_handler),
+        )
+        .route("/report/accept", post(accept_report::accept_report_handler))
+        .route("/report/reject", post(reject_report::reject_report))
+        .route("/report/close", post(close_report::close_report_handler))
+        .route("/reports", get(list_reports::list_reports_handler))
+        .route(
+            "/reports/search",
+            get(search_reports::search_reports_handler),
+        )
+        .route("/reports/{report_id}", get(get_report::get_report_handler))
+        .route(
+            "/reports/{report_id}/history",
+            get(report_history::report_history_handler),
+        )
 }
@@ -0,0 +1,63 @@
+use crate::{
+    AppState, Error, Result,
+    http::{
+        auth::AuthenticatedWallet,
+        report::{
+            ProvideInfoRequest, Report, ReportAction,
+            shared::{REPORT_COLUMNS, apply_transition, lock_report},
+        },
+    },
+};
+use axum::{Json, extract::State};
+use garde::Validate;
+
+/// The researcher answers a validator's request for information.
+#[tracing::instrument(name = "provide_info_handler", skip(state, payload))]
+pub async fn provide_info_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(researcher): AuthenticatedWallet,
+    Json(payload): Json<ProvideInfoRequest>,
+) -> Result<Json<Report>> {
+    payload.validate()?;
+    let mut tx = state.db.pool.begin().await?;
+    let report = lock_report(&mut tx, payload.report_id).await?;
+
+    if report.reported_by != researcher {
+        tracing::warn!(
+            report_id = %report.id,
+            caller = %researcher,
+            "Only the reporting researcher can provide information"
+        );
+        return Err(Error::Forbidden);
+    }
+    let status = apply_transition(
+        &mut tx,
+        &report,
+        ReportAction::ProvideInfo,
+        &researcher,
+        Some(&payload.researcher_response),
+    )
+    .await?;
+
+    let report = sqlx::query_as::<_, Report>(&format!(
+        r#"
+        UPDATE research_report
+        SET
+            status = $3,
+            researcher_response = $2,
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING {REPORT_COLUMNS}
+        "#
+    ))
+    .bind(payload.report_id)
+    .bind(&payload.researcher_response)
+    .bind(status)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    tracing::info!(report_id = %report.id, "Information provided");
+
+    Ok(Json(report))
+}
@@ -0,0 +1,43 @@
+use crate::{
+    AppState, Result,
+    http::{
+        auth::AuthenticatedWallet,
+        report::{
+            ReportHistoryEntry,
+            shared::{ensure_can_view, get_report_by_id},
+        },
+    },
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use uuid::Uuid;
+
+/// GET /reports/{report_id}/history
+///
+/// The full timeline of a report, oldest change first.
+#[tracing::instrument(name = "report_history_handler", skip(state))]
+pub async fn report_history_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Path(report_id): Path<Uuid>,
+) -> Result<Json<Vec<ReportHistoryEntry>>> {
+    let mut conn = state.db.pool.acquire().await?;
+    let report = get_report_by_id(&mut conn, report_id).await?;
+    ensure_can_view(&state.db.pool, &report, &caller).await?;
+
+    let history = sqlx::query_as::<_, ReportHistoryEntry>(
+        r#"
+        SELECT id, from_status, to_status, changed_by, notes, created_at
+        FROM report_history
+        WHERE report_id = $1
+        ORDER BY created_at, id
+        "#,
+    )
+    .bind(report_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(Json(history))
+}
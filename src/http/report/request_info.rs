@@ -0,0 +1,55 @@
+use crate::{
+    AppState, Result,
+    http::{
+        report::{
+            Report, ReportAction, RequestInfoRequest,
+            shared::{REPORT_COLUMNS, apply_transition, ensure_assigned_validator, lock_report},
+        },
+        role::{RequireRole, Validator},
+    },
+};
+use axum::{Json, extract::State};
+use garde::Validate;
+
+#[tracing::instrument(name = "request_info_handler", skip(state, payload))]
+pub async fn request_info_handler(
+    State(state): State<AppState>,
+    RequireRole(validator, _): RequireRole<Validator>,
+    Json(payload): Json<RequestInfoRequest>,
+) -> Result<Json<Report>> {
+    payload.validate()?;
+    let mut tx = state.db.pool.begin().await?;
+    let report = lock_report(&mut tx, payload.report_id).await?;
+    ensure_assigned_validator(&report, &validator)?;
+    let status = apply_transition(
+        &mut tx,
+        &report,
+        ReportAction::RequestInfo,
+        &validator,
+        Some(&payload.validator_notes),
+    )
+    .await?;
+
+    let report = sqlx::query_as::<_, Report>(&format!(
+        r#"
+        UPDATE research_report
+        SET
+            status = $3,
+            validator_notes = $2,
+            researcher_response = NULL,
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING {REPORT_COLUMNS}
+        "#
+    ))
+    .bind(payload.report_id)
+    .bind(&payload.validator_notes)
+    .bind(status)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    tracing::info!(report_id = %report.id, "More information requested");
+
+    Ok(Json(report))
+}
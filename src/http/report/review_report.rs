@@ -0,0 +1,42 @@
+use crate::{
+    AppState, Result,
+    http::{
+        report::{
+            Report, ReportAction, ReportActionRequest,
+            shared::{REPORT_COLUMNS, apply_transition, ensure_assigned_validator, lock_report},
+        },
+        role::{RequireRole, Validator},
+    },
+};
+use axum::{Json, extract::State};
+
+/// The assigned validator starts (or, after the researcher answered, resumes) the review.
+#[tracing::instrument(name = "review_report_handler", skip(state, payload))]
+pub async fn review_report_handler(
+    State(state): State<AppState>,
+    RequireRole(validator, _): RequireRole<Validator>,
+    Json(payload): Json<ReportActionRequest>,
+) -> Result<Json<Report>> {
+    let mut tx = state.db.pool.begin().await?;
+    let report = lock_report(&mut tx, payload.report_id).await?;
+    ensure_assigned_validator(&report, &validator)?;
+    let status = apply_transition(&mut tx, &report, ReportAction::Review, &validator, None).await?;
+
+    let report = sqlx::query_as::<_, Report>(&format!(
+        r#"
+        UPDATE research_report
+        SET status = $2, updated_at = NOW()
+        WHERE id = $1
+        RETURNING {REPORT_COLUMNS}
+        "#
+    ))
+    .bind(payload.report_id)
+    .bind(status)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    tracing::info!(report_id = %report.id, "Report in review");
+
+    Ok(Json(report))
+}
@@ -0,0 +1,63 @@
+use crate::{
+    AppState, Result,
+    http::{
+        auth::AuthenticatedWallet,
+        report::{ReportSearchHit, SearchReportsQuery, shared::REPORT_COLUMNS},
+        role::{UserRole, has_role},
+        search::SNIPPET_OPTIONS,
+    },
+};
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use garde::Validate;
+
+/// GET /reports/search?q=reentrancy&limit=20&offset=0
+///
+/// Searches the titles and bodies of the reports the caller may view, best match first. As with
+/// `GET /reports/{report_id}`, those are the caller's own reports, the reports assigned to them,
+/// the reports on projects they own, and every report for validators.
+#[tracing::instrument(name = "search_reports_handler", skip(state))]
+pub async fn search_reports_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Query(params): Query<SearchReportsQuery>,
+) -> Result<Json<Vec<ReportSearchHit>>> {
+    params.validate()?;
+
+    let is_validator = has_role(&state.db.pool, &caller, UserRole::Validator).await?;
+
+    let hits = sqlx::query_as::<_, ReportSearchHit>(&format!(
+        r#"
+        SELECT
+            {REPORT_COLUMNS},
+            ts_rank(search_vector, query) AS rank,
+            ts_headline('english', html_escape(body), query, '{SNIPPET_OPTIONS}') AS snippet
+        FROM research_report, websearch_to_tsquery('english', $1) AS query
+        WHERE search_vector @@ query
+          AND (
+            $3
+            OR reported_by = $2
+            OR validated_by = $2
+            OR project_id IN (SELECT id FROM projects WHERE owner_address = $2)
+            OR project_id IN (
+                SELECT project_id
+                FROM project_members
+                WHERE wallet_address = $2 AND accepted_at IS NOT NULL
+            )
+          )
+        ORDER BY rank DESC, created_at DESC, id DESC
+        LIMIT $4 OFFSET $5
+        "#
+    ))
+    .bind(&params.q)
+    .bind(&caller)
+    .bind(is_validator)
+    .bind(params.limit.unwrap_or(10))
+    .bind(params.offset.unwrap_or(0))
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    Ok(Json(hits))
+}
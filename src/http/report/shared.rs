@@ -0,0 +1,130 @@
+use crate::{
+    Error, Result,
+    http::{
+        project::project_role,
+        report::{Report, ReportAction, ReportStatus},
+        role::{UserRole, has_role},
+    },
+};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+/// Columns selected into [`Report`].
+pub const REPORT_COLUMNS: &str = r#"
+    id, title, project_id, body, reported_by, validated_by, status, severity,
+    allocated_reward, reason, validator_notes, researcher_response, scope_asset_id,
+    created_at, updated_at
+"#;
+
+pub async fn get_report_by_id(conn: &mut PgConnection, report_id: Uuid) -> Result<Report> {
+    let report = sqlx::query_as::<_, Report>(&format!(
+        "SELECT {REPORT_COLUMNS} FROM research_report WHERE id = $1"
+    ))
+    .bind(report_id)
+    .fetch_optional(conn)
+    .await?;
+
+    report.ok_or(Error::NotFound)
+}
+
+/// Like [`get_report_by_id`] but locks the row until the transaction ends, so two
+/// transitions on the same report cannot interleave.
+pub async fn lock_report(conn: &mut PgConnection, report_id: Uuid) -> Result<Report> {
+    let report = sqlx::query_as::<_, Report>(&format!(
+        "SELECT {REPORT_COLUMNS} FROM research_report WHERE id = $1 FOR UPDATE"
+    ))
+    .bind(report_id)
+    .fetch_optional(conn)
+    .await?;
+
+    report.ok_or(Error::NotFound)
+}
+
+/// Checks `action` against the transition table and records it in the report's history.
+///
+/// Returns the status the report must be updated to within the same transaction.
+pub async fn apply_transition(
+    conn: &mut PgConnection,
+    report: &Report,
+    action: ReportAction,
+    changed_by: &str,
+    notes: Option<&str>,
+) -> Result<ReportStatus> {
+    let next = report.status.transition(action).inspect_err(|_| {
+        tracing::warn!(
+            report_id = %report.id,
+            status = %report.status,
+            action = %action,
+            "Illegal report transition"
+        );
+    })?;
+
+    record_history(
+        conn,
+        report.id,
+        Some(report.status),
+        next,
+        changed_by,
+        notes,
+    )
+    .await?;
+
+    Ok(next)
+}
+
+pub async fn record_history(
+    conn: &mut PgConnection,
+    report_id: Uuid,
+    from_status: Option<ReportStatus>,
+    to_status: ReportStatus,
+    changed_by: &str,
+    notes: Option<&str>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO report_history (report_id, from_status, to_status, changed_by, notes)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        report_id,
+        from_status as Option<ReportStatus>,
+        to_status as ReportStatus,
+        changed_by,
+        notes
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Only the validator a report is assigned to may move it forward.
+pub fn ensure_assigned_validator(report: &Report, validator: &str) -> Result<()> {
+    if report.validated_by.as_deref() != Some(validator) {
+        tracing::warn!(
+            report_id = %report.id,
+            assigned_validator = ?report.validated_by,
+            request_validator = %validator,
+            "Validator is not assigned to this report"
+        );
+        return Err(Error::Forbidden);
+    }
+    Ok(())
+}
+
+/// A report is visible to its researcher, the reported project's team and validators.
+pub async fn ensure_can_view(pool: &PgPool, report: &Report, caller: &str) -> Result<()> {
+    if report.reported_by == caller || report.validated_by.as_deref() == Some(caller) {
+        return Ok(());
+    }
+
+    let is_project_member = project_role(pool, report.project_id, caller)
+        .await?
+        .is_some();
+
+    if is_project_member || has_role(pool, caller, UserRole::Validator).await? {
+        return Ok(());
+    }
+
+    tracing::warn!(report_id = %report.id, caller = %caller, "Report not visible to caller");
+    Err(Error::Forbidden)
+}
@@ -0,0 +1,133 @@
+use crate::{
+    AppState, Error, Result,
+    http::{
+        project::project_role,
+        report::{
+            Report, ReportStatus, SubmitReportRequest,
+            shared::{REPORT_COLUMNS, record_history},
+        },
+        role::{RequireRole, Researcher},
+    },
+};
+use axum::{Json, extract::State, http::StatusCode};
+use garde::Validate;
+
+#[tracing::instrument(name = "submit_report_handler", skip(state, payload))]
+pub async fn submit_report_handler(
+    State(state): State<AppState>,
+    RequireRole(reported_by, _): RequireRole<Researcher>,
+    Json(payload): Json<SubmitReportRequest>,
+) -> Result<(StatusCode, Json<Report>)> {
+    payload.validate()?;
+
+    tracing::info!(
+        project_id = %payload.project_id,
+        reported_by = %reported_by,
+        "Attempting to submit report"
+    );
+
+    let project = sqlx::query!(
+        r#"
+        SELECT closed_at, paused_at
+        FROM projects
+        WHERE id = $1
+        "#,
+        payload.project_id
+    )
+    .fetch_optional(&state.db.pool)
+    .await?
+    .ok_or(Error::NotFound)?;
+
+    if project_role(&state.db.pool, payload.project_id, &reported_by)
+        .await?
+        .is_some()
+    {
+        tracing::warn!(
+            project_id = %payload.project_id,
+            "Project members cannot report on their own project"
+        );
+        return Err(Error::Forbidden);
+    }
+
+    if project.closed_at.is_some() {
+        tracing::warn!(
+            project_id = %payload.project_id,
+            "Cannot submit a report against a closed project"
+        );
+        return Err(Error::conflict("project_closed", "the project is closed"));
+    }
+
+    if project.paused_at.is_some() {
+        tracing::warn!(
+            project_id = %payload.project_id,
+            "Cannot submit a report against a paused project"
+        );
+        return Err(Error::conflict(
+            "project_paused",
+            "the project is paused and not taking new reports",
+        ));
+    }
+
+    let mut tx = state.db.pool.begin().await?;
+    let in_current_scope = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1
+            FROM project_scope_assets a
+            JOIN (
+                SELECT project_id, version
+                FROM project_scopes
+                WHERE project_id = $1
+                ORDER BY version DESC
+                LIMIT 1
+            ) current USING (project_id, version)
+            WHERE a.id = $2 AND a.in_scope
+        ) AS "exists!"
+        "#,
+        payload.project_id,
+        payload.scope_asset_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if !in_current_scope {
+        tracing::warn!(
+            project_id = %payload.project_id,
+            scope_asset_id = %payload.scope_asset_id,
+            "Report does not target an in-scope asset"
+        );
+        return Err(Error::unprocessable_entity([(
+            "scope_asset_id",
+            "must be an in-scope asset of the project's current scope",
+        )]));
+    }
+
+    let report = sqlx::query_as::<_, Report>(&format!(
+        r#"
+        INSERT INTO research_report (title, project_id, body, reported_by, scope_asset_id)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING {REPORT_COLUMNS}
+        "#
+    ))
+    .bind(&payload.title)
+    .bind(payload.project_id)
+    .bind(&payload.body)
+    .bind(&reported_by)
+    .bind(payload.scope_asset_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    record_history(
+        &mut tx,
+        report.id,
+        None,
+        ReportStatus::Submitted,
+        &reported_by,
+        None,
+    )
+    .await?;
+    tx.commit().await?;
+
+    tracing::info!(report_id = %report.id, "Report submitted");
+
+    Ok((StatusCode::CREATED, Json(report)))
+}
@@ -0,0 +1,103 @@
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::http::auth::validate_starknet_address;
+
+/// Mirrors the `user_type` Postgres enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "user_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum UserRole {
+    Admin,
+    SupportAgent,
+    /// Implicit role of every wallet. It is never stored or granted.
+    User,
+    Validator,
+    Researcher,
+    ProjectOwner,
+}
+
+impl UserRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserRole::Admin => "admin",
+            UserRole::SupportAgent => "support_agent",
+            UserRole::User => "user",
+            UserRole::Validator => "validator",
+            UserRole::Researcher => "researcher",
+            UserRole::ProjectOwner => "project_owner",
+        }
+    }
+}
+
+impl std::fmt::Display for UserRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "role_change_action", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RoleChangeAction {
+    Granted,
+    Revoked,
+}
+
+/// A role a route can require through [`RequireRole`](super::RequireRole).
+pub trait Role: Send + Sync + 'static {
+    const ROLE: UserRole;
+}
+
+macro_rules! roles {
+    ($($name:ident),* $(,)?) => {
+        $(
+            #[derive(Debug, Clone, Copy)]
+            pub struct $name;
+
+            impl Role for $name {
+                const ROLE: UserRole = UserRole::$name;
+            }
+        )*
+    };
+}
+
+roles!(Admin, SupportAgent, Validator, Researcher, ProjectOwner);
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RoleChangeRequest {
+    #[garde(custom(validate_starknet_address))]
+    pub wallet_address: String,
+    #[garde(custom(validate_grantable_role))]
+    pub role: UserRole,
+    #[garde(length(max = 500))]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct RoleChange {
+    pub id: Uuid,
+    pub wallet_address: String,
+    pub role: UserRole,
+    pub action: RoleChangeAction,
+    pub changed_by: String,
+    pub reason: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WalletRolesResponse {
+    pub wallet_address: String,
+    pub roles: Vec<UserRole>,
+    pub changes: Vec<RoleChange>,
+}
+
+pub fn validate_grantable_role(role: &UserRole, _context: &()) -> garde::Result {
+    match role {
+        UserRole::User => Err(garde::Error::new(
+            "Every wallet implicitly has the user role",
+        )),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,57 @@
+use std::marker::PhantomData;
+
+use axum::{extract::FromRequestParts, http::request::Parts};
+
+use crate::{
+    AppState, Error, Result,
+    http::{
+        auth::AuthenticatedWallet,
+        role::{Role, UserRole},
+    },
+};
+
+/// An authenticated wallet holding role `R`. Admins satisfy every role.
+///
+/// Rejects with `401` when the request is not authenticated and `403` when the wallet
+/// lacks the role.
+#[derive(Debug, Clone)]
+pub struct RequireRole<R: Role>(pub String, pub PhantomData<R>);
+
+impl<R: Role> FromRequestParts<AppState> for RequireRole<R> {
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self> {
+        let AuthenticatedWallet(wallet_address) =
+            AuthenticatedWallet::from_request_parts(parts, state).await?;
+
+        if !has_role(&state.db.pool, &wallet_address, R::ROLE).await? {
+            tracing::warn!(
+                wallet = %wallet_address,
+                required_role = %R::ROLE,
+                "Wallet lacks the required role"
+            );
+            return Err(Error::Forbidden);
+        }
+
+        Ok(RequireRole(wallet_address, PhantomData))
+    }
+}
+
+pub async fn has_role(pool: &sqlx::PgPool, wallet_address: &str, role: UserRole) -> Result<bool> {
+    let has_role = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1
+            FROM user_roles
+            WHERE wallet_address = $1
+              AND (role = $2 OR role = 'admin')
+        ) AS "has_role!"
+        "#,
+        wallet_address,
+        role as UserRole
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(has_role)
+}
@@ -0,0 +1,101 @@
+use axum::{Json, extract::State, http::StatusCode};
+use garde::Validate;
+
+use crate::{
+    AppState, Error, Result,
+    http::{
+        auth::parse_wallet_address,
+        role::{Admin, RequireRole, RoleChange, RoleChangeAction, RoleChangeRequest, UserRole},
+    },
+};
+
+#[tracing::instrument(name = "grant_role_handler", skip(state, payload))]
+pub async fn grant_role_handler(
+    State(state): State<AppState>,
+    RequireRole(admin, _): RequireRole<Admin>,
+    Json(payload): Json<RoleChangeRequest>,
+) -> Result<(StatusCode, Json<RoleChange>)> {
+    payload.validate()?;
+    let (_, wallet_address) =
+        parse_wallet_address(&payload.wallet_address).expect("address validated above");
+    let mut tx = state.db.pool.begin().await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO escrow_users (wallet_address)
+        VALUES ($1)
+        ON CONFLICT (wallet_address) DO NOTHING
+        "#,
+        wallet_address
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let granted = sqlx::query!(
+        r#"
+        INSERT INTO user_roles (wallet_address, role, granted_by)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (wallet_address, role) DO NOTHING
+        "#,
+        wallet_address,
+        payload.role as UserRole,
+        admin
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if granted == 0 {
+        tracing::warn!(wallet = %wallet_address, role = %payload.role, "Role already granted");
+        return Err(Error::Conflict);
+    }
+
+    let change = record_role_change(
+        &mut tx,
+        &wallet_address,
+        payload.role,
+        RoleChangeAction::Granted,
+        &admin,
+        payload.reason.as_deref(),
+    )
+    .await?;
+
+    tx.commit().await?;
+    tracing::info!(wallet = %wallet_address, role = %payload.role, "Role granted");
+
+    Ok((StatusCode::CREATED, Json(change)))
+}
+
+pub(super) async fn record_role_change(
+    tx: &mut sqlx::PgConnection,
+    wallet_address: &str,
+    role: UserRole,
+    action: RoleChangeAction,
+    changed_by: &str,
+    reason: Option<&str>,
+) -> Result<RoleChange> {
+    let change = sqlx::query_as!(
+        RoleChange,
+        r#"
+        INSERT INTO role_changes (wallet_address, role, action, changed_by, reason)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING
+            id,
+            wallet_address,
+            role AS "role: UserRole",
+            action AS "action: RoleChangeAction",
+            changed_by,
+            reason,
+            created_at
+        "#,
+        wallet_address,
+        role as UserRole,
+        action as RoleChangeAction,
+        changed_by,
+        reason
+    )
+    .fetch_one(tx)
+    .await?;
+
+    Ok(change)
+}
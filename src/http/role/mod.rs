@@ -0,0 +1,27 @@
+mod domain;
+mod extractor;
+mod grant_role;
+mod revoke_role;
+mod wallet_roles;
+
+use axum::{
+    Router,
+    routing::{get, post},
+};
+pub use domain::*;
+pub use extractor::*;
+
+use crate::AppState;
+
+pub(crate) fn router() -> Router<AppState> {
+    Router::new()
+        .route("/admin/roles/grant", post(grant_role::grant_role_handler))
+        .route(
+            "/admin/roles/revoke",
+            post(revoke_role::revoke_role_handler),
+        )
+        .route(
+            "/admin/roles/{wallet_address}",
+            get(wallet_roles::wallet_roles_handler),
+        )
+}
@@ -0,0 +1,64 @@
+use axum::{Json, extract::State};
+use garde::Validate;
+
+use crate::{
+    AppState, Error, Result,
+    http::{
+        auth::parse_wallet_address,
+        role::{
+            Admin, RequireRole, RoleChange, RoleChangeAction, RoleChangeRequest, UserRole,
+            grant_role::record_role_change,
+        },
+    },
+};
+
+#[tracing::instrument(name = "revoke_role_handler", skip(state, payload))]
+pub async fn revoke_role_handler(
+    State(state): State<AppState>,
+    RequireRole(admin, _): RequireRole<Admin>,
+    Json(payload): Json<RoleChangeRequest>,
+) -> Result<Json<RoleChange>> {
+    payload.validate()?;
+    let (_, wallet_address) =
+        parse_wallet_address(&payload.wallet_address).expect("address validated above");
+
+    // Revoking your own admin role could leave the platform without any admin.
+    if wallet_address == admin && payload.role == UserRole::Admin {
+        tracing::warn!(wallet = %admin, "Admin attempted to revoke their own admin role");
+        return Err(Error::Forbidden);
+    }
+
+    let mut tx = state.db.pool.begin().await?;
+
+    let revoked = sqlx::query!(
+        r#"
+        DELETE FROM user_roles
+        WHERE wallet_address = $1 AND role = $2
+        "#,
+        wallet_address,
+        payload.role as UserRole
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if revoked == 0 {
+        tracing::warn!(wallet = %wallet_address, role = %payload.role, "Role not held");
+        return Err(Error::NotFound);
+    }
+
+    let change = record_role_change(
+        &mut tx,
+        &wallet_address,
+        payload.role,
+        RoleChangeAction::Revoked,
+        &admin,
+        payload.reason.as_deref(),
+    )
+    .await?;
+
+    tx.commit().await?;
+    tracing::info!(wallet = %wallet_address, role = %payload.role, "Role revoked");
+
+    Ok(Json(change))
+}
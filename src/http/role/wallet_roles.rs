@@ -0,0 +1,60 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    AppState, Error, Result,
+    http::{
+        auth::parse_wallet_address,
+        role::{Admin, RequireRole, RoleChange, RoleChangeAction, UserRole, WalletRolesResponse},
+    },
+};
+
+#[tracing::instrument(name = "wallet_roles_handler", skip(state))]
+pub async fn wallet_roles_handler(
+    State(state): State<AppState>,
+    _admin: RequireRole<Admin>,
+    Path(wallet_address): Path<String>,
+) -> Result<Json<WalletRolesResponse>> {
+    let (_, wallet_address) = parse_wallet_address(&wallet_address)
+        .ok_or_else(|| Error::InvalidRequest("Invalid Starknet address".to_string()))?;
+
+    let roles = sqlx::query_scalar!(
+        r#"
+        SELECT role AS "role: UserRole"
+        FROM user_roles
+        WHERE wallet_address = $1
+        ORDER BY role
+        "#,
+        wallet_address
+    )
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    let changes = sqlx::query_as!(
+        RoleChange,
+        r#"
+        SELECT
+            id,
+            wallet_address,
+            role AS "role: UserRole",
+            action AS "action: RoleChangeAction",
+            changed_by,
+            reason,
+            created_at
+        FROM role_changes
+        WHERE wallet_address = $1
+        ORDER BY created_at DESC
+        "#,
+        wallet_address
+    )
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    Ok(Json(WalletRolesResponse {
+        wallet_address,
+        roles,
+        changes,
+    }))
+}
@@ -0,0 +1,7 @@
+/// `ts_headline` options for the snippets of full-text search results: a few words around the best
+/// match, with the matched terms wrapped in `<mark>`.
+///
+/// Snippets are HTML. The searched text goes through the `html_escape` SQL function before
+/// `ts_headline`, so `<mark>` is the only markup in them and they can be rendered as is.
+pub(crate) const SNIPPET_OPTIONS: &str =
+    "StartSel=<mark>, StopSel=</mark>, MinWords=15, MaxWords=35, MaxFragments=2";
@@ -1,10 +1,17 @@
 use axum::{Json, extract::State, http::StatusCode};
 
-use crate::{AppState, Error, Result, http::support_ticket::AssignSupportTicketRequest};
+use crate::{
+    AppState, Error, Result,
+    http::{
+        role::{RequireRole, SupportAgent},
+        support_ticket::AssignSupportTicketRequest,
+    },
+};
 
 #[tracing::instrument(name = "assign_ticket_handler", skip(state, payload))]
 pub async fn assign_ticket_handler(
     state: State<AppState>,
+    RequireRole(support_agent_wallet, _): RequireRole<SupportAgent>,
     Json(payload): Json<AssignSupportTicketRequest>,
 ) -> Result<StatusCode> {
     let db = &state.db;
@@ -12,7 +19,7 @@ pub async fn assign_ticket_handler(
 
     tracing::info!(
         ticket_id = %payload.ticket_id,
-        agent = %payload.support_agent_wallet,
+        agent = %support_agent_wallet,
         "Attempting to assign ticket"
     );
 
@@ -44,46 +51,23 @@ pub async fn assign_ticket_handler(
         return Err(Error::Conflict);
     }
 
-    let agent_info: (String, bool) = match sqlx::query_as(
+    let is_busy: bool = sqlx::query_scalar(
         r#"
-        SELECT
-            type::TEXT,
-            EXISTS(
-                SELECT 1
-                FROM request_ticket
-                WHERE assigned_to = $1
-                AND status IN ('assigned', 'in_progress', 'open')
-            ) AS is_busy
-        FROM escrow_users
-        WHERE wallet_address = $1
+        SELECT EXISTS(
+            SELECT 1
+            FROM request_ticket
+            WHERE assigned_to = $1
+            AND status IN ('assigned', 'in_progress', 'open')
+        )
         "#,
     )
-    .bind(&payload.support_agent_wallet)
-    .fetch_optional(&mut *tx)
-    .await?
-    {
-        Some(row) => row,
-        None => {
-            tracing::warn!(
-                agent = %payload.support_agent_wallet,
-                "Agent not found"
-            );
-            return Err(Error::NotFound);
-        }
-    };
-
-    if agent_info.0 != "support_agent" {
-        tracing::warn!(
-            agent = %payload.support_agent_wallet,
-            agent_type = %agent_info.0,
-            "User is not a support agent"
-        );
-        return Err(Error::Forbidden);
-    }
+    .bind(&support_agent_wallet)
+    .fetch_one(&mut *tx)
+    .await?;
 
-    if agent_info.1 {
+    if is_busy {
         tracing::warn!(
-            agent = %payload.support_agent_wallet,
+            agent = %support_agent_wallet,
             "Agent is already assigned to another ticket"
         );
         return Err(Error::Conflict);
@@ -98,7 +82,7 @@ pub async fn assign_ticket_handler(
             updated_at = NOW()
         WHERE id = $2
         "#,
-        &payload.support_agent_wallet,
+        &support_agent_wallet,
         payload.ticket_id
     )
     .execute(&mut *tx)
@@ -118,7 +102,7 @@ pub async fn assign_ticket_handler(
     tx.commit().await?;
     tracing::info!(
         ticket_id = %payload.ticket_id,
-        agent = %payload.support_agent_wallet,
+        agent = %support_agent_wallet,
         "Ticket successfully assigned"
     );
 
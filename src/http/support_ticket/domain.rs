@@ -8,14 +8,11 @@ pub struct OpenSupportTicketRequest {
     pub subject: String,
     #[garde(ascii, length(min = 10, max = 5000))]
     pub message: String,
-    #[garde(ascii, length(bytes, equal = 66))]
-    pub opened_by: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AssignSupportTicketRequest {
     pub ticket_id: Uuid,
-    pub support_agent_wallet: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
@@ -24,8 +21,6 @@ pub struct ResolveSupportTicketRequest {
     pub ticket_id: Uuid,
     #[garde(ascii, length(min = 10, max = 5000))]
     pub resolution_response: String,
-    #[garde(ascii, length(bytes, equal = 66))]
-    pub resolved_by: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
@@ -58,10 +53,10 @@ pub struct ListTicketsQuery {
 }
 
 pub fn validate_sort(sort: &Option<String>, _context: &()) -> garde::Result {
-    if let Some(sort) = sort {
-        if !["asc", "desc"].contains(&sort.as_str()) {
-            return Err(garde::Error::new("Invalid sort specified"));
-        }
+    if let Some(sort) = sort
+        && !["asc", "desc"].contains(&sort.as_str())
+    {
+        return Err(garde::Error::new("Invalid sort specified"));
     }
     Ok(())
 }
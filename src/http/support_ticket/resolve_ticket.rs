@@ -1,10 +1,17 @@
-use crate::{AppState, Error, Result, http::support_ticket::ResolveSupportTicketRequest};
+use crate::{
+    AppState, Error, Result,
+    http::{
+        role::{Admin, RequireRole},
+        support_ticket::ResolveSupportTicketRequest,
+    },
+};
 use axum::{Json, extract::State, http::StatusCode};
 use garde::Validate;
 
 #[tracing::instrument(name = "resolve_ticket_handler", skip(state, payload))]
 pub async fn resolve_ticket_handler(
     state: State<AppState>,
+    RequireRole(resolved_by, _): RequireRole<Admin>,
     Json(payload): Json<ResolveSupportTicketRequest>,
 ) -> Result<StatusCode> {
     payload.validate()?;
@@ -13,6 +20,7 @@ pub async fn resolve_ticket_handler(
 
     tracing::info!(
         ticket_id = %payload.ticket_id,
+        resolver = %resolved_by,
         "Attempting to resolve support ticket"
     );
 
@@ -46,36 +54,6 @@ pub async fn resolve_ticket_handler(
         return Err(Error::Conflict);
     }
 
-    let resolver_type: String = match sqlx::query_scalar(
-        r#"
-        SELECT type::TEXT
-        FROM escrow_users
-        WHERE wallet_address = $1
-        "#,
-    )
-    .bind(&payload.resolved_by)
-    .fetch_optional(&mut *tx)
-    .await?
-    {
-        Some(resolver_type) => resolver_type,
-        None => {
-            tracing::warn!(
-                resolver = %payload.resolved_by,
-                "Resolver user not found"
-            );
-            return Err(Error::NotFound);
-        }
-    };
-
-    if resolver_type != "admin" {
-        tracing::warn!(
-            resolver = %payload.resolved_by,
-            resolver_type = %resolver_type,
-            "Only admins can resolve tickets"
-        );
-        return Err(Error::Forbidden);
-    }
-
     let rows_updated = sqlx::query!(
         r#"
         UPDATE request_ticket
@@ -1,29 +1,32 @@
-use crate::{AppState, Result, http::transaction::DepositRequest};
+use crate::{
+    AppState, Result,
+    http::{auth::AuthenticatedWallet, transaction::DepositRequest},
+    ledger::open_escrow_account,
+};
 use axum::{Json, extract::State, http::StatusCode};
 use garde::Validate;
 
 #[tracing::instrument(name = "deposit_handler", skip(state, payload))]
 pub async fn deposit_handler(
     state: State<AppState>,
+    AuthenticatedWallet(wallet_address): AuthenticatedWallet,
     Json(payload): Json<DepositRequest>,
 ) -> Result<StatusCode> {
     payload.validate()?;
     let mut tx = state.db.pool.begin().await?;
 
+    open_escrow_account(&mut tx, &wallet_address).await?;
+
+    // The balance is only credited once the chain watcher sees the escrow contract's `Deposit` event
+    // for this transaction, with the amount from the event.
     sqlx::query!(
         r#"
-        WITH balance_update AS (
-            INSERT INTO escrow_users (wallet_address, balance)
-            VALUES ($1, $2)
-            ON CONFLICT (wallet_address) DO UPDATE
-            SET balance = COALESCE(escrow_users.balance, 0) + EXCLUDED.balance
-        )
         INSERT INTO escrow_transactions (
             wallet_address, amount, currency, transaction_hash, notes, type, status
         )
-        VALUES ($1, $2, $3, $4, $5, 'deposit', 'completed')
+        VALUES ($1, $2, $3, $4, $5, 'deposit', 'pending')
         "#,
-        payload.wallet_address,
+        wallet_address,
         payload.amount,
         payload.currency,
         payload.transaction_hash,
@@ -1,11 +1,11 @@
 use bigdecimal::{BigDecimal, Zero};
+use chrono::{DateTime, Utc};
 use garde::Validate;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 #[derive(Serialize, Deserialize, Validate)]
 pub struct DepositRequest {
-    #[garde(custom(validate_starknet_address))]
-    pub wallet_address: String,
     #[garde(custom(validate_deposit_amount))]
     pub amount: BigDecimal,
     #[garde(custom(validate_currency))]
@@ -16,17 +16,6 @@ pub struct DepositRequest {
     pub transaction_hash: String,
 }
 
-pub fn validate_starknet_address(address: &str, _context: &()) -> garde::Result {
-    if address.starts_with("0x")
-        && address.len() == 66
-        && address.chars().skip(2).all(|c| c.is_ascii_hexdigit())
-    {
-        Ok(())
-    } else {
-        Err(garde::Error::new("Invalid Starknet address"))
-    }
-}
-
 pub fn validate_deposit_amount(amount: &BigDecimal, _context: &()) -> garde::Result {
     if amount > &BigDecimal::zero() {
         Ok(())
@@ -51,16 +40,12 @@ pub fn validate_currency(currency: &str, _context: &()) -> garde::Result {
 
 #[derive(Serialize, Deserialize, Validate)]
 pub struct WithdrawalRequest {
-    #[garde(custom(validate_starknet_address))]
-    pub wallet_address: String,
     #[garde(custom(validate_withdrawal_amount))]
     pub amount: BigDecimal,
     #[garde(custom(validate_currency))]
     pub currency: String,
     #[garde(inner(length(min = 1, max = 255)))]
     pub notes: Option<String>,
-    #[garde(length(equal = 65))]
-    pub transaction_hash: String,
 }
 
 pub fn validate_withdrawal_amount(amount: &BigDecimal, _context: &()) -> garde::Result {
@@ -72,3 +57,162 @@ pub fn validate_withdrawal_amount(amount: &BigDecimal, _context: &()) -> garde::
         ))
     }
 }
+
+/// Mirrors the `transaction_type` Postgres enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "transaction_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionType {
+    Deposit,
+    BountyAllocation,
+    BountyDisbursement,
+    Withdrawal,
+    BountyRefund,
+}
+
+/// Mirrors the `transaction_status` Postgres enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "transaction_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionStatus {
+    Pending,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ListTransactionsQuery {
+    /// Only admins may list another wallet's transactions.
+    #[garde(skip)]
+    pub wallet_address: Option<String>,
+    #[garde(skip)]
+    pub transaction_type: Option<TransactionType>,
+    #[garde(skip)]
+    pub transaction_status: Option<TransactionStatus>,
+    #[garde(inner(custom(validate_currency)))]
+    pub currency: Option<String>,
+    #[garde(skip)]
+    pub project_id: Option<Uuid>,
+    /// Inclusive lower bound on `created_at`.
+    #[garde(skip)]
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `created_at`.
+    #[garde(skip)]
+    pub to: Option<DateTime<Utc>>,
+    #[garde(inner(custom(validate_cursor)))]
+    pub cursor: Option<String>,
+    #[garde(range(min = 1, max = 100))]
+    pub limit: Option<i64>,
+}
+
+/// Position after the last transaction of a page, as `<created_at in microseconds>_<id>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl TransactionCursor {
+    pub fn encode(&self) -> String {
+        format!("{}_{}", self.created_at.timestamp_micros(), self.id)
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let (micros, id) = cursor.split_once('_')?;
+        Some(Self {
+            created_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?,
+            id: id.parse().ok()?,
+        })
+    }
+}
+
+pub fn validate_cursor(cursor: &str, _context: &()) -> garde::Result {
+    TransactionCursor::decode(cursor)
+        .map(|_| ())
+        .ok_or_else(|| garde::Error::new("Invalid cursor"))
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct EscrowTransaction {
+    pub id: Uuid,
+    pub wallet_address: String,
+    pub project_id: Option<Uuid>,
+    pub transaction_type: TransactionType,
+    pub status: TransactionStatus,
+    pub amount: BigDecimal,
+    pub currency: String,
+    /// Absent while a withdrawal waits to be paid out.
+    pub transaction_hash: Option<String>,
+    pub notes: Option<String>,
+    /// The wallet's available balance in `currency` once this transaction is applied. Pending
+    /// withdrawals count against it; pending deposits do not.
+    pub running_balance: BigDecimal,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransactionPage {
+    pub transactions: Vec<EscrowTransaction>,
+    /// Pass as `cursor` to fetch the next page. Absent on the last page.
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PayoutControlRequest {
+    pub paused: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PayoutStatus {
+    pub paused: bool,
+    pub updated_by: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Withdrawals waiting to be submitted, not counting those still awaiting approval.
+    pub queued: i64,
+}
+
+/// Mirrors the `withdrawal_request_status` Postgres enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "withdrawal_request_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum WithdrawalApprovalStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListWithdrawalApprovalsQuery {
+    /// Defaults to `pending`.
+    pub status: Option<WithdrawalApprovalStatus>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RejectWithdrawalRequest {
+    #[garde(length(min = 1, max = 1000))]
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WithdrawalApproval {
+    pub admin_address: String,
+    pub approved_at: DateTime<Utc>,
+}
+
+/// A withdrawal above its currency's threshold, held until enough admins approve it.
+#[derive(Debug, Serialize)]
+pub struct WithdrawalApprovalRequest {
+    pub id: Uuid,
+    pub transaction_id: Uuid,
+    pub wallet_address: String,
+    pub amount: BigDecimal,
+    pub currency: String,
+    pub status: WithdrawalApprovalStatus,
+    pub approvals_required: i32,
+    pub approvals: Vec<WithdrawalApproval>,
+    pub rejected_by: Option<String>,
+    pub rejection_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,112 @@
+use crate::{
+    AppState, Error, Result,
+    http::{
+        auth::AuthenticatedWallet,
+        role::{UserRole, has_role},
+        transaction::{
+            EscrowTransaction, ListTransactionsQuery, TransactionCursor, TransactionPage,
+        },
+    },
+};
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use garde::Validate;
+
+/// GET /transactions?transaction_type=deposit&currency=STRK&from=...&to=...&cursor=...&limit=20
+///
+/// Lists the caller's escrow transactions, newest first, with the wallet's balance in the
+/// transaction's currency after each one. Admins see every wallet, or one through `wallet_address`.
+#[tracing::instrument(name = "list_transactions_handler", skip(state))]
+pub async fn list_transactions_handler(
+    State(state): State<AppState>,
+    AuthenticatedWallet(caller): AuthenticatedWallet,
+    Query(params): Query<ListTransactionsQuery>,
+) -> Result<Json<TransactionPage>> {
+    params.validate()?;
+
+    let wallet_address = if has_role(&state.db.pool, &caller, UserRole::Admin).await? {
+        params.wallet_address.clone()
+    } else {
+        if params
+            .wallet_address
+            .as_ref()
+            .is_some_and(|wallet| wallet != &caller)
+        {
+            tracing::warn!(caller = %caller, "Only admins can list another wallet's transactions");
+            return Err(Error::Forbidden);
+        }
+        Some(caller)
+    };
+
+    let cursor = params.cursor.as_deref().and_then(TransactionCursor::decode);
+    let limit = params.limit.unwrap_or(20);
+
+    // The running balance is computed over the wallet's whole history before any filter applies.
+    let mut transactions = sqlx::query_as::<_, EscrowTransaction>(
+        r#"
+        WITH history AS (
+            SELECT
+                t.*,
+                SUM(
+                    CASE
+                        -- A pending withdrawal already holds its funds.
+                        WHEN t.status = 'pending' AND t.type = 'withdrawal' THEN -t.amount
+                        WHEN t.status <> 'completed' THEN 0
+                        WHEN t.type IN ('deposit', 'bounty_disbursement', 'bounty_refund') THEN t.amount
+                        ELSE -t.amount
+                    END
+                ) OVER (
+                    PARTITION BY t.wallet_address, t.currency
+                    ORDER BY t.created_at, t.id
+                ) AS running_balance
+            FROM escrow_transactions t
+            WHERE $1::text IS NULL OR t.wallet_address = $1
+        )
+        SELECT
+            id, wallet_address, project_id, type AS transaction_type, status, amount, currency,
+            transaction_hash, notes, running_balance, created_at, updated_at
+        FROM history
+        WHERE ($2::transaction_type IS NULL OR type = $2)
+          AND ($3::transaction_status IS NULL OR status = $3)
+          AND ($4::text IS NULL OR currency = $4)
+          AND ($5::uuid IS NULL OR project_id = $5)
+          AND ($6::timestamptz IS NULL OR created_at >= $6)
+          AND ($7::timestamptz IS NULL OR created_at < $7)
+          AND ($8::timestamptz IS NULL OR (created_at, id) < ($8, $9))
+        ORDER BY created_at DESC, id DESC
+        LIMIT $10
+        "#,
+    )
+    .bind(&wallet_address)
+    .bind(params.transaction_type)
+    .bind(params.transaction_status)
+    .bind(&params.currency)
+    .bind(params.project_id)
+    .bind(params.from)
+    .bind(params.to)
+    .bind(cursor.map(|cursor| cursor.created_at))
+    .bind(cursor.map(|cursor| cursor.id))
+    .bind(limit + 1)
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    let next_cursor = if transactions.len() as i64 > limit {
+        transactions.truncate(limit as usize);
+        transactions.last().map(|last| {
+            TransactionCursor {
+                created_at: last.created_at,
+                id: last.id,
+            }
+            .encode()
+        })
+    } else {
+        None
+    };
+
+    Ok(Json(TransactionPage {
+        transactions,
+        next_cursor,
+    }))
+}
@@ -1,14 +1,50 @@
 mod deposit;
 mod domain;
+mod list_transactions;
+mod payout_controls;
 mod withdraw;
+mod withdrawal_approvals;
 
-use axum::{Router, routing::post};
+use axum::{
+    Router,
+    middleware::from_fn_with_state,
+    routing::{get, post},
+};
 pub use domain::*;
 
-use crate::AppState;
+use crate::{AppState, http::idempotency::idempotency_layer};
+
+pub(crate) fn router(app_state: &AppState) -> Router<AppState> {
+    let idempotency_layer = from_fn_with_state(app_state.clone(), idempotency_layer);
 
-pub(crate) fn router() -> Router<AppState> {
     Router::new()
-        .route("/deposit", post(deposit::deposit_handler))
-        .route("/withdraw", post(withdraw::withdraw_handler))
+        .route(
+            "/deposit",
+            post(deposit::deposit_handler).layer(idempotency_layer.clone()),
+        )
+        .route(
+            "/withdraw",
+            post(withdraw::withdraw_handler).layer(idempotency_layer),
+        )
+        .route(
+            "/transactions",
+            get(list_transactions::list_transactions_handler),
+        )
+        .route(
+            "/admin/payouts",
+            get(payout_controls::payout_status_handler)
+                .put(payout_controls::update_payouts_handler),
+        )
+        .route(
+            "/admin/withdrawals",
+            get(withdrawal_approvals::list_withdrawal_approvals_handler),
+        )
+        .route(
+            "/admin/withdrawals/{request_id}/approve",
+            post(withdrawal_approvals::approve_withdrawal_handler),
+        )
+        .route(
+            "/admin/withdrawals/{request_id}/reject",
+            post(withdrawal_approvals::reject_withdrawal_handler),
+        )
 }
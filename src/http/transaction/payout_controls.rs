@@ -0,0 +1,86 @@
+use axum::{Json, extract::State};
+use sqlx::PgConnection;
+
+use crate::{
+    AppState, Result,
+    http::{
+        role::{Admin, RequireRole},
+        transaction::{PayoutControlRequest, PayoutStatus},
+    },
+    jobs::PAYOUTS_JOB,
+};
+
+/// GET /admin/payouts
+///
+/// Whether the payout worker is stopped, and how many withdrawals wait to be submitted.
+#[tracing::instrument(name = "payout_status_handler", skip(state))]
+pub async fn payout_status_handler(
+    State(state): State<AppState>,
+    _admin: RequireRole<Admin>,
+) -> Result<Json<PayoutStatus>> {
+    let mut conn = state.db.pool.acquire().await?;
+    Ok(Json(payout_status(&mut conn).await?))
+}
+
+/// PUT /admin/payouts
+///
+/// Kill switch of the payout worker. Withdrawals can still be requested while it is stopped; they
+/// are queued with their funds reserved.
+#[tracing::instrument(name = "update_payouts_handler", skip(state, payload))]
+pub async fn update_payouts_handler(
+    State(state): State<AppState>,
+    RequireRole(admin, _): RequireRole<Admin>,
+    Json(payload): Json<PayoutControlRequest>,
+) -> Result<Json<PayoutStatus>> {
+    let mut tx = state.db.pool.begin().await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO job_controls (job, paused, updated_by, updated_at)
+        VALUES ($1, $2, $3, NOW())
+        ON CONFLICT (job) DO UPDATE
+        SET paused = EXCLUDED.paused,
+            updated_by = EXCLUDED.updated_by,
+            updated_at = EXCLUDED.updated_at
+        "#,
+        PAYOUTS_JOB,
+        payload.paused,
+        admin
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let status = payout_status(&mut tx).await?;
+    tx.commit().await?;
+    tracing::warn!(paused = payload.paused, "Payout kill switch changed");
+
+    Ok(Json(status))
+}
+
+async fn payout_status(conn: &mut PgConnection) -> Result<PayoutStatus> {
+    let status = sqlx::query_as!(
+        PayoutStatus,
+        r#"
+        SELECT
+            COALESCE(c.paused, FALSE) AS "paused!",
+            c.updated_by,
+            c.updated_at,
+            (
+                SELECT COUNT(*)
+                FROM escrow_transactions t
+                LEFT JOIN withdrawal_requests r ON r.transaction_id = t.id
+                WHERE t.type = 'withdrawal'
+                  AND t.status = 'pending'
+                  AND t.transaction_hash IS NULL
+                  AND (r.id IS NULL OR r.status = 'approved')
+            ) AS "queued!"
+        FROM (SELECT 1) AS one
+        LEFT JOIN job_controls c ON c.job = $1
+        "#,
+        PAYOUTS_JOB
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(status)
+}
@@ -1,64 +1,93 @@
-use crate::{AppState, Error, Result, http::transaction::WithdrawalRequest};
+use crate::{
+    AppState, Error, Result,
+    http::{auth::AuthenticatedWallet, transaction::WithdrawalRequest},
+    ledger::{LedgerAccount, debit_balance, record_transfer},
+};
 use axum::{Json, extract::State, http::StatusCode};
 use garde::Validate;
 
+/// POST /withdraw
+///
+/// Reserves the funds and queues the withdrawal for payout. Withdrawals above the approval
+/// threshold of their currency answer `202 Accepted` and are only paid out once enough admins
+/// approved them.
 #[tracing::instrument(name = "withdraw_handler", skip(state, payload))]
 pub async fn withdraw_handler(
     state: State<AppState>,
+    AuthenticatedWallet(wallet_address): AuthenticatedWallet,
     Json(payload): Json<WithdrawalRequest>,
 ) -> Result<StatusCode> {
     payload.validate()?;
     let mut tx = state.db.pool.begin().await?;
 
-    let query_result = sqlx::query!(
+    let exists: bool = sqlx::query_scalar(
         r#"
-        WITH balance_update AS (
-            UPDATE escrow_users
-            SET balance = balance - $2
-            WHERE wallet_address = $1 AND balance >= $2
-            RETURNING 1
-        )
-        INSERT INTO escrow_transactions (
-            wallet_address, amount, currency, transaction_hash, notes, type, status
-        )
-        SELECT $1, $2, $3, $4, $5, 'withdrawal', 'completed'
-        FROM balance_update
+        SELECT EXISTS(SELECT 1 FROM escrow_users WHERE wallet_address = $1)
         "#,
-        payload.wallet_address,
+    )
+    .bind(&wallet_address)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if !exists {
+        return Err(Error::NotFound);
+    }
+
+    if !debit_balance(&mut tx, &wallet_address, &payload.currency, &payload.amount).await? {
+        tracing::warn!(currency = %payload.currency, "Insufficient funds for withdrawal");
+        return Err(Error::InvalidRequest("Insufficient Funds".to_string()));
+    }
+
+    // The funds stay reserved until the payout worker has sent them, and are released if it fails.
+    let transaction_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO escrow_transactions (wallet_address, amount, currency, notes, type, status)
+        VALUES ($1, $2, $3, $4, 'withdrawal', 'pending')
+        RETURNING id
+        "#,
+        wallet_address,
         payload.amount,
         payload.currency,
-        payload.transaction_hash,
         payload.notes
     )
-    .execute(&mut *tx)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    record_transfer(
+        &mut tx,
+        LedgerAccount::User(&wallet_address),
+        LedgerAccount::Platform,
+        &payload.currency,
+        &payload.amount,
+        Some(transaction_id),
+        "Withdrawal",
+    )
     .await?;
 
-    match query_result.rows_affected() {
-        1 => {
-            tx.commit().await?;
-            Ok(StatusCode::CREATED)
-        }
-        0 => {
-            let exists: bool = sqlx::query_scalar(
-                r#"
-                SELECT EXISTS(SELECT 1 FROM escrow_users WHERE wallet_address = $1)
-                "#,
-            )
-            .bind(payload.wallet_address)
-            .fetch_one(&mut *tx)
-            .await?;
+    let needs_approval = state
+        .configuration
+        .withdrawal_approval_thresholds
+        .get(&payload.currency)
+        .is_some_and(|threshold| &payload.amount > threshold);
+    if needs_approval {
+        sqlx::query!(
+            r#"
+            INSERT INTO withdrawal_requests (transaction_id, approvals_required)
+            VALUES ($1, $2)
+            "#,
+            transaction_id,
+            state.configuration.withdrawal_approvals_required as i32
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
 
-            if exists {
-                Err(Error::InvalidRequest("Insufficient Funds".to_string()))
-            } else {
-                Err(Error::NotFound)
-            }
-        }
-        _ => {
-            tracing::error!("Unexpected rows affected {}", query_result.rows_affected());
-            Err(Error::InternalServerError(anyhow::anyhow!(
-                "Unexpected rows affected"
-            )))
-        }
+    if needs_approval {
+        tracing::info!(%transaction_id, "Withdrawal awaits admin approval");
+        Ok(StatusCode::ACCEPTED)
+    } else {
+        Ok(StatusCode::CREATED)
     }
 }
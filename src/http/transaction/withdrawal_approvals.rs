@@ -0,0 +1,282 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use garde::Validate;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::{
+    AppState, Error, Result,
+    http::{
+        role::{Admin, RequireRole},
+        transaction::{
+            ListWithdrawalApprovalsQuery, RejectWithdrawalRequest, WithdrawalApproval,
+            WithdrawalApprovalRequest, WithdrawalApprovalStatus,
+        },
+    },
+    jobs::release_withdrawal,
+};
+
+/// Held withdrawals returned by one listing, oldest first.
+const MAX_LISTED_WITHDRAWALS: i64 = 100;
+
+/// GET /admin/withdrawals
+///
+/// Withdrawals held for approval, by default those still waiting for it.
+#[tracing::instrument(name = "list_withdrawal_approvals_handler", skip(state))]
+pub async fn list_withdrawal_approvals_handler(
+    State(state): State<AppState>,
+    _admin: RequireRole<Admin>,
+    Query(query): Query<ListWithdrawalApprovalsQuery>,
+) -> Result<Json<Vec<WithdrawalApprovalRequest>>> {
+    let status = query.status.unwrap_or(WithdrawalApprovalStatus::Pending);
+    let mut conn = state.db.pool.acquire().await?;
+    Ok(Json(fetch_requests(&mut conn, None, Some(status)).await?))
+}
+
+/// POST /admin/withdrawals/{request_id}/approve
+///
+/// Records the admin's approval. The withdrawal is released to the payout worker once the required
+/// number of distinct admins approved it. Admins cannot approve their own withdrawals.
+#[tracing::instrument(name = "approve_withdrawal_handler", skip(state))]
+pub async fn approve_withdrawal_handler(
+    State(state): State<AppState>,
+    RequireRole(admin, _): RequireRole<Admin>,
+    Path(request_id): Path<Uuid>,
+) -> Result<Json<WithdrawalApprovalRequest>> {
+    let mut tx = state.db.pool.begin().await?;
+    let request = lock_pending_request(&mut tx, request_id).await?;
+
+    if request.wallet_address == admin {
+        tracing::warn!(%request_id, "Admin tried to approve their own withdrawal");
+        return Err(Error::Forbidden);
+    }
+
+    let approved = sqlx::query_scalar!(
+        r#"
+        INSERT INTO withdrawal_approvals (withdrawal_request_id, admin_address)
+        VALUES ($1, $2)
+        ON CONFLICT DO NOTHING
+        RETURNING admin_address
+        "#,
+        request_id,
+        admin
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if approved.is_none() {
+        return Err(Error::conflict(
+            "withdrawal_already_approved",
+            "you already approved this withdrawal",
+        ));
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE withdrawal_requests r
+        SET status = 'approved', decided_at = NOW()
+        WHERE id = $1
+          AND (SELECT COUNT(*) FROM withdrawal_approvals WHERE withdrawal_request_id = r.id)
+              >= r.approvals_required
+        "#,
+        request_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let request = fetch_request(&mut tx, request_id).await?;
+    tx.commit().await?;
+    tracing::info!(
+        %request_id,
+        approvals = request.approvals.len(),
+        status = ?request.status,
+        "Withdrawal approved"
+    );
+
+    Ok(Json(request))
+}
+
+/// POST /admin/withdrawals/{request_id}/reject
+///
+/// Fails the withdrawal and releases its reserved funds back to the wallet. A single admin's
+/// rejection is enough.
+#[tracing::instrument(name = "reject_withdrawal_handler", skip(state, payload))]
+pub async fn reject_withdrawal_handler(
+    State(state): State<AppState>,
+    RequireRole(admin, _): RequireRole<Admin>,
+    Path(request_id): Path<Uuid>,
+    Json(payload): Json<RejectWithdrawalRequest>,
+) -> Result<Json<WithdrawalApprovalRequest>> {
+    payload.validate()?;
+    let mut tx = state.db.pool.begin().await?;
+    let request = lock_pending_request(&mut tx, request_id).await?;
+
+    release_withdrawal(
+        &mut tx,
+        request.transaction_id,
+        &request.wallet_address,
+        &request.currency,
+        &request.amount,
+    )
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE escrow_transactions
+        SET status = 'failed', updated_at = NOW()
+        WHERE id = $1
+        "#,
+        request.transaction_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE withdrawal_requests
+        SET status = 'rejected',
+            rejected_by = $2,
+            rejection_reason = $3,
+            decided_at = NOW()
+        WHERE id = $1
+        "#,
+        request_id,
+        admin,
+        payload.reason
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let request = fetch_request(&mut tx, request_id).await?;
+    tx.commit().await?;
+    tracing::warn!(%request_id, "Withdrawal rejected");
+
+    Ok(Json(request))
+}
+
+struct PendingRequest {
+    transaction_id: Uuid,
+    wallet_address: String,
+    amount: bigdecimal::BigDecimal,
+    currency: String,
+}
+
+/// Locks a held withdrawal and its transaction, failing unless it still awaits a decision.
+async fn lock_pending_request(conn: &mut PgConnection, request_id: Uuid) -> Result<PendingRequest> {
+    let request = sqlx::query!(
+        r#"
+        SELECT
+            r.status AS "status: WithdrawalApprovalStatus",
+            t.id AS transaction_id,
+            t.wallet_address,
+            t.amount,
+            t.currency
+        FROM withdrawal_requests r
+        JOIN escrow_transactions t ON t.id = r.transaction_id
+        WHERE r.id = $1
+        FOR UPDATE
+        "#,
+        request_id
+    )
+    .fetch_optional(conn)
+    .await?
+    .ok_or(Error::NotFound)?;
+
+    if request.status != WithdrawalApprovalStatus::Pending {
+        return Err(Error::conflict(
+            "withdrawal_not_pending",
+            "the withdrawal was already approved or rejected",
+        ));
+    }
+
+    Ok(PendingRequest {
+        transaction_id: request.transaction_id,
+        wallet_address: request.wallet_address,
+        amount: request.amount,
+        currency: request.currency,
+    })
+}
+
+async fn fetch_request(
+    conn: &mut PgConnection,
+    request_id: Uuid,
+) -> Result<WithdrawalApprovalRequest> {
+    fetch_requests(conn, Some(request_id), None)
+        .await?
+        .pop()
+        .ok_or(Error::NotFound)
+}
+
+async fn fetch_requests(
+    conn: &mut PgConnection,
+    request_id: Option<Uuid>,
+    status: Option<WithdrawalApprovalStatus>,
+) -> Result<Vec<WithdrawalApprovalRequest>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            r.id,
+            r.transaction_id,
+            t.wallet_address,
+            t.amount,
+            t.currency,
+            r.status AS "status: WithdrawalApprovalStatus",
+            r.approvals_required,
+            r.rejected_by,
+            r.rejection_reason,
+            r.created_at,
+            r.decided_at
+        FROM withdrawal_requests r
+        JOIN escrow_transactions t ON t.id = r.transaction_id
+        WHERE ($1::uuid IS NULL OR r.id = $1)
+          AND ($2::withdrawal_request_status IS NULL OR r.status = $2)
+        ORDER BY r.created_at, r.id
+        LIMIT $3
+        "#,
+        request_id,
+        status as Option<WithdrawalApprovalStatus>,
+        MAX_LISTED_WITHDRAWALS
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+    let approvals = sqlx::query!(
+        r#"
+        SELECT withdrawal_request_id, admin_address, approved_at
+        FROM withdrawal_approvals
+        WHERE withdrawal_request_id = ANY($1)
+        ORDER BY approved_at, admin_address
+        "#,
+        &ids
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| WithdrawalApprovalRequest {
+            approvals: approvals
+                .iter()
+                .filter(|approval| approval.withdrawal_request_id == row.id)
+                .map(|approval| WithdrawalApproval {
+                    admin_address: approval.admin_address.clone(),
+                    approved_at: approval.approved_at,
+                })
+                .collect(),
+            id: row.id,
+            transaction_id: row.transaction_id,
+            wallet_address: row.wallet_address,
+            amount: row.amount,
+            currency: row.currency,
+            status: row.status,
+            approvals_required: row.approvals_required,
+            rejected_by: row.rejected_by,
+            rejection_reason: row.rejection_reason,
+            created_at: row.created_at,
+            decided_at: row.decided_at,
+        })
+        .collect())
+}
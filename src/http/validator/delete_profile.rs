@@ -1,31 +1,27 @@
 use crate::{
     AppState, Error, Result,
-    http::validator::{DeleteValidatorProfileRequest, DeleteValidatorProfileResponse},
+    http::{auth::AuthenticatedWallet, validator::DeleteValidatorProfileResponse},
 };
 use axum::{Json, extract::State, http::StatusCode};
-use garde::Validate;
 use sqlx::PgPool;
 use uuid::Uuid;
 
-#[tracing::instrument(name = "Delete Validator Profile", skip(state, request))]
+#[tracing::instrument(name = "Delete Validator Profile", skip(state))]
 pub async fn delete_validator_profile(
     State(state): State<AppState>,
-    Json(request): Json<DeleteValidatorProfileRequest>,
+    AuthenticatedWallet(wallet_address): AuthenticatedWallet,
 ) -> Result<(StatusCode, Json<DeleteValidatorProfileResponse>)> {
-    request.validate()?;
-
     tracing::info!(
-        wallet_address = %request.wallet_address,
+        wallet_address = %wallet_address,
         "Attempting to delete validator profile"
     );
 
     // First, verify the validator profile exists
-    let validator =
-        get_validator_by_wallet_address(&state.db.pool, &request.wallet_address).await?;
+    let validator = get_validator_by_wallet_address(&state.db.pool, &wallet_address).await?;
 
     if validator.is_none() {
         tracing::warn!(
-            wallet_address = %request.wallet_address,
+            wallet_address = %wallet_address,
             "Validator profile not found"
         );
         return Err(Error::NotFound);
@@ -35,12 +31,11 @@ pub async fn delete_validator_profile(
     let deletion_time = chrono::Utc::now();
 
     // Perform the deletion with all related data cleanup
-    delete_validator_and_related_data(&state.db.pool, &validator.id, &request.wallet_address)
-        .await?;
+    delete_validator_and_related_data(&state.db.pool, &validator.id, &wallet_address).await?;
 
     tracing::info!(
         validator_id = %validator.id,
-        wallet_address = %request.wallet_address,
+        wallet_address = %wallet_address,
         "Successfully deleted validator profile and all related data"
     );
 
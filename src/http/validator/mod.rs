@@ -1,12 +1,12 @@
-
 mod delete_profile;
 mod domain;
 mod view_profile;
 
-
-use axum::{Router, routing::{delete, post}};
+use axum::{
+    Router,
+    routing::{delete, post},
+};
 pub use domain::*;
-pub use view_profile::*;
 
 use crate::AppState;
 
@@ -73,7 +73,7 @@ async fn get_validator_profile(
             vp.wallet_address = $1
         GROUP BY
             vp.id
-        "#
+        "#,
     )
     .bind(wallet_address)
     .fetch_optional(pool)
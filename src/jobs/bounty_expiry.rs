@@ -0,0 +1,179 @@
+use crate::{
+    Result,
+    http::generate_transaction_hash,
+    ledger::{LedgerAccount, credit_balance, record_transfer, take_bounty_funds},
+};
+use bigdecimal::{BigDecimal, Zero};
+use sqlx::{PgConnection, PgPool};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// How often expired bounties are looked for.
+pub const BOUNTY_EXPIRY_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Returns every expired bounty to its funders, and the ids of the projects refunded.
+///
+/// A project whose reports are not all rejected or closed is held back: an accepted report still
+/// has to be paid from the bounty, and one in review may yet be accepted. It is refunded on a later
+/// run, once they are settled.
+pub async fn refund_expired_bounties(pool: &PgPool) -> Result<Vec<Uuid>> {
+    let expired = sqlx::query_scalar!(
+        r#"
+        SELECT p.id
+        FROM projects p
+        WHERE p.closed_at IS NULL
+          AND p.bounty_expiry_date <= NOW()
+          AND p.bounty_amount > 0
+          AND NOT EXISTS (
+              SELECT 1
+              FROM research_report r
+              WHERE r.project_id = p.id AND r.status NOT IN ('rejected', 'closed')
+          )
+        ORDER BY p.bounty_expiry_date, p.id
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut refunded = Vec::new();
+    for project_id in expired {
+        let mut tx = pool.begin().await?;
+        // Checked again under the lock, in case the owner extended the bounty or a report came in.
+        let project = sqlx::query!(
+            r#"
+            SELECT
+                owner_address,
+                bounty_amount AS "bounty_amount!",
+                bounty_currency AS "bounty_currency!"
+            FROM projects p
+            WHERE id = $1
+              AND closed_at IS NULL
+              AND bounty_expiry_date <= NOW()
+              AND bounty_amount > 0
+              AND bounty_currency IS NOT NULL
+              AND NOT EXISTS (
+                  SELECT 1
+                  FROM research_report r
+                  WHERE r.project_id = p.id AND r.status NOT IN ('rejected', 'closed')
+              )
+            FOR UPDATE SKIP LOCKED
+            "#,
+            project_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(project) = project else {
+            continue;
+        };
+
+        refund_bounty(
+            &mut tx,
+            project_id,
+            &project.owner_address,
+            &project.bounty_currency,
+            &project.bounty_amount,
+            "Bounty refunded on expiry",
+        )
+        .await?;
+        sqlx::query!(
+            "UPDATE projects SET bounty_amount = 0, updated_at = NOW() WHERE id = $1",
+            project_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        tracing::info!(
+            %project_id,
+            amount = %project.bounty_amount,
+            currency = %project.bounty_currency,
+            "Expired bounty refunded"
+        );
+        refunded.push(project_id);
+    }
+
+    Ok(refunded)
+}
+
+/// Moves `amount` of a project's bounty back to the balances of those who funded it, each up to
+/// their share, recording the refunds. Anything the shares do not cover goes to the owner. The
+/// caller zeroes or closes the bounty.
+pub(crate) async fn refund_bounty(
+    conn: &mut PgConnection,
+    project_id: Uuid,
+    owner_address: &str,
+    currency: &str,
+    amount: &BigDecimal,
+    notes: &str,
+) -> Result<()> {
+    let mut left = amount.clone();
+    for (funder, share) in take_bounty_funds(&mut *conn, project_id).await? {
+        let refund = share.min(left.clone());
+        if refund.is_zero() {
+            break;
+        }
+        refund_to(&mut *conn, project_id, &funder, currency, &refund, notes).await?;
+        left -= refund;
+    }
+    if left > BigDecimal::zero() {
+        refund_to(conn, project_id, owner_address, currency, &left, notes).await?;
+    }
+
+    Ok(())
+}
+
+/// Moves `amount` of a project's bounty to the wallet's balance, recording the refund.
+async fn refund_to(
+    conn: &mut PgConnection,
+    project_id: Uuid,
+    wallet_address: &str,
+    currency: &str,
+    amount: &BigDecimal,
+    notes: &str,
+) -> Result<()> {
+    credit_balance(&mut *conn, wallet_address, currency, amount).await?;
+    let transaction_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO escrow_transactions (
+            wallet_address, project_id, type, amount, currency,
+            transaction_hash, status, notes
+        )
+        VALUES ($1, $2, 'bounty_refund', $3, $4, $5, 'completed', $6)
+        RETURNING id
+        "#,
+        wallet_address,
+        project_id,
+        amount,
+        currency,
+        generate_transaction_hash(),
+        notes
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+    record_transfer(
+        conn,
+        LedgerAccount::ProjectPool(project_id),
+        LedgerAccount::User(wallet_address),
+        currency,
+        amount,
+        Some(transaction_id),
+        notes,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Runs [`refund_expired_bounties`] every [`BOUNTY_EXPIRY_INTERVAL`].
+pub fn spawn_bounty_expiry_job(pool: PgPool) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(BOUNTY_EXPIRY_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = refund_expired_bounties(&pool).await {
+                tracing::error!("Failed to refund expired bounties: {e}");
+            }
+        }
+    })
+}
@@ -0,0 +1,452 @@
+use crate::{
+    Configuration, EscrowToken,
+    ledger::{LedgerAccount, credit_balance, debit_balance, open_escrow_account, record_transfer},
+};
+use anyhow::Context;
+use bigdecimal::{
+    BigDecimal, RoundingMode,
+    num_bigint::{BigInt, Sign},
+};
+use sqlx::{PgConnection, PgPool};
+use starknet::{
+    core::{
+        types::{
+            BlockId, EmittedEvent, EventFilter, Felt, MaybePreConfirmedBlockWithTxHashes,
+            StarknetError,
+        },
+        utils::get_selector_from_name,
+    },
+    providers::{JsonRpcClient, Provider, ProviderError, Url, jsonrpc::HttpTransport},
+};
+use std::{collections::HashMap, time::Duration};
+use tokio::task::JoinHandle;
+
+/// How often the escrow contract is polled for new blocks.
+pub const CHAIN_WATCHER_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Most blocks scanned per poll, so a watcher far behind the chain catches up in steps.
+const MAX_BLOCKS_PER_POLL: u64 = 1000;
+
+/// Events requested per `starknet_getEvents` page.
+const EVENTS_CHUNK_SIZE: u64 = 100;
+
+/// Checkpoints kept to find where the chain diverged after a reorg.
+const CHECKPOINT_WINDOW: i64 = 64;
+
+/// Digits an `escrow_transactions.amount` (`numeric(30, 2)`) holds.
+const MAX_AMOUNT_DIGITS: u64 = 30;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WatchSummary {
+    /// Deposits credited during this poll.
+    pub deposits: usize,
+    /// Deposits undone because their block was reorged out.
+    pub reverted: usize,
+    /// Last block processed.
+    pub checkpoint: Option<u64>,
+}
+
+/// Follows the escrow contract on Starknet and credits wallets from its `Deposit` events.
+///
+/// The event is expected as `Deposit { #[key] depositor, token, amount: u256 }`, i.e. keys
+/// `[selector, depositor]` and data `[token, amount.low, amount.high]`. Deposits are matched to
+/// `escrow_transactions` by transaction hash, so a deposit the client already reported through
+/// `POST /deposit` is completed with the amount from the event rather than booked twice.
+pub struct ChainWatcher {
+    pool: PgPool,
+    provider: JsonRpcClient<HttpTransport>,
+    contract: Felt,
+    tokens: HashMap<Felt, EscrowToken>,
+    start_block: u64,
+    deposit_selector: Felt,
+}
+
+impl ChainWatcher {
+    /// Builds a watcher from `configuration`, or `None` when no escrow contract is configured.
+    pub fn new(pool: PgPool, configuration: &Configuration) -> anyhow::Result<Option<Self>> {
+        let Some(contract) = &configuration.escrow_contract_address else {
+            return Ok(None);
+        };
+        let contract =
+            Felt::from_hex(contract).context("ESCROW_CONTRACT_ADDRESS is not a valid address")?;
+        let tokens = configuration
+            .escrow_tokens
+            .iter()
+            .map(|token| {
+                let address = Felt::from_hex(&token.address)
+                    .with_context(|| format!("{} has an invalid token address", token.currency))?;
+                Ok((address, token.clone()))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        let url = Url::parse(&configuration.starknet_rpc_url)?;
+
+        Ok(Some(Self {
+            pool,
+            provider: JsonRpcClient::new(HttpTransport::new(url)),
+            contract,
+            tokens,
+            start_block: configuration.escrow_start_block,
+            deposit_selector: get_selector_from_name("Deposit")?,
+        }))
+    }
+
+    /// Undoes deposits from reorged blocks, then ingests the deposits of the blocks after the last
+    /// checkpoint.
+    pub async fn poll(&self) -> anyhow::Result<WatchSummary> {
+        let mut summary = WatchSummary::default();
+
+        let mut checkpoint = self.checkpoints().await?.into_iter().next();
+        if let Some((number, hash)) = checkpoint
+            && self.block_hash(number).await? != Some(hash)
+        {
+            checkpoint = self.common_ancestor().await?;
+            summary.reverted = self.rewind(checkpoint.map(|(number, _)| number)).await?;
+        }
+        summary.checkpoint = checkpoint.map(|(number, _)| number);
+
+        let from = checkpoint.map_or(self.start_block, |(number, _)| number + 1);
+        let latest = self.provider.block_number().await?;
+        if from > latest {
+            return Ok(summary);
+        }
+        let to = latest.min(from + MAX_BLOCKS_PER_POLL - 1);
+        // The block may have been reorged out since `latest` was read; the next poll retries.
+        let Some(to_hash) = self.block_hash(to).await? else {
+            return Ok(summary);
+        };
+
+        let events = self.deposit_events(from, to).await?;
+
+        let mut tx = self.pool.begin().await?;
+        for event in &events {
+            if self.ingest(&mut tx, event).await? {
+                summary.deposits += 1;
+            }
+        }
+        self.save_checkpoint(&mut tx, to, to_hash).await?;
+        tx.commit().await?;
+
+        summary.checkpoint = Some(to);
+        Ok(summary)
+    }
+
+    /// Hash of the canonical block at `number`, `None` if the chain has not produced it yet.
+    async fn block_hash(&self, number: u64) -> anyhow::Result<Option<Felt>> {
+        match self
+            .provider
+            .get_block_with_tx_hashes(BlockId::Number(number))
+            .await
+        {
+            Ok(MaybePreConfirmedBlockWithTxHashes::Block(block)) => Ok(Some(block.block_hash)),
+            Ok(MaybePreConfirmedBlockWithTxHashes::PreConfirmedBlock(_)) => Ok(None),
+            Err(ProviderError::StarknetError(StarknetError::BlockNotFound)) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Stored checkpoints, newest first.
+    async fn checkpoints(&self) -> anyhow::Result<Vec<(u64, Felt)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT block_number, block_hash
+            FROM chain_checkpoints
+            WHERE contract_address = $1
+            ORDER BY block_number DESC
+            "#,
+            self.contract.to_fixed_hex_string()
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Ok((row.block_number as u64, Felt::from_hex(&row.block_hash)?)))
+            .collect()
+    }
+
+    /// Newest checkpoint still on the canonical chain, `None` if the reorg is deeper than the window.
+    async fn common_ancestor(&self) -> anyhow::Result<Option<(u64, Felt)>> {
+        for (number, hash) in self.checkpoints().await? {
+            if self.block_hash(number).await? == Some(hash) {
+                return Ok(Some((number, hash)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Reverses the deposits ingested after block `ancestor` and drops the checkpoints past it.
+    async fn rewind(&self, ancestor: Option<u64>) -> anyhow::Result<usize> {
+        let after = ancestor.map_or(-1, |number| number as i64);
+        tracing::warn!(after, "Chain reorg detected, rewinding escrow deposits");
+
+        let mut tx = self.pool.begin().await?;
+        let deposits = sqlx::query!(
+            r#"
+            SELECT id, wallet_address, amount, currency
+            FROM escrow_transactions
+            WHERE type = 'deposit' AND status = 'completed' AND block_number > $1
+            ORDER BY block_number, id
+            FOR UPDATE
+            "#,
+            after
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut reverted = 0;
+        for deposit in deposits {
+            if !debit_balance(
+                &mut tx,
+                &deposit.wallet_address,
+                &deposit.currency,
+                &deposit.amount,
+            )
+            .await?
+            {
+                tracing::error!(
+                    transaction_id = %deposit.id,
+                    "Reorged deposit was already spent and cannot be reversed"
+                );
+                continue;
+            }
+            record_transfer(
+                &mut tx,
+                LedgerAccount::User(&deposit.wallet_address),
+                LedgerAccount::Platform,
+                &deposit.currency,
+                &deposit.amount,
+                Some(deposit.id),
+                "Deposit reorged out",
+            )
+            .await?;
+            sqlx::query!(
+                r#"
+                UPDATE escrow_transactions
+                SET status = 'failed', updated_at = NOW()
+                WHERE id = $1
+                "#,
+                deposit.id
+            )
+            .execute(&mut *tx)
+            .await?;
+            reverted += 1;
+        }
+
+        sqlx::query!(
+            r#"
+            DELETE FROM chain_checkpoints
+            WHERE contract_address = $1 AND block_number > $2
+            "#,
+            self.contract.to_fixed_hex_string(),
+            after
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(reverted)
+    }
+
+    /// Every `Deposit` event the escrow contract emitted in blocks `from..=to`.
+    async fn deposit_events(&self, from: u64, to: u64) -> anyhow::Result<Vec<EmittedEvent>> {
+        let filter = EventFilter {
+            from_block: Some(BlockId::Number(from)),
+            to_block: Some(BlockId::Number(to)),
+            address: Some(self.contract),
+            keys: Some(vec![vec![self.deposit_selector]]),
+        };
+
+        let mut events = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let page = self
+                .provider
+                .get_events(filter.clone(), continuation_token, EVENTS_CHUNK_SIZE)
+                .await?;
+            events.extend(page.events);
+            continuation_token = page.continuation_token;
+            if continuation_token.is_none() {
+                return Ok(events);
+            }
+        }
+    }
+
+    /// Credits the deposit `event` describes. Returns `false` if it was skipped or already booked.
+    async fn ingest(&self, conn: &mut PgConnection, event: &EmittedEvent) -> anyhow::Result<bool> {
+        let transaction_hash = event.transaction_hash.to_fixed_hex_string();
+        let ([_, depositor, ..], [token, low, high, ..], Some(block_number), Some(block_hash)) = (
+            event.keys.as_slice(),
+            event.data.as_slice(),
+            event.block_number,
+            event.block_hash,
+        ) else {
+            tracing::warn!(%transaction_hash, "Skipping malformed Deposit event");
+            return Ok(false);
+        };
+        let Some(token) = self.tokens.get(token) else {
+            tracing::warn!(%transaction_hash, token = %token, "Skipping deposit of unknown token");
+            return Ok(false);
+        };
+        let Some(amount) = token_amount(*low, *high, token.decimals) else {
+            tracing::warn!(%transaction_hash, "Skipping deposit with an unbookable amount");
+            return Ok(false);
+        };
+        let wallet_address = depositor.to_fixed_hex_string();
+
+        // Clients may have reported the hash without its leading zeros.
+        let existing = sqlx::query!(
+            r#"
+            SELECT id, type = 'deposit' AS "is_deposit!", status = 'completed' AS "completed!"
+            FROM escrow_transactions
+            WHERE transaction_hash IN ($1, $2)
+            FOR UPDATE
+            "#,
+            transaction_hash,
+            event.transaction_hash.to_hex_string()
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        open_escrow_account(&mut *conn, &wallet_address).await?;
+
+        let transaction_id = match existing {
+            Some(existing) if !existing.is_deposit => {
+                tracing::warn!(%transaction_hash, "Deposit hash is already booked as another type");
+                return Ok(false);
+            }
+            Some(existing) if existing.completed => return Ok(false),
+            // Reported by the client, or reorged out and now included again.
+            Some(existing) => {
+                sqlx::query!(
+                    r#"
+                    UPDATE escrow_transactions
+                    SET transaction_hash = $2,
+                        wallet_address = $3,
+                        amount = $4,
+                        currency = $5,
+                        block_number = $6,
+                        block_hash = $7,
+                        status = 'completed',
+                        updated_at = NOW()
+                    WHERE id = $1
+                    "#,
+                    existing.id,
+                    transaction_hash,
+                    wallet_address,
+                    amount,
+                    token.currency,
+                    block_number as i64,
+                    block_hash.to_fixed_hex_string()
+                )
+                .execute(&mut *conn)
+                .await?;
+                existing.id
+            }
+            None => {
+                sqlx::query_scalar!(
+                    r#"
+                    INSERT INTO escrow_transactions (
+                        wallet_address, amount, currency, transaction_hash, type, status,
+                        block_number, block_hash
+                    )
+                    VALUES ($1, $2, $3, $4, 'deposit', 'completed', $5, $6)
+                    RETURNING id
+                    "#,
+                    wallet_address,
+                    amount,
+                    token.currency,
+                    transaction_hash,
+                    block_number as i64,
+                    block_hash.to_fixed_hex_string()
+                )
+                .fetch_one(&mut *conn)
+                .await?
+            }
+        };
+
+        credit_balance(&mut *conn, &wallet_address, &token.currency, &amount).await?;
+        record_transfer(
+            &mut *conn,
+            LedgerAccount::Platform,
+            LedgerAccount::User(&wallet_address),
+            &token.currency,
+            &amount,
+            Some(transaction_id),
+            "Deposit",
+        )
+        .await?;
+
+        tracing::info!(%transaction_id, %transaction_hash, "Deposit ingested from chain");
+        Ok(true)
+    }
+
+    async fn save_checkpoint(
+        &self,
+        conn: &mut PgConnection,
+        block_number: u64,
+        block_hash: Felt,
+    ) -> anyhow::Result<()> {
+        let contract_address = self.contract.to_fixed_hex_string();
+        sqlx::query!(
+            r#"
+            INSERT INTO chain_checkpoints (contract_address, block_number, block_hash)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (contract_address, block_number) DO UPDATE
+            SET block_hash = EXCLUDED.block_hash, created_at = NOW()
+            "#,
+            contract_address,
+            block_number as i64,
+            block_hash.to_fixed_hex_string()
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM chain_checkpoints
+            WHERE contract_address = $1
+              AND block_number NOT IN (
+                SELECT block_number
+                FROM chain_checkpoints
+                WHERE contract_address = $1
+                ORDER BY block_number DESC
+                LIMIT $2
+              )
+            "#,
+            contract_address,
+            CHECKPOINT_WINDOW
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Converts a `u256` token amount to the currency's units, truncated to cents. `None` when it is
+/// zero once truncated or too large to book.
+fn token_amount(low: Felt, high: Felt, decimals: u32) -> Option<BigDecimal> {
+    let high = BigInt::from_bytes_be(Sign::Plus, &high.to_bytes_be());
+    let low = BigInt::from_bytes_be(Sign::Plus, &low.to_bytes_be());
+    let amount = BigDecimal::new((high << 128) + low, decimals as i64)
+        .with_scale_round(2, RoundingMode::Down);
+
+    let zero = BigDecimal::from(0);
+    (amount > zero && amount.digits() <= MAX_AMOUNT_DIGITS).then_some(amount)
+}
+
+/// Runs [`ChainWatcher::poll`] every [`CHAIN_WATCHER_POLL_INTERVAL`].
+pub fn spawn_chain_watcher(watcher: ChainWatcher) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CHAIN_WATCHER_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            match watcher.poll().await {
+                Ok(summary) if summary.deposits + summary.reverted > 0 => {
+                    tracing::info!(?summary, "Processed escrow contract events");
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Failed to poll the escrow contract: {e:#}"),
+            }
+        }
+    })
+}
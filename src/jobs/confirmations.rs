@@ -0,0 +1,180 @@
+use crate::{
+    Result,
+    chain::{ChainTransactionStatus, ConfirmationSource},
+    ledger::{LedgerAccount, credit_balance, record_transfer},
+};
+use bigdecimal::BigDecimal;
+use sqlx::{PgConnection, PgPool};
+use std::{sync::Arc, time::Duration};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// How often pending withdrawals are checked against the chain.
+pub const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Pending withdrawals looked at per run.
+const CONFIRMATION_BATCH_SIZE: i64 = 100;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SettlementSummary {
+    pub confirmed: usize,
+    pub failed: usize,
+    pub pending: usize,
+}
+
+/// Settles every pending withdrawal whose hash the chain reports as final.
+///
+/// A failed withdrawal releases the funds it reserved. Deposits are not looked at: a final
+/// transaction says nothing about what it moved, so only the chain watcher settles a deposit, with
+/// the amount from the escrow contract's `Deposit` event. A deposit claimed with a hash that never
+/// emits one stays pending without holding up the withdrawals here.
+pub async fn settle_pending_transactions(
+    pool: &PgPool,
+    source: &dyn ConfirmationSource,
+) -> Result<SettlementSummary> {
+    let pending = sqlx::query!(
+        r#"
+        SELECT id, transaction_hash AS "transaction_hash!"
+        FROM escrow_transactions
+        WHERE status = 'pending'
+          AND type = 'withdrawal'
+          -- Withdrawals get a hash once the payout worker submits them.
+          AND transaction_hash IS NOT NULL
+        ORDER BY created_at, id
+        LIMIT $1
+        "#,
+        CONFIRMATION_BATCH_SIZE
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut summary = SettlementSummary::default();
+    for transaction in pending {
+        let status = match source.status(&transaction.transaction_hash).await {
+            Ok(status) => status,
+            Err(e) => {
+                tracing::warn!(
+                    transaction_id = %transaction.id,
+                    "Failed to fetch transaction status: {e}"
+                );
+                summary.pending += 1;
+                continue;
+            }
+        };
+
+        match status {
+            ChainTransactionStatus::Pending => summary.pending += 1,
+            ChainTransactionStatus::Confirmed => {
+                if settle_transaction(pool, transaction.id, status).await? {
+                    summary.confirmed += 1;
+                }
+            }
+            ChainTransactionStatus::Failed => {
+                if settle_transaction(pool, transaction.id, status).await? {
+                    summary.failed += 1;
+                }
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Moves one pending withdrawal to its final status. Returns `false` if it was settled meanwhile.
+async fn settle_transaction(
+    pool: &PgPool,
+    transaction_id: Uuid,
+    status: ChainTransactionStatus,
+) -> Result<bool> {
+    let mut tx = pool.begin().await?;
+
+    let transaction = sqlx::query!(
+        r#"
+        SELECT wallet_address, amount, currency
+        FROM escrow_transactions
+        WHERE id = $1 AND status = 'pending'
+        FOR UPDATE SKIP LOCKED
+        "#,
+        transaction_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(transaction) = transaction else {
+        return Ok(false);
+    };
+
+    let confirmed = status == ChainTransactionStatus::Confirmed;
+    if !confirmed {
+        release_withdrawal(
+            &mut tx,
+            transaction_id,
+            &transaction.wallet_address,
+            &transaction.currency,
+            &transaction.amount,
+        )
+        .await?;
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE escrow_transactions
+        SET status = CASE WHEN $2 THEN 'completed' ELSE 'failed' END::transaction_status,
+            updated_at = NOW()
+        WHERE id = $1
+        "#,
+        transaction_id,
+        confirmed
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    tracing::info!(
+        transaction_id = %transaction_id,
+        confirmed,
+        "Escrow transaction settled"
+    );
+
+    Ok(true)
+}
+
+/// Returns the funds a failed withdrawal reserved when it was requested.
+pub(crate) async fn release_withdrawal(
+    conn: &mut PgConnection,
+    transaction_id: Uuid,
+    wallet_address: &str,
+    currency: &str,
+    amount: &BigDecimal,
+) -> Result<()> {
+    credit_balance(&mut *conn, wallet_address, currency, amount).await?;
+    record_transfer(
+        conn,
+        LedgerAccount::Platform,
+        LedgerAccount::User(wallet_address),
+        currency,
+        amount,
+        Some(transaction_id),
+        "Withdrawal reversed",
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Runs [`settle_pending_transactions`] every [`CONFIRMATION_POLL_INTERVAL`].
+pub fn spawn_confirmation_job(pool: PgPool, source: Arc<dyn ConfirmationSource>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CONFIRMATION_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            match settle_pending_transactions(&pool, source.as_ref()).await {
+                Ok(summary) if summary.confirmed + summary.failed > 0 => {
+                    tracing::info!(?summary, "Settled pending escrow transactions");
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Failed to settle pending escrow transactions: {e}"),
+            }
+        }
+    })
+}
@@ -0,0 +1,40 @@
+use crate::Result;
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Hours an idempotency key is remembered for.
+pub const IDEMPOTENCY_KEY_TTL_HOURS: i32 = 24;
+
+/// How often expired idempotency keys are deleted.
+pub const IDEMPOTENCY_KEY_SWEEP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Deletes the idempotency keys older than [`IDEMPOTENCY_KEY_TTL_HOURS`], returning how many were
+/// deleted.
+pub async fn delete_expired_idempotency_keys(pool: &PgPool) -> Result<u64> {
+    let deleted = sqlx::query!(
+        r#"
+        DELETE FROM idempotency_keys
+        WHERE created_at < NOW() - make_interval(hours => $1)
+        "#,
+        IDEMPOTENCY_KEY_TTL_HOURS
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(deleted)
+}
+
+/// Runs [`delete_expired_idempotency_keys`] every [`IDEMPOTENCY_KEY_SWEEP_INTERVAL`].
+pub fn spawn_idempotency_key_sweep_job(pool: PgPool) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(IDEMPOTENCY_KEY_SWEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = delete_expired_idempotency_keys(&pool).await {
+                tracing::error!("Failed to delete expired idempotency keys: {e}");
+            }
+        }
+    })
+}
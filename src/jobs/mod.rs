@@ -0,0 +1,15 @@
+//! Background jobs that run alongside the HTTP server.
+
+mod bounty_expiry;
+mod chain_watcher;
+mod confirmations;
+mod idempotency_keys;
+mod payouts;
+mod reconciliation;
+
+pub use bounty_expiry::*;
+pub use chain_watcher::*;
+pub use confirmations::*;
+pub use idempotency_keys::*;
+pub use payouts::*;
+pub use reconciliation::*;
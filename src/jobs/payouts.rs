@@ -0,0 +1,369 @@
+use crate::{
+    Configuration, EscrowToken, Result,
+    chain::{
+        GasPrices, LocalPayoutSigner, PayoutNode, PayoutSigner, RpcPayoutNode, SubmitError,
+        Transfer,
+    },
+    jobs::release_withdrawal,
+};
+use anyhow::Context;
+use bigdecimal::{BigDecimal, num_bigint::Sign};
+use sqlx::PgPool;
+use starknet::core::types::{Felt, U256};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// How often queued withdrawals are picked up.
+pub const PAYOUT_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Submissions a withdrawal gets before it is failed and refunded.
+pub const MAX_PAYOUT_ATTEMPTS: i32 = 5;
+
+/// Seconds before a rejected withdrawal is retried, doubled with every further rejection.
+const PAYOUT_RETRY_BASE_SECONDS: f64 = 30.0;
+
+/// Withdrawals submitted per run.
+const PAYOUT_BATCH_SIZE: i64 = 20;
+
+/// Kill switch of the payout worker in `job_controls`.
+pub const PAYOUTS_JOB: &str = "payouts";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PayoutSummary {
+    /// The kill switch stopped the run.
+    pub paused: bool,
+    pub submitted: usize,
+    /// Rejected by the node and scheduled for another attempt.
+    pub rejected: usize,
+    /// Given up on and refunded.
+    pub failed: usize,
+}
+
+/// What became of one queued withdrawal.
+enum Payout {
+    Submitted,
+    Rejected,
+    Failed,
+    /// The node may have taken the transfer; the nonce can no longer be trusted.
+    Unknown,
+    /// Claimed by another worker, or no longer queued.
+    Skipped,
+}
+
+/// Pays pending withdrawals out of the platform hot wallet.
+///
+/// The hash of a signed transfer is stored before it is submitted, and only cleared again when the
+/// node rejects it. A transfer that may have reached the node is never signed a second time; the
+/// confirmation job settles it from its hash like any other pending withdrawal.
+pub struct PayoutWorker {
+    pool: PgPool,
+    signer: Arc<dyn PayoutSigner>,
+    node: Arc<dyn PayoutNode>,
+    /// Token address and decimals by currency.
+    tokens: HashMap<String, (Felt, u32)>,
+}
+
+impl PayoutWorker {
+    pub fn new(
+        pool: PgPool,
+        signer: Arc<dyn PayoutSigner>,
+        node: Arc<dyn PayoutNode>,
+        tokens: &[EscrowToken],
+    ) -> anyhow::Result<Self> {
+        let tokens = tokens
+            .iter()
+            .map(|token| {
+                let address = Felt::from_hex(&token.address)
+                    .with_context(|| format!("{} has an invalid token address", token.currency))?;
+                Ok((token.currency.clone(), (address, token.decimals)))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Self {
+            pool,
+            signer,
+            node,
+            tokens,
+        })
+    }
+
+    /// Builds a worker paying out of the configured hot wallet, or `None` when there is none.
+    pub fn from_configuration(
+        pool: PgPool,
+        configuration: &Configuration,
+    ) -> anyhow::Result<Option<Self>> {
+        let (Some(address), Some(private_key)) = (
+            &configuration.payout_account_address,
+            &configuration.payout_private_key,
+        ) else {
+            return Ok(None);
+        };
+
+        let signer = LocalPayoutSigner::new(
+            &configuration.starknet_rpc_url,
+            &configuration.starknet_chain_id,
+            address,
+            private_key,
+        )?;
+        let node = RpcPayoutNode::new(&configuration.starknet_rpc_url)?;
+        Self::new(
+            pool,
+            Arc::new(signer),
+            Arc::new(node),
+            &configuration.escrow_tokens,
+        )
+        .map(Some)
+    }
+
+    /// Submits the withdrawals that are due, unless the kill switch is on.
+    pub async fn run(&self) -> anyhow::Result<PayoutSummary> {
+        let mut summary = PayoutSummary::default();
+        if payouts_paused(&self.pool).await? {
+            summary.paused = true;
+            return Ok(summary);
+        }
+
+        let queued = sqlx::query_scalar!(
+            r#"
+            SELECT t.id
+            FROM escrow_transactions t
+            LEFT JOIN withdrawal_requests r ON r.transaction_id = t.id
+            WHERE t.type = 'withdrawal'
+              AND t.status = 'pending'
+              AND t.transaction_hash IS NULL
+              AND (t.payout_next_attempt_at IS NULL OR t.payout_next_attempt_at <= NOW())
+              AND (r.id IS NULL OR r.status = 'approved')
+            ORDER BY t.created_at, t.id
+            LIMIT $1
+            "#,
+            PAYOUT_BATCH_SIZE
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        if queued.is_empty() {
+            return Ok(summary);
+        }
+
+        let gas_prices = self.node.gas_prices().await?;
+        let mut nonce = self.node.nonce(self.signer.address()).await?;
+
+        for transaction_id in queued {
+            // A pause takes effect between two submissions.
+            if payouts_paused(&self.pool).await? {
+                summary.paused = true;
+                break;
+            }
+
+            match self.pay_out(transaction_id, nonce, &gas_prices).await? {
+                Payout::Submitted => {
+                    summary.submitted += 1;
+                    nonce += Felt::ONE;
+                }
+                Payout::Rejected => summary.rejected += 1,
+                Payout::Failed => summary.failed += 1,
+                Payout::Unknown => break,
+                Payout::Skipped => {}
+            }
+        }
+
+        Ok(summary)
+    }
+
+    async fn pay_out(
+        &self,
+        transaction_id: Uuid,
+        nonce: Felt,
+        gas_prices: &GasPrices,
+    ) -> anyhow::Result<Payout> {
+        let mut tx = self.pool.begin().await?;
+        let withdrawal = sqlx::query!(
+            r#"
+            SELECT wallet_address, amount, currency, payout_attempts
+            FROM escrow_transactions t
+            WHERE id = $1
+              AND status = 'pending'
+              AND transaction_hash IS NULL
+              AND NOT EXISTS (
+                  SELECT 1 FROM withdrawal_requests r
+                  WHERE r.transaction_id = t.id AND r.status <> 'approved'
+              )
+            FOR UPDATE SKIP LOCKED
+            "#,
+            transaction_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(withdrawal) = withdrawal else {
+            return Ok(Payout::Skipped);
+        };
+
+        let transfer = self
+            .tokens
+            .get(&withdrawal.currency)
+            .and_then(|&(token, decimals)| {
+                Some(Transfer {
+                    token,
+                    recipient: Felt::from_hex(&withdrawal.wallet_address).ok()?,
+                    amount: base_units(&withdrawal.amount, decimals)?,
+                })
+            });
+        let Some(transfer) = transfer else {
+            let reason = format!("{} cannot be paid out", withdrawal.currency);
+            tracing::error!(%transaction_id, "{reason}");
+            release_withdrawal(
+                &mut tx,
+                transaction_id,
+                &withdrawal.wallet_address,
+                &withdrawal.currency,
+                &withdrawal.amount,
+            )
+            .await?;
+            fail_withdrawal(&mut tx, transaction_id, &reason).await?;
+            tx.commit().await?;
+            return Ok(Payout::Failed);
+        };
+
+        let invoke = self
+            .signer
+            .sign_transfer(&transfer, nonce, gas_prices)
+            .await?;
+        let transaction_hash = invoke.transaction_hash.to_fixed_hex_string();
+
+        // Recorded before submitting, so a crash cannot lead to the transfer being signed again.
+        sqlx::query!(
+            r#"
+            UPDATE escrow_transactions
+            SET transaction_hash = $2,
+                payout_attempts = payout_attempts + 1,
+                payout_error = NULL
+            WHERE id = $1
+            "#,
+            transaction_id,
+            transaction_hash
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        let reason = match self.node.submit(&invoke).await {
+            Ok(_) => {
+                tracing::info!(%transaction_id, %transaction_hash, "Withdrawal submitted");
+                return Ok(Payout::Submitted);
+            }
+            Err(SubmitError::Unknown(e)) => {
+                tracing::error!(
+                    %transaction_id,
+                    %transaction_hash,
+                    "Withdrawal submission outcome unknown: {e:#}"
+                );
+                return Ok(Payout::Unknown);
+            }
+            Err(SubmitError::Rejected(reason)) => reason,
+        };
+
+        let attempts = withdrawal.payout_attempts + 1;
+        tracing::warn!(%transaction_id, attempts, "Withdrawal rejected: {reason}");
+
+        let mut tx = self.pool.begin().await?;
+        if attempts >= MAX_PAYOUT_ATTEMPTS {
+            release_withdrawal(
+                &mut tx,
+                transaction_id,
+                &withdrawal.wallet_address,
+                &withdrawal.currency,
+                &withdrawal.amount,
+            )
+            .await?;
+            fail_withdrawal(&mut tx, transaction_id, &reason).await?;
+            tx.commit().await?;
+            return Ok(Payout::Failed);
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE escrow_transactions
+            SET transaction_hash = NULL,
+                payout_error = $2,
+                payout_next_attempt_at = NOW() + $3 * POWER(2, payout_attempts - 1) * INTERVAL '1 second'
+            WHERE id = $1
+            "#,
+            transaction_id,
+            reason,
+            PAYOUT_RETRY_BASE_SECONDS
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(Payout::Rejected)
+    }
+}
+
+/// Whether an admin has stopped the payout worker.
+pub async fn payouts_paused(pool: &PgPool) -> Result<bool> {
+    let paused = sqlx::query_scalar!(
+        "SELECT paused FROM job_controls WHERE job = $1",
+        PAYOUTS_JOB
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(paused.unwrap_or(false))
+}
+
+async fn fail_withdrawal(
+    conn: &mut sqlx::PgConnection,
+    transaction_id: Uuid,
+    reason: &str,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE escrow_transactions
+        SET status = 'failed',
+            transaction_hash = NULL,
+            payout_error = $2,
+            updated_at = NOW()
+        WHERE id = $1
+        "#,
+        transaction_id,
+        reason
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// `amount` in the smallest unit of a token with `decimals`, `None` if it does not fit a `u256`.
+fn base_units(amount: &BigDecimal, decimals: u32) -> Option<U256> {
+    let scale = BigDecimal::new(1.into(), -(decimals as i64));
+    let (units, _) = (amount * scale).with_scale(0).into_bigint_and_exponent();
+    let (sign, bytes) = units.to_bytes_be();
+    if sign == Sign::Minus || bytes.len() > 32 {
+        return None;
+    }
+
+    let mut word = [0u8; 32];
+    word[32 - bytes.len()..].copy_from_slice(&bytes);
+    let high = u128::from_be_bytes(word[..16].try_into().ok()?);
+    let low = u128::from_be_bytes(word[16..].try_into().ok()?);
+    Some(U256::from_words(low, high))
+}
+
+/// Runs [`PayoutWorker::run`] every [`PAYOUT_POLL_INTERVAL`].
+pub fn spawn_payout_worker(worker: PayoutWorker) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PAYOUT_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            match worker.run().await {
+                Ok(summary) if summary.submitted + summary.rejected + summary.failed > 0 => {
+                    tracing::info!(?summary, "Processed queued withdrawals");
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Failed to pay out withdrawals: {e:#}"),
+            }
+        }
+    })
+}
@@ -0,0 +1,257 @@
+use crate::Result;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// How often balances are reconciled against the transactions.
+pub const RECONCILIATION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Mirrors the `reconciliation_mismatch_kind` Postgres enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, sqlx::Type)]
+#[sqlx(type_name = "reconciliation_mismatch_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum MismatchKind {
+    WalletBalance,
+    ProjectBounty,
+}
+
+/// A balance or bounty that differs from what its transactions add up to.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReconciliationMismatch {
+    pub kind: MismatchKind,
+    pub wallet_address: Option<String>,
+    pub project_id: Option<Uuid>,
+    pub currency: Option<String>,
+    /// As stored in `escrow_balances` or `projects`.
+    pub recorded: BigDecimal,
+    /// What the transactions add up to.
+    pub expected: BigDecimal,
+}
+
+/// Outcome of one [`reconcile`] run.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReconciliationReport {
+    pub id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    /// Wallet balances checked, one per wallet and currency.
+    pub wallets_checked: i32,
+    pub projects_checked: i32,
+    pub mismatches: Vec<ReconciliationMismatch>,
+}
+
+/// Recomputes every escrow balance and project bounty from `escrow_transactions`, and records the
+/// ones that disagree.
+///
+/// A wallet's balance is its completed deposits, disbursements and refunds, minus its completed
+/// allocations and its withdrawals that have not failed: a pending withdrawal already holds its
+/// funds. A project's bounty is its completed allocations minus its disbursements and refunds.
+pub async fn reconcile(pool: &PgPool) -> Result<ReconciliationReport> {
+    let started_at = Utc::now();
+    let mut tx = pool.begin().await?;
+    // Balances and transactions are compared in a single snapshot.
+    sqlx::query!("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+        .execute(&mut *tx)
+        .await?;
+
+    let wallets = sqlx::query!(
+        r#"
+        WITH expected AS (
+            SELECT
+                wallet_address,
+                currency,
+                SUM(
+                    CASE
+                        WHEN type = 'withdrawal' AND status <> 'failed' THEN -amount
+                        WHEN status <> 'completed' THEN 0
+                        WHEN type IN ('deposit', 'bounty_disbursement', 'bounty_refund') THEN amount
+                        WHEN type = 'bounty_allocation' THEN -amount
+                        ELSE 0
+                    END
+                ) AS balance
+            FROM escrow_transactions
+            GROUP BY wallet_address, currency
+        )
+        SELECT
+            COALESCE(b.wallet_address, e.wallet_address) AS "wallet_address!",
+            COALESCE(b.currency, e.currency) AS "currency!",
+            COALESCE(b.balance, 0) AS "recorded!",
+            COALESCE(e.balance, 0) AS "expected!"
+        FROM escrow_balances b
+        FULL JOIN expected e
+            ON e.wallet_address = b.wallet_address AND e.currency = b.currency
+        ORDER BY 1, 2
+        "#
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let projects = sqlx::query!(
+        r#"
+        WITH expected AS (
+            SELECT
+                project_id,
+                SUM(CASE WHEN type = 'bounty_allocation' THEN amount ELSE -amount END) AS bounty
+            FROM escrow_transactions
+            WHERE status = 'completed'
+              AND type IN ('bounty_allocation', 'bounty_disbursement', 'bounty_refund')
+            GROUP BY project_id
+        )
+        SELECT
+            p.id,
+            p.bounty_currency,
+            COALESCE(p.bounty_amount, 0) AS "recorded!",
+            COALESCE(e.bounty, 0) AS "expected!"
+        FROM projects p
+        LEFT JOIN expected e ON e.project_id = p.id
+        ORDER BY p.id
+        "#
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let wallets_checked = wallets.len() as i32;
+    let projects_checked = projects.len() as i32;
+    let mismatches: Vec<_> = wallets
+        .into_iter()
+        .filter(|wallet| wallet.recorded != wallet.expected)
+        .map(|wallet| ReconciliationMismatch {
+            kind: MismatchKind::WalletBalance,
+            wallet_address: Some(wallet.wallet_address),
+            project_id: None,
+            currency: Some(wallet.currency),
+            recorded: wallet.recorded,
+            expected: wallet.expected,
+        })
+        .chain(
+            projects
+                .into_iter()
+                .filter(|project| project.recorded != project.expected)
+                .map(|project| ReconciliationMismatch {
+                    kind: MismatchKind::ProjectBounty,
+                    wallet_address: None,
+                    project_id: Some(project.id),
+                    currency: project.bounty_currency,
+                    recorded: project.recorded,
+                    expected: project.expected,
+                }),
+        )
+        .collect();
+
+    let run = sqlx::query!(
+        r#"
+        INSERT INTO reconciliation_runs (
+            started_at, finished_at, wallets_checked, projects_checked, mismatches
+        )
+        VALUES ($1, NOW(), $2, $3, $4)
+        RETURNING id, finished_at
+        "#,
+        started_at,
+        wallets_checked,
+        projects_checked,
+        mismatches.len() as i32
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    for mismatch in &mismatches {
+        sqlx::query!(
+            r#"
+            INSERT INTO reconciliation_mismatches (
+                run_id, kind, wallet_address, project_id, currency, recorded, expected
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            run.id,
+            mismatch.kind as MismatchKind,
+            mismatch.wallet_address,
+            mismatch.project_id,
+            mismatch.currency,
+            mismatch.recorded,
+            mismatch.expected
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    if !mismatches.is_empty() {
+        tracing::error!(
+            run_id = %run.id,
+            mismatches = mismatches.len(),
+            "Escrow balances do not reconcile with their transactions"
+        );
+    }
+
+    Ok(ReconciliationReport {
+        id: run.id,
+        started_at,
+        finished_at: run.finished_at,
+        wallets_checked,
+        projects_checked,
+        mismatches,
+    })
+}
+
+/// The most recent reconciliation, if one ever ran.
+pub async fn latest_reconciliation(pool: &PgPool) -> Result<Option<ReconciliationReport>> {
+    let run = sqlx::query!(
+        r#"
+        SELECT id, started_at, finished_at, wallets_checked, projects_checked
+        FROM reconciliation_runs
+        ORDER BY started_at DESC, id DESC
+        LIMIT 1
+        "#
+    )
+    .fetch_optional(pool)
+    .await?;
+    let Some(run) = run else {
+        return Ok(None);
+    };
+
+    let mismatches = sqlx::query_as!(
+        ReconciliationMismatch,
+        r#"
+        SELECT
+            kind AS "kind: MismatchKind",
+            wallet_address,
+            project_id,
+            currency,
+            recorded,
+            expected
+        FROM reconciliation_mismatches
+        WHERE run_id = $1
+        ORDER BY kind, wallet_address, project_id, currency
+        "#,
+        run.id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(Some(ReconciliationReport {
+        id: run.id,
+        started_at: run.started_at,
+        finished_at: run.finished_at,
+        wallets_checked: run.wallets_checked,
+        projects_checked: run.projects_checked,
+        mismatches,
+    }))
+}
+
+/// Runs [`reconcile`] every [`RECONCILIATION_INTERVAL`].
+pub fn spawn_reconciliation_job(pool: PgPool) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(RECONCILIATION_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = reconcile(&pool).await {
+                tracing::error!("Failed to reconcile escrow balances: {e}");
+            }
+        }
+    })
+}
@@ -0,0 +1,74 @@
+use crate::Result;
+use bigdecimal::BigDecimal;
+use sqlx::PgConnection;
+
+/// Opens an escrow account for the wallet unless it already has one.
+pub async fn open_escrow_account(conn: &mut PgConnection, wallet_address: &str) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO escrow_users (wallet_address)
+        VALUES ($1)
+        ON CONFLICT (wallet_address) DO NOTHING
+        "#,
+        wallet_address
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Adds `amount` to the wallet's balance in `currency`, opening the escrow account and the balance
+/// as needed.
+pub async fn credit_balance(
+    conn: &mut PgConnection,
+    wallet_address: &str,
+    currency: &str,
+    amount: &BigDecimal,
+) -> Result<()> {
+    open_escrow_account(&mut *conn, wallet_address).await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO escrow_balances (wallet_address, currency, balance)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (wallet_address, currency) DO UPDATE
+        SET balance = escrow_balances.balance + EXCLUDED.balance
+        "#,
+        wallet_address,
+        currency,
+        amount
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Takes `amount` from the wallet's balance in `currency`.
+///
+/// Returns `false`, leaving every balance untouched, when the wallet holds less than `amount` of
+/// that currency.
+pub async fn debit_balance(
+    conn: &mut PgConnection,
+    wallet_address: &str,
+    currency: &str,
+    amount: &BigDecimal,
+) -> Result<bool> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE escrow_balances
+        SET balance = balance - $3
+        WHERE wallet_address = $1
+          AND currency = $2
+          AND balance >= $3
+        "#,
+        wallet_address,
+        currency,
+        amount
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
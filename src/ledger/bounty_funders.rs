@@ -0,0 +1,111 @@
+use crate::Result;
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+/// Adds `amount` to the wallet's share of the project's bounty.
+pub async fn add_bounty_funds(
+    conn: &mut PgConnection,
+    project_id: Uuid,
+    wallet_address: &str,
+    amount: &BigDecimal,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO project_bounty_funders (project_id, wallet_address, amount)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (project_id, wallet_address) DO UPDATE
+        SET amount = project_bounty_funders.amount + EXCLUDED.amount
+        "#,
+        project_id,
+        wallet_address,
+        amount
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Takes `amount` out of the project's bounty, drawing on every funder's share in proportion.
+///
+/// Shares are rounded down to the cent; the largest funder keeps the rounding remainder, so the
+/// shares still add up to what is left of the bounty.
+pub async fn draw_bounty_funds(
+    conn: &mut PgConnection,
+    project_id: Uuid,
+    amount: &BigDecimal,
+) -> Result<()> {
+    let shares = sqlx::query!(
+        r#"
+        SELECT wallet_address, amount
+        FROM project_bounty_funders
+        WHERE project_id = $1 AND amount > 0
+        ORDER BY amount DESC, wallet_address
+        FOR UPDATE
+        "#,
+        project_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let total: BigDecimal = shares.iter().map(|share| &share.amount).sum();
+    if total.is_zero() {
+        return Ok(());
+    }
+    let left = (&total - amount).max(BigDecimal::zero());
+
+    let mut amounts: Vec<BigDecimal> = shares
+        .iter()
+        .skip(1)
+        .map(|share| (&share.amount * &left / &total).with_scale_round(2, RoundingMode::Down))
+        .collect();
+    let rest = &left - amounts.iter().sum::<BigDecimal>();
+    amounts.insert(0, rest);
+    let wallets: Vec<String> = shares
+        .into_iter()
+        .map(|share| share.wallet_address)
+        .collect();
+
+    sqlx::query!(
+        r#"
+        UPDATE project_bounty_funders f
+        SET amount = s.amount
+        FROM unnest($2::text[], $3::numeric[]) AS s(wallet_address, amount)
+        WHERE f.project_id = $1 AND f.wallet_address = s.wallet_address
+        "#,
+        project_id,
+        &wallets,
+        &amounts
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Clears the project's funder shares, returning each funder's part of the bounty.
+pub async fn take_bounty_funds(
+    conn: &mut PgConnection,
+    project_id: Uuid,
+) -> Result<Vec<(String, BigDecimal)>> {
+    let shares = sqlx::query!(
+        r#"
+        DELETE FROM project_bounty_funders
+        WHERE project_id = $1
+        RETURNING wallet_address, amount
+        "#,
+        project_id
+    )
+    .fetch_all(conn)
+    .await?;
+
+    let mut shares: Vec<(String, BigDecimal)> = shares
+        .into_iter()
+        .filter(|share| share.amount > BigDecimal::zero())
+        .map(|share| (share.wallet_address, share.amount))
+        .collect();
+    shares.sort();
+
+    Ok(shares)
+}
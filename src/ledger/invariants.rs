@@ -0,0 +1,162 @@
+use crate::Result;
+use bigdecimal::BigDecimal;
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Outcome of [`check_ledger`].
+#[derive(Debug, Serialize)]
+pub struct LedgerCheck {
+    pub balanced: bool,
+    pub violations: Vec<LedgerViolation>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LedgerViolation {
+    /// The postings of a journal entry do not sum to zero.
+    UnbalancedEntry {
+        journal_entry_id: Uuid,
+        currency: String,
+        total: BigDecimal,
+    },
+    /// All postings in a currency do not sum to zero.
+    UnbalancedCurrency { currency: String, total: BigDecimal },
+    /// An escrow balance differs from its account in the ledger.
+    BalanceMismatch {
+        wallet_address: String,
+        currency: String,
+        balance: BigDecimal,
+        ledger: BigDecimal,
+    },
+    /// A project's bounty differs from its pool in the ledger.
+    BountyMismatch {
+        project_id: Uuid,
+        currency: Option<String>,
+        bounty_amount: BigDecimal,
+        ledger: BigDecimal,
+    },
+}
+
+/// Proves the ledger sums to zero and that the balances it mirrors agree with it.
+pub async fn check_ledger(pool: &PgPool) -> Result<LedgerCheck> {
+    let mut violations = Vec::new();
+
+    let entries = sqlx::query!(
+        r#"
+        SELECT p.journal_entry_id, a.currency, SUM(p.amount) AS "total!"
+        FROM ledger_postings p
+        JOIN ledger_accounts a ON a.id = p.account_id
+        GROUP BY p.journal_entry_id, a.currency
+        HAVING SUM(p.amount) <> 0
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+    violations.extend(
+        entries
+            .into_iter()
+            .map(|entry| LedgerViolation::UnbalancedEntry {
+                journal_entry_id: entry.journal_entry_id,
+                currency: entry.currency,
+                total: entry.total,
+            }),
+    );
+
+    let currencies = sqlx::query!(
+        r#"
+        SELECT a.currency, SUM(p.amount) AS "total!"
+        FROM ledger_postings p
+        JOIN ledger_accounts a ON a.id = p.account_id
+        GROUP BY a.currency
+        HAVING SUM(p.amount) <> 0
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+    violations.extend(
+        currencies
+            .into_iter()
+            .map(|currency| LedgerViolation::UnbalancedCurrency {
+                currency: currency.currency,
+                total: currency.total,
+            }),
+    );
+
+    let balances = sqlx::query!(
+        r#"
+        WITH ledger AS (
+            SELECT a.wallet_address, a.currency, SUM(p.amount) AS total
+            FROM ledger_accounts a
+            JOIN ledger_postings p ON p.account_id = a.id
+            WHERE a.kind = 'user'
+            GROUP BY a.wallet_address, a.currency
+        )
+        SELECT
+            COALESCE(b.wallet_address, l.wallet_address) AS "wallet_address!",
+            COALESCE(b.currency, l.currency) AS "currency!",
+            COALESCE(b.balance, 0) AS "balance!",
+            COALESCE(l.total, 0) AS "ledger!"
+        FROM escrow_balances b
+        FULL OUTER JOIN ledger l
+            ON l.wallet_address = b.wallet_address AND l.currency = b.currency
+        WHERE COALESCE(b.balance, 0) <> COALESCE(l.total, 0)
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+    violations.extend(
+        balances
+            .into_iter()
+            .map(|balance| LedgerViolation::BalanceMismatch {
+                wallet_address: balance.wallet_address,
+                currency: balance.currency,
+                balance: balance.balance,
+                ledger: balance.ledger,
+            }),
+    );
+
+    let bounties = sqlx::query!(
+        r#"
+        WITH ledger AS (
+            SELECT a.project_id, a.currency, SUM(p.amount) AS total
+            FROM ledger_accounts a
+            JOIN ledger_postings p ON p.account_id = a.id
+            WHERE a.kind = 'project_pool'
+            GROUP BY a.project_id, a.currency
+        )
+        SELECT
+            p.id,
+            p.bounty_currency,
+            COALESCE(p.bounty_amount, 0) AS "bounty_amount!",
+            COALESCE(SUM(l.total) FILTER (WHERE l.currency = p.bounty_currency), 0) AS "ledger!"
+        FROM projects p
+        LEFT JOIN ledger l ON l.project_id = p.id
+        GROUP BY p.id
+        HAVING COALESCE(p.bounty_amount, 0)
+                <> COALESCE(SUM(l.total) FILTER (WHERE l.currency = p.bounty_currency), 0)
+            OR COALESCE(SUM(l.total) FILTER (WHERE l.currency IS DISTINCT FROM p.bounty_currency), 0) <> 0
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+    violations.extend(
+        bounties
+            .into_iter()
+            .map(|bounty| LedgerViolation::BountyMismatch {
+                project_id: bounty.id,
+                currency: bounty.bounty_currency,
+                bounty_amount: bounty.bounty_amount,
+                ledger: bounty.ledger,
+            }),
+    );
+
+    if !violations.is_empty() {
+        tracing::error!(violations = ?violations, "Ledger invariants violated");
+    }
+
+    Ok(LedgerCheck {
+        balanced: violations.is_empty(),
+        violations,
+    })
+}
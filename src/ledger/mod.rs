@@ -0,0 +1,125 @@
+//! Double-entry ledger behind the escrow.
+//!
+//! Every movement of funds is booked as a journal entry whose postings sum to zero, so balances can
+//! always be explained from the postings. See `migrations/20250715100000_ledger.sql` for the accounts.
+
+mod balances;
+mod bounty_funders;
+mod invariants;
+
+pub use balances::*;
+pub use bounty_funders::*;
+pub use invariants::*;
+
+use crate::Result;
+use bigdecimal::BigDecimal;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+/// Mirrors the `ledger_account_kind` Postgres enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "ledger_account_kind", rename_all = "snake_case")]
+pub enum LedgerAccountKind {
+    User,
+    ProjectPool,
+    Platform,
+}
+
+/// An account funds can be booked to. Each account holds a single currency.
+#[derive(Debug, Clone, Copy)]
+pub enum LedgerAccount<'a> {
+    /// A wallet's escrow balance.
+    User(&'a str),
+    /// The bounty held for a project.
+    ProjectPool(Uuid),
+    /// The platform's custody of on-chain funds.
+    Platform,
+}
+
+impl LedgerAccount<'_> {
+    fn kind(&self) -> LedgerAccountKind {
+        match self {
+            LedgerAccount::User(_) => LedgerAccountKind::User,
+            LedgerAccount::ProjectPool(_) => LedgerAccountKind::ProjectPool,
+            LedgerAccount::Platform => LedgerAccountKind::Platform,
+        }
+    }
+
+    async fn id(&self, conn: &mut PgConnection, currency: &str) -> Result<Uuid> {
+        let (wallet_address, project_id) = match self {
+            LedgerAccount::User(wallet_address) => (Some(*wallet_address), None),
+            LedgerAccount::ProjectPool(project_id) => (None, Some(*project_id)),
+            LedgerAccount::Platform => (None, None),
+        };
+
+        // The no-op update makes `RETURNING` yield the existing account as well.
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO ledger_accounts (kind, wallet_address, project_id, currency)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT ON CONSTRAINT uq_ledger_accounts DO UPDATE SET kind = EXCLUDED.kind
+            RETURNING id
+            "#,
+            self.kind() as LedgerAccountKind,
+            wallet_address,
+            project_id,
+            currency
+        )
+        .fetch_one(conn)
+        .await?;
+
+        Ok(id)
+    }
+}
+
+/// Books `amount` of `currency` moving from one account to another as a single journal entry.
+///
+/// Only records the movement; the caller updates the balances it mirrors within the same transaction.
+pub async fn record_transfer(
+    conn: &mut PgConnection,
+    from: LedgerAccount<'_>,
+    to: LedgerAccount<'_>,
+    currency: &str,
+    amount: &BigDecimal,
+    transaction_id: Option<Uuid>,
+    description: &str,
+) -> Result<Uuid> {
+    let from_id = from.id(&mut *conn, currency).await?;
+    let to_id = to.id(&mut *conn, currency).await?;
+
+    let journal_entry_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO journal_entries (transaction_id, description)
+        VALUES ($1, $2)
+        RETURNING id
+        "#,
+        transaction_id,
+        description
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO ledger_postings (journal_entry_id, account_id, amount)
+        VALUES ($1, $2, -$4::numeric), ($1, $3, $4)
+        "#,
+        journal_entry_id,
+        from_id,
+        to_id,
+        amount
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    tracing::debug!(
+        journal_entry_id = %journal_entry_id,
+        from = ?from,
+        to = ?to,
+        currency = %currency,
+        amount = %amount,
+        "Ledger transfer recorded"
+    );
+
+    Ok(journal_entry_id)
+}
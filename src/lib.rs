@@ -1,13 +1,20 @@
+pub mod chain;
 pub mod config;
 pub mod db;
 pub mod error;
 pub mod http;
+pub mod jobs;
+pub mod ledger;
 pub mod middleware;
 pub mod telemetry;
+pub mod verification;
 
+pub use chain::*;
 pub use config::*;
 pub use db::*;
 pub use error::*;
 pub use http::*;
+pub use ledger::*;
 pub use middleware::*;
 pub use telemetry::*;
+pub use verification::*;
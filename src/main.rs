@@ -1,4 +1,4 @@
-use fortichain_server::{Configuration, db::Db, http, telemetry::setup_tracing};
+use fortichain_server::{Configuration, db::Db, http, jobs::reconcile, telemetry::setup_tracing};
 
 #[tokio::main]
 async fn main() {
@@ -17,6 +17,22 @@ async fn main() {
     tracing::info!("Running Migrations");
     db.migrate().await.expect("Failed to run migrations");
 
+    // `fortichain_server reconcile` runs a single reconciliation instead of serving, and exits
+    // non-zero when it finds a mismatch.
+    if std::env::args().nth(1).as_deref() == Some("reconcile") {
+        let report = reconcile(&db.pool)
+            .await
+            .expect("Failed to reconcile escrow balances");
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("Failed to serialize report")
+        );
+        if !report.mismatches.is_empty() {
+            std::process::exit(1);
+        }
+        return;
+    }
+
     tracing::info!("Starting server");
     http::serve(config, db)
         .await
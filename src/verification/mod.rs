@@ -0,0 +1,134 @@
+mod repository;
+
+pub use repository::*;
+
+use crate::{
+    Configuration,
+    chain::{
+        ContractDeployerSource, RpcContractDeployerSource, SignatureVerifier,
+        project_verification_typed_data, typed_data_hash,
+    },
+};
+use starknet::core::types::Felt;
+use std::sync::Arc;
+
+/// How an owner proves control of a project.
+#[derive(Debug, Clone)]
+pub enum OwnershipProof {
+    /// The token is committed to [`VERIFICATION_FILE`] in the project's repository.
+    RepositoryFile,
+    /// The account that deployed the project's contract signed the token.
+    DeployerSignature {
+        deployment_transaction_hash: Felt,
+        signature: Vec<Felt>,
+    },
+}
+
+/// Outcome of checking an [`OwnershipProof`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Verified,
+    /// The proof does not hold, and why.
+    Failed(&'static str),
+}
+
+/// Checks ownership proofs against the project's repository and contract.
+pub struct OwnershipVerifier {
+    repositories: Arc<dyn RepositoryFetcher>,
+    deployers: Arc<dyn ContractDeployerSource>,
+    signatures: Arc<dyn SignatureVerifier>,
+    chain_id: String,
+}
+
+impl OwnershipVerifier {
+    pub fn new(
+        repositories: Arc<dyn RepositoryFetcher>,
+        deployers: Arc<dyn ContractDeployerSource>,
+        signatures: Arc<dyn SignatureVerifier>,
+        chain_id: &str,
+    ) -> Self {
+        Self {
+            repositories,
+            deployers,
+            signatures,
+            chain_id: chain_id.to_string(),
+        }
+    }
+
+    /// Fetches repositories over HTTP and deployments from the configured node.
+    pub fn from_configuration(
+        configuration: &Configuration,
+        signatures: Arc<dyn SignatureVerifier>,
+    ) -> anyhow::Result<Self> {
+        Ok(Self::new(
+            Arc::new(HttpRepositoryFetcher::new(
+                &configuration.verification_repository_hosts,
+            )?),
+            Arc::new(RpcContractDeployerSource::new(
+                &configuration.starknet_rpc_url,
+            )?),
+            signatures,
+            &configuration.starknet_chain_id,
+        ))
+    }
+
+    pub async fn verify(
+        &self,
+        contract_address: &str,
+        repository_url: &str,
+        token: &str,
+        proof: &OwnershipProof,
+    ) -> anyhow::Result<Verdict> {
+        match proof {
+            OwnershipProof::RepositoryFile => {
+                let contents = match self
+                    .repositories
+                    .fetch_file(repository_url, VERIFICATION_FILE)
+                    .await
+                {
+                    Ok(contents) => contents,
+                    Err(e) => {
+                        tracing::warn!(repository_url, "Failed to fetch verification file: {e}");
+                        return Ok(Verdict::Failed("the repository could not be fetched"));
+                    }
+                };
+                Ok(match contents {
+                    None => Verdict::Failed("the repository has no .fortichain file"),
+                    Some(contents) if contents.split_whitespace().any(|word| word == token) => {
+                        Verdict::Verified
+                    }
+                    Some(_) => Verdict::Failed("the .fortichain file does not hold the token"),
+                })
+            }
+            OwnershipProof::DeployerSignature {
+                deployment_transaction_hash,
+                signature,
+            } => {
+                let Ok(contract) = Felt::from_hex(contract_address) else {
+                    return Ok(Verdict::Failed(
+                        "the project's contract address is not a Starknet address",
+                    ));
+                };
+                let Some(deployer) = self
+                    .deployers
+                    .deployer(contract, *deployment_transaction_hash)
+                    .await?
+                else {
+                    return Ok(Verdict::Failed(
+                        "the transaction did not deploy the project's contract",
+                    ));
+                };
+
+                let typed_data = project_verification_typed_data(&self.chain_id, contract, token);
+                let hash = typed_data_hash(typed_data, deployer)?;
+                Ok(
+                    if self.signatures.verify(deployer, hash, signature).await? {
+                        Verdict::Verified
+                    } else {
+                        Verdict::Failed("the signature is not the deployer's")
+                    },
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+use reqwest::{StatusCode, Url, redirect};
+use std::time::Duration;
+
+/// File an owner commits to the root of the project's repository, holding its verification token.
+pub const VERIFICATION_FILE: &str = ".fortichain";
+
+/// Larger files are not read to the end; a token fits many times over.
+const MAX_FILE_BYTES: usize = 4096;
+
+/// Reads a file from the default branch of a source repository.
+///
+/// Keeping this behind a trait lets tests serve repositories from a local stand-in.
+#[async_trait]
+pub trait RepositoryFetcher: Send + Sync {
+    /// The start of the file at `path` in the repository at `repository_url`, or `None` if it does
+    /// not exist.
+    async fn fetch_file(&self, repository_url: &str, path: &str) -> anyhow::Result<Option<String>>;
+}
+
+/// Fetches raw files over HTTP from an allow-list of hosts.
+///
+/// GitHub and GitLab repositories are read through their raw file URLs; any other allowed host is
+/// expected to serve them Gitea-style, under `<repository>/raw/HEAD/<path>`.
+pub struct HttpRepositoryFetcher {
+    client: reqwest::Client,
+    allowed_hosts: Vec<String>,
+}
+
+impl HttpRepositoryFetcher {
+    pub fn new(allowed_hosts: &[String]) -> anyhow::Result<Self> {
+        // Redirects could lead to hosts outside the allow-list.
+        let client = reqwest::Client::builder()
+            .redirect(redirect::Policy::none())
+            .timeout(Duration::from_secs(10))
+            .build()?;
+        Ok(Self {
+            client,
+            allowed_hosts: allowed_hosts.to_vec(),
+        })
+    }
+
+    fn raw_file_url(&self, repository_url: &str, path: &str) -> anyhow::Result<Url> {
+        let repository = Url::parse(repository_url.trim_end_matches('/'))?;
+        let host = repository.host_str().unwrap_or_default().to_lowercase();
+        if !matches!(repository.scheme(), "http" | "https") || !self.allowed_hosts.contains(&host) {
+            anyhow::bail!("repositories on {host} cannot be fetched");
+        }
+
+        let repository_path = repository.path().trim_end_matches(".git");
+        let raw = match host.as_str() {
+            "github.com" => {
+                format!("https://raw.githubusercontent.com{repository_path}/HEAD/{path}")
+            }
+            "gitlab.com" => format!("https://gitlab.com{repository_path}/-/raw/HEAD/{path}"),
+            _ => {
+                let mut raw = repository.clone();
+                raw.set_path(&format!("{repository_path}/raw/HEAD/{path}"));
+                raw.to_string()
+            }
+        };
+        Ok(Url::parse(&raw)?)
+    }
+}
+
+#[async_trait]
+impl RepositoryFetcher for HttpRepositoryFetcher {
+    async fn fetch_file(&self, repository_url: &str, path: &str) -> anyhow::Result<Option<String>> {
+        let url = self.raw_file_url(repository_url, path)?;
+        let response = self.client.get(url).send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let mut response = response.error_for_status()?;
+
+        let mut contents = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            contents.extend_from_slice(&chunk);
+            if contents.len() >= MAX_FILE_BYTES {
+                contents.truncate(MAX_FILE_BYTES);
+                break;
+            }
+        }
+        Ok(Some(String::from_utf8_lossy(&contents).into_owned()))
+    }
+}
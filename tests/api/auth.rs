@@ -0,0 +1,173 @@
+use crate::helpers::TestApp;
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use serde_json::json;
+
+fn ticket_payload() -> String {
+    json!({
+        "subject": "Valid Subject",
+        "message": "This is a valid message with enough length."
+    })
+    .to_string()
+}
+
+async fn insert_user(app: &TestApp, wallet: &str) {
+    sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
+        .bind(wallet)
+        .execute(&app.db.pool)
+        .await
+        .expect("Failed to insert user");
+}
+
+#[tokio::test]
+async fn challenge_returns_typed_data() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+
+    let req = Request::post("/auth/challenge")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({ "wallet_address": wallet.address }).to_string(),
+        ))
+        .unwrap();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let challenge: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(challenge["typed_data"]["primaryType"], "Authentication");
+    assert_eq!(challenge["typed_data"]["message"]["wallet"], wallet.address);
+    assert_eq!(
+        challenge["typed_data"]["message"]["nonce"],
+        challenge["nonce"]
+    );
+}
+
+#[tokio::test]
+async fn challenge_invalid_address() {
+    let app = TestApp::new().await;
+
+    let req = Request::post("/auth/challenge")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({ "wallet_address": "not_a_wallet" }).to_string(),
+        ))
+        .unwrap();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn signed_request_succeeds() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    insert_user(&app, &wallet.address).await;
+
+    let req = app
+        .signed(&wallet, Request::post("/open_ticket"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(ticket_payload()))
+        .unwrap();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn missing_signature_is_unauthorized() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    insert_user(&app, &wallet.address).await;
+
+    let req = Request::post("/open_ticket")
+        .header("content-type", "application/json")
+        .header("x-wallet-address", &wallet.address)
+        .body(Body::from(ticket_payload()))
+        .unwrap();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn invalid_signature_is_unauthorized() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    insert_user(&app, &wallet.address).await;
+
+    let mut req = app
+        .signed(&wallet, Request::post("/open_ticket"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(ticket_payload()))
+        .unwrap();
+    req.headers_mut()
+        .insert("x-wallet-signature", "0x1,0x2".parse().unwrap());
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn signature_for_another_wallet_is_unauthorized() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    let victim = app.wallet();
+    insert_user(&app, &victim.address).await;
+
+    let mut req = app
+        .signed(&wallet, Request::post("/open_ticket"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(ticket_payload()))
+        .unwrap();
+    req.headers_mut()
+        .insert("x-wallet-address", victim.address.parse().unwrap());
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn replayed_nonce_is_unauthorized() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    insert_user(&app, &wallet.address).await;
+
+    let req = app
+        .signed(&wallet, Request::post("/open_ticket"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(ticket_payload()))
+        .unwrap();
+    let headers = req.headers().clone();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+
+    let mut replay = Request::post("/open_ticket")
+        .body(Body::from(ticket_payload()))
+        .unwrap();
+    *replay.headers_mut() = headers;
+    let res = app.request(replay).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn expired_nonce_is_unauthorized() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    insert_user(&app, &wallet.address).await;
+
+    let req = app
+        .signed(&wallet, Request::post("/open_ticket"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(ticket_payload()))
+        .unwrap();
+    sqlx::query("UPDATE auth_challenges SET expires_at = NOW() - INTERVAL '1 minute'")
+        .execute(&app.db.pool)
+        .await
+        .expect("Failed to expire challenge");
+
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
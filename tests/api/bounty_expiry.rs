@@ -0,0 +1,257 @@
+use crate::helpers::{TestApp, TestWallet, generate_address};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Duration, Utc};
+use fortichain_server::jobs::refund_expired_bounties;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+async fn post(
+    app: &TestApp,
+    wallet: &TestWallet,
+    uri: &str,
+    payload: Value,
+) -> (StatusCode, Value) {
+    let req = app
+        .signed(wallet, Request::post(uri))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let res = app.request(req).await;
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+/// A project whose owner allocated a 300 STRK bounty that expired yesterday.
+async fn expired_bounty(app: &TestApp) -> (TestWallet, Uuid) {
+    let owner = app.wallet();
+    app.fund(&owner.address, "STRK", BigDecimal::from(300))
+        .await;
+    let contract_address = generate_address();
+    let project_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO projects (owner_address, contract_address, name, description, contact_info) \
+         VALUES ($1, $2, 'Test Project', 'A test project.', 'test@example.com') RETURNING id",
+    )
+    .bind(&owner.address)
+    .bind(&contract_address)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap();
+    let (status, _) = post(
+        app,
+        &owner,
+        "/allocate_bounty",
+        json!({
+            "project_contract_address": contract_address,
+            "amount": "300",
+            "currency": "STRK",
+            "bounty_expiry_date": Utc::now() + Duration::days(30),
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    set_expiry(app, project_id, Utc::now() - Duration::days(1)).await;
+
+    (owner, project_id)
+}
+
+async fn set_expiry(app: &TestApp, project_id: Uuid, expiry: DateTime<Utc>) {
+    sqlx::query("UPDATE projects SET bounty_expiry_date = $2 WHERE id = $1")
+        .bind(project_id)
+        .bind(expiry)
+        .execute(&app.db.pool)
+        .await
+        .unwrap();
+}
+
+async fn bounty(app: &TestApp, project_id: Uuid) -> BigDecimal {
+    sqlx::query_scalar("SELECT bounty_amount FROM projects WHERE id = $1")
+        .bind(project_id)
+        .fetch_one(&app.db.pool)
+        .await
+        .unwrap()
+}
+
+async fn open_report(app: &TestApp, project_id: Uuid) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO research_report (title, project_id, body, reported_by, status)
+        VALUES ('Re-entrancy in withdraw', $1, $2, $3, 'in_review')
+        RETURNING id
+        "#,
+    )
+    .bind(project_id)
+    .bind("The withdraw function updates balances after the external call, allowing re-entrancy.")
+    .bind(generate_address())
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn expired_bounty_is_refunded_to_owner() {
+    let app = TestApp::new().await;
+    let (owner, project_id) = expired_bounty(&app).await;
+    assert_eq!(
+        app.balance(&owner.address, "STRK").await,
+        BigDecimal::from(0)
+    );
+
+    let refunded = refund_expired_bounties(&app.db.pool).await.unwrap();
+    assert_eq!(refunded, vec![project_id]);
+    assert_eq!(
+        app.balance(&owner.address, "STRK").await,
+        BigDecimal::from(300)
+    );
+    assert_eq!(bounty(&app, project_id).await, BigDecimal::from(0));
+
+    let (wallet, amount): (String, BigDecimal) = sqlx::query_as(
+        "SELECT wallet_address, amount FROM escrow_transactions \
+         WHERE project_id = $1 AND type = 'bounty_refund' AND status = 'completed'",
+    )
+    .bind(project_id)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap();
+    assert_eq!(wallet, owner.address);
+    assert_eq!(amount, BigDecimal::from(300));
+
+    // The project stays open, and nothing is refunded twice.
+    let closed: Option<DateTime<Utc>> =
+        sqlx::query_scalar("SELECT closed_at FROM projects WHERE id = $1")
+            .bind(project_id)
+            .fetch_one(&app.db.pool)
+            .await
+            .unwrap();
+    assert_eq!(closed, None);
+    assert!(
+        refund_expired_bounties(&app.db.pool)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        app.balance(&owner.address, "STRK").await,
+        BigDecimal::from(300)
+    );
+}
+
+#[tokio::test]
+async fn refund_waits_for_open_reports() {
+    let app = TestApp::new().await;
+    let (owner, project_id) = expired_bounty(&app).await;
+    let report_id = open_report(&app, project_id).await;
+
+    assert!(
+        refund_expired_bounties(&app.db.pool)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(bounty(&app, project_id).await, BigDecimal::from(300));
+
+    sqlx::query("UPDATE research_report SET status = 'closed' WHERE id = $1")
+        .bind(report_id)
+        .execute(&app.db.pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        refund_expired_bounties(&app.db.pool).await.unwrap(),
+        vec![project_id]
+    );
+    assert_eq!(
+        app.balance(&owner.address, "STRK").await,
+        BigDecimal::from(300)
+    );
+}
+
+#[tokio::test]
+async fn owner_extends_bounty_expiry() {
+    let app = TestApp::new().await;
+    let (owner, project_id) = expired_bounty(&app).await;
+    let stranger = app.wallet();
+    let uri = format!("/projects/{project_id}/bounty/extend");
+    let extended = (Utc::now() + Duration::days(60)).to_rfc3339();
+
+    let (status, _) = post(
+        &app,
+        &stranger,
+        &uri,
+        json!({ "bounty_expiry_date": extended }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = post(
+        &app,
+        &owner,
+        &uri,
+        json!({ "bounty_expiry_date": Utc::now() - Duration::hours(1) }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body["errors"]["bounty_expiry_date"].is_array());
+
+    let (status, project) = post(
+        &app,
+        &owner,
+        &uri,
+        json!({ "bounty_expiry_date": extended }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let expiry: DateTime<Utc> = project["bounty_expiry_date"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(
+        expiry.timestamp(),
+        extended.parse::<DateTime<Utc>>().unwrap().timestamp()
+    );
+
+    // An extension cannot bring the expiry forward.
+    let (status, _) = post(
+        &app,
+        &owner,
+        &uri,
+        json!({ "bounty_expiry_date": Utc::now() + Duration::days(10) }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let changes: Value = sqlx::query_scalar(
+        "SELECT changes FROM project_revisions WHERE project_id = $1 ORDER BY revision DESC LIMIT 1",
+    )
+    .bind(project_id)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap();
+    assert!(changes["bounty_expiry_date"]["from"].is_string());
+    assert!(changes["bounty_expiry_date"]["to"].is_string());
+
+    assert!(
+        refund_expired_bounties(&app.db.pool)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(bounty(&app, project_id).await, BigDecimal::from(300));
+
+    set_expiry(&app, project_id, Utc::now() - Duration::days(1)).await;
+    refund_expired_bounties(&app.db.pool).await.unwrap();
+    let (status, body) = post(
+        &app,
+        &owner,
+        &uri,
+        json!({ "bounty_expiry_date": extended }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "no_bounty");
+}
@@ -0,0 +1,202 @@
+use crate::helpers::{MockChain, STRK, TestApp, TestWallet, watcher};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use bigdecimal::BigDecimal;
+use fortichain_server::{
+    Configuration, EscrowToken,
+    jobs::{SettlementSummary, WatchSummary},
+};
+use serde_json::{Value, json};
+use starknet::core::types::Felt;
+
+fn tx_hash(n: u8) -> String {
+    format!("0x0{}{n:02x}", "7".repeat(61))
+}
+
+async fn status_of(app: &TestApp, transaction_hash: &str) -> String {
+    sqlx::query_scalar("SELECT status::text FROM escrow_transactions WHERE transaction_hash = $1")
+        .bind(transaction_hash)
+        .fetch_one(&app.db.pool)
+        .await
+        .unwrap()
+}
+
+async fn ledger_is_balanced(app: &TestApp) -> bool {
+    let admin = app.wallet();
+    app.grant_role(&admin.address, "admin").await;
+    let req = app
+        .signed(&admin, Request::get("/admin/ledger/check"))
+        .await
+        .body(Body::empty())
+        .unwrap();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let check: Value = serde_json::from_slice(&body).unwrap();
+    check["balanced"] == true
+}
+
+async fn report_deposit(app: &TestApp, wallet: &TestWallet, transaction_hash: &str, amount: &str) {
+    let req = app
+        .signed(wallet, Request::post("/deposit"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({ "amount": amount, "currency": "STRK", "transaction_hash": transaction_hash })
+                .to_string(),
+        ))
+        .unwrap();
+    assert_eq!(app.request(req).await.status(), StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn watcher_credits_each_deposit_event_once() {
+    let app = TestApp::new().await;
+    let (server, chain) = MockChain::start(6).await;
+    let watcher = watcher(&app, &server);
+    let alice = app.wallet();
+    let bob = app.wallet();
+
+    chain.deposit(2, &tx_hash(1), &alice.address, STRK, 150);
+    chain.deposit(4, &tx_hash(2), &bob.address, STRK, 20);
+    // Tokens the escrow does not accept are ignored.
+    chain.deposit(4, &tx_hash(3), &bob.address, "0x0999", 1000);
+
+    let summary = watcher.poll().await.unwrap();
+    assert_eq!(
+        summary,
+        WatchSummary {
+            deposits: 2,
+            reverted: 0,
+            checkpoint: Some(5),
+        }
+    );
+    assert_eq!(
+        app.balance(&alice.address, "STRK").await,
+        BigDecimal::from(150)
+    );
+    assert_eq!(
+        app.balance(&bob.address, "STRK").await,
+        BigDecimal::from(20)
+    );
+    assert_eq!(status_of(&app, &tx_hash(1)).await, "completed");
+
+    // The next poll resumes after the checkpoint and books nothing twice.
+    chain.mine(2);
+    chain.deposit(7, &tx_hash(4), &alice.address, STRK, 5);
+    let summary = watcher.poll().await.unwrap();
+    assert_eq!(summary.deposits, 1);
+    assert_eq!(summary.checkpoint, Some(7));
+    assert_eq!(
+        app.balance(&alice.address, "STRK").await,
+        BigDecimal::from(155)
+    );
+
+    assert_eq!(watcher.poll().await.unwrap().deposits, 0);
+    assert_eq!(
+        app.balance(&alice.address, "STRK").await,
+        BigDecimal::from(155)
+    );
+    assert!(ledger_is_balanced(&app).await);
+}
+
+#[tokio::test]
+async fn watcher_books_the_event_amount_for_client_reported_deposits() {
+    let app = TestApp::new().await;
+    let (server, chain) = MockChain::start(3).await;
+    let watcher = watcher(&app, &server);
+    let wallet = app.wallet();
+
+    // Clients report the hash without its leading zeros.
+    let reported_hash = Felt::from_hex(&tx_hash(1)).unwrap().to_hex_string();
+    report_deposit(&app, &wallet, &reported_hash, "999").await;
+
+    // A confirmed hash alone does not credit the claimed amount.
+    assert_eq!(
+        app.confirm(&reported_hash).await,
+        SettlementSummary::default()
+    );
+    assert_eq!(status_of(&app, &reported_hash).await, "pending");
+
+    chain.deposit(1, &tx_hash(1), &wallet.address, STRK, 100);
+    assert_eq!(watcher.poll().await.unwrap().deposits, 1);
+
+    let (count, amount): (i64, BigDecimal) = sqlx::query_as(
+        "SELECT COUNT(*), MAX(amount) FROM escrow_transactions WHERE transaction_hash = $1",
+    )
+    .bind(tx_hash(1))
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap();
+    assert_eq!(count, 1);
+    assert_eq!(amount, BigDecimal::from(100));
+    assert_eq!(status_of(&app, &tx_hash(1)).await, "completed");
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(100)
+    );
+    assert!(ledger_is_balanced(&app).await);
+}
+
+#[tokio::test]
+async fn watcher_reverses_deposits_from_reorged_blocks() {
+    let app = TestApp::new().await;
+    let (server, chain) = MockChain::start(3).await;
+    let watcher = watcher(&app, &server);
+    let wallet = app.wallet();
+
+    chain.deposit(1, &tx_hash(1), &wallet.address, STRK, 10);
+    assert_eq!(watcher.poll().await.unwrap().checkpoint, Some(2));
+    chain.mine(3);
+    chain.deposit(4, &tx_hash(2), &wallet.address, STRK, 40);
+    assert_eq!(watcher.poll().await.unwrap().checkpoint, Some(5));
+    chain.mine(2);
+    assert_eq!(watcher.poll().await.unwrap().checkpoint, Some(7));
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(50)
+    );
+
+    // Blocks 3 onwards are replaced; the second deposit lands in a later block with a new hash.
+    chain.reorg(3);
+    chain.deposit(6, &tx_hash(3), &wallet.address, STRK, 30);
+
+    let summary = watcher.poll().await.unwrap();
+    assert_eq!(
+        summary,
+        WatchSummary {
+            deposits: 1,
+            reverted: 1,
+            checkpoint: Some(7),
+        }
+    );
+    assert_eq!(status_of(&app, &tx_hash(1)).await, "completed");
+    assert_eq!(status_of(&app, &tx_hash(2)).await, "failed");
+    assert_eq!(status_of(&app, &tx_hash(3)).await, "completed");
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(40)
+    );
+    assert!(ledger_is_balanced(&app).await);
+}
+
+#[test]
+fn escrow_tokens_are_limited_to_supported_currencies() {
+    let token = EscrowToken::try_from(format!("STRK:{STRK}:18").as_str()).unwrap();
+    assert_eq!(token.decimals, 18);
+    assert!(
+        EscrowToken::try_from(
+            "ETH:0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7:18"
+        )
+        .is_err()
+    );
+
+    let currencies: Vec<String> = Configuration::new()
+        .escrow_tokens
+        .iter()
+        .map(|token| token.currency.clone())
+        .collect();
+    assert_eq!(currencies, ["STRK", "USDC", "USDT"]);
+}
@@ -1,4 +1,4 @@
-use crate::helpers::{TestApp, generate_address};
+use crate::helpers::{TestApp, TestWallet, generate_address};
 use axum::{
     body::Body,
     http::{Request, StatusCode},
@@ -9,59 +9,77 @@ use uuid::Uuid;
 
 pub async fn create_project(
     app: &TestApp,
-    owner_address: &str,
+    owner: &TestWallet,
     closed_at: Option<chrono::DateTime<chrono::Utc>>,
 ) -> Uuid {
-    sqlx::query!(
-        "INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT (wallet_address) DO NOTHING",
-        owner_address
-    )
-    .execute(&app.db.pool)
-    .await
-    .unwrap();
-
+    let contract_address = generate_address();
     let project_id = sqlx::query_scalar!(
         r#"
         INSERT INTO projects (
             owner_address, contract_address, name, description, contact_info,
-            supporting_document_path, project_logo_path, repository_url,
-            bounty_amount, bounty_currency, bounty_expiry_date, closed_at
+            supporting_document_path, project_logo_path, repository_url
         ) VALUES (
-            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12
+            $1, $2, $3, $4, $5, $6, $7, $8
         ) RETURNING id
         "#,
-        owner_address,
-        &generate_address(),
+        owner.address,
+        &contract_address,
         "StarkNet Yield Aggregator",
         "A decentralized protocol for yield farming on the StarkNet ecosystem.",
         "contact@starkyield.com",
         "https://github.com/starkyield/doc.pdf",
         "https://github.com/starkyield/logo.png",
-        "https://github.com/starkyield",
-        BigDecimal::from(1000),
-        "USD",
-        chrono::Utc::now() + chrono::Duration::days(30),
-        closed_at
+        "https://github.com/starkyield"
     )
     .fetch_one(&app.db.pool)
     .await
     .unwrap();
 
+    // The bounty is funded from the owner's balance, which it leaves empty.
+    app.fund(&owner.address, "STRK", BigDecimal::from(1000))
+        .await;
+    let req = app
+        .signed(owner, Request::post("/allocate_bounty"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({
+                "project_contract_address": contract_address,
+                "amount": "1000",
+                "currency": "STRK",
+                "bounty_expiry_date": chrono::Utc::now() + chrono::Duration::days(30),
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    assert_eq!(app.request(req).await.status(), StatusCode::OK);
+
+    sqlx::query!(
+        "UPDATE projects SET closed_at = $2 WHERE id = $1",
+        project_id,
+        closed_at
+    )
+    .execute(&app.db.pool)
+    .await
+    .unwrap();
+
     project_id
 }
 
 #[tokio::test]
 async fn test_close_project_success() {
     let app = TestApp::new().await;
-    let owner_address = generate_address();
-    let project_id = create_project(&app, &owner_address, None).await;
+    let owner = app.wallet();
+    let owner_address = owner.address.clone();
+    let project_id = create_project(&app, &owner, None).await;
 
-    let req = Request::post("/closed_project")
+    let req = app
+        .signed(&owner, Request::post("/closed_project"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(
             json!({
-                "project_id": project_id,
-                "owner_address": owner_address
+                "project_id": project_id
             })
             .to_string(),
         ))
@@ -76,27 +94,24 @@ async fn test_close_project_success() {
         .unwrap();
     assert!(project.closed_at.is_some());
 
-    let user = sqlx::query!(
-        "SELECT balance FROM escrow_users WHERE wallet_address = $1",
-        owner_address
-    )
-    .fetch_one(&app.db.pool)
-    .await
-    .unwrap();
-    assert_eq!(user.balance, BigDecimal::from(1000));
+    assert_eq!(
+        app.balance(&owner_address, "STRK").await,
+        BigDecimal::from(1000)
+    );
 }
 
 #[tokio::test]
 async fn test_close_project_with_partial_refund() {
     let app = TestApp::new().await;
-    let owner_address = generate_address();
-    let project_id = create_project(&app, &owner_address, None).await;
+    let owner = app.wallet();
+    let owner_address = owner.address.clone();
+    let project_id = create_project(&app, &owner, None).await;
 
     let disbursed_amount = BigDecimal::from(100);
     let disbursed_wallet = &generate_address();
 
     sqlx::query!(
-        "INSERT INTO escrow_users (wallet_address, balance) VALUES ($1, 0)",
+        "INSERT INTO escrow_users (wallet_address) VALUES ($1)",
         disbursed_wallet
     )
     .execute(&app.db.pool)
@@ -120,18 +135,8 @@ async fn test_close_project_with_partial_refund() {
     .await
     .unwrap();
 
-    sqlx::query!(
-        r#"
-        UPDATE escrow_users
-        SET balance = balance + $1
-        WHERE wallet_address = $2
-        "#,
-        disbursed_amount,
-        disbursed_wallet
-    )
-    .execute(&app.db.pool)
-    .await
-    .unwrap();
+    app.fund(disbursed_wallet, "STRK", disbursed_amount.clone())
+        .await;
 
     sqlx::query!(
         r#"
@@ -146,12 +151,13 @@ async fn test_close_project_with_partial_refund() {
     .await
     .unwrap();
 
-    let req = Request::post("/closed_project")
+    let req = app
+        .signed(&owner, Request::post("/closed_project"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(
             json!({
-                "project_id": project_id,
-                "owner_address": owner_address
+                "project_id": project_id
             })
             .to_string(),
         ))
@@ -160,28 +166,25 @@ async fn test_close_project_with_partial_refund() {
 
     assert_eq!(res.status(), StatusCode::OK);
 
-    let user = sqlx::query!(
-        "SELECT balance FROM escrow_users WHERE wallet_address = $1",
-        owner_address
-    )
-    .fetch_one(&app.db.pool)
-    .await
-    .unwrap();
-    assert_eq!(user.balance, BigDecimal::from(900));
+    assert_eq!(
+        app.balance(&owner_address, "STRK").await,
+        BigDecimal::from(900)
+    );
 }
 
 #[tokio::test]
 async fn test_close_project_not_found() {
     let app = TestApp::new().await;
-    let owner_address = generate_address();
+    let owner = app.wallet();
     let invalid_project_id = Uuid::now_v7();
 
-    let req = Request::post("/closed_project")
+    let req = app
+        .signed(&owner, Request::post("/closed_project"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(
             json!({
-                "project_id": invalid_project_id,
-                "owner_address": owner_address
+                "project_id": invalid_project_id
             })
             .to_string(),
         ))
@@ -194,39 +197,41 @@ async fn test_close_project_not_found() {
 #[tokio::test]
 async fn test_close_project_unauthorized() {
     let app = TestApp::new().await;
-    let owner_address = generate_address();
-    let attacker_address = generate_address();
+    let owner = app.wallet();
+    let attacker = app.wallet();
 
-    let project_id = create_project(&app, &owner_address, None).await;
+    let project_id = create_project(&app, &owner, None).await;
 
-    let req = Request::post("/closed_project")
+    let req = app
+        .signed(&attacker, Request::post("/closed_project"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(
             json!({
-                "project_id": project_id,
-                "owner_address": attacker_address
+                "project_id": project_id
             })
             .to_string(),
         ))
         .unwrap();
     let res = app.request(req).await;
 
-    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
 }
 
 #[tokio::test]
 async fn test_close_project_already_closed() {
     let app = TestApp::new().await;
-    let owner_address = generate_address();
+    let owner = app.wallet();
 
-    let project_id = create_project(&app, &owner_address, Some(chrono::Utc::now())).await;
+    let project_id = create_project(&app, &owner, Some(chrono::Utc::now())).await;
 
-    let req = Request::post("/closed_project")
+    let req = app
+        .signed(&owner, Request::post("/closed_project"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(
             json!({
-                "project_id": project_id,
-                "owner_address": owner_address
+                "project_id": project_id
             })
             .to_string(),
         ))
@@ -239,37 +244,39 @@ async fn test_close_project_already_closed() {
 #[tokio::test]
 async fn test_close_project_user_not_exists() {
     let app = TestApp::new().await;
-    let owner_address = generate_address();
-    let non_existent_user = generate_address();
+    let owner = app.wallet();
+    let non_existent_user = app.wallet();
 
-    let project_id = create_project(&app, &owner_address, None).await;
+    let project_id = create_project(&app, &owner, None).await;
 
-    let req = Request::post("/closed_project")
+    let req = app
+        .signed(&non_existent_user, Request::post("/closed_project"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(
             json!({
-                "project_id": project_id,
-                "owner_address": non_existent_user
+                "project_id": project_id
             })
             .to_string(),
         ))
         .unwrap();
     let res = app.request(req).await;
 
-    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
 }
 
 #[tokio::test]
 async fn test_close_project_no_refund_needed() {
     let app = TestApp::new().await;
-    let owner_address = generate_address();
-    let project_id = create_project(&app, &owner_address, None).await;
+    let owner = app.wallet();
+    let owner_address = owner.address.clone();
+    let project_id = create_project(&app, &owner, None).await;
 
     let disbursed_amount = BigDecimal::from(1000);
     let disbursed_wallet = &generate_address();
 
     sqlx::query!(
-        "INSERT INTO escrow_users (wallet_address, balance) VALUES ($1, 0)",
+        "INSERT INTO escrow_users (wallet_address) VALUES ($1)",
         disbursed_wallet
     )
     .execute(&app.db.pool)
@@ -293,18 +300,8 @@ async fn test_close_project_no_refund_needed() {
     .await
     .unwrap();
 
-    sqlx::query!(
-        r#"
-        UPDATE escrow_users
-        SET balance = balance + $1
-        WHERE wallet_address = $2
-        "#,
-        disbursed_amount,
-        disbursed_wallet
-    )
-    .execute(&app.db.pool)
-    .await
-    .unwrap();
+    app.fund(disbursed_wallet, "STRK", disbursed_amount.clone())
+        .await;
 
     sqlx::query!(
         r#"
@@ -319,12 +316,13 @@ async fn test_close_project_no_refund_needed() {
     .await
     .unwrap();
 
-    let req = Request::post("/closed_project")
+    let req = app
+        .signed(&owner, Request::post("/closed_project"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(
             json!({
-                "project_id": project_id,
-                "owner_address": owner_address
+                "project_id": project_id
             })
             .to_string(),
         ))
@@ -333,12 +331,74 @@ async fn test_close_project_no_refund_needed() {
 
     assert_eq!(res.status(), StatusCode::OK);
 
-    let user = sqlx::query!(
-        "SELECT balance FROM escrow_users WHERE wallet_address = $1",
-        owner_address
+    assert_eq!(
+        app.balance(&owner_address, "STRK").await,
+        BigDecimal::from(0)
+    );
+}
+
+async fn add_member(app: &TestApp, project_id: Uuid, wallet_address: &str, role: &str) {
+    sqlx::query(
+        r#"
+        INSERT INTO project_members (project_id, wallet_address, role, invited_by, accepted_at)
+        SELECT id, $2, $3::project_member_role, owner_address, now() FROM projects WHERE id = $1
+        "#,
     )
-    .fetch_one(&app.db.pool)
+    .bind(project_id)
+    .bind(wallet_address)
+    .bind(role)
+    .execute(&app.db.pool)
     .await
     .unwrap();
-    assert_eq!(user.balance, BigDecimal::from(0));
+}
+
+#[tokio::test]
+async fn test_close_project_by_maintainer_refunds_owner() {
+    let app = TestApp::new().await;
+    let owner = app.wallet();
+    let maintainer = app.wallet();
+    let project_id = create_project(&app, &owner, None).await;
+    add_member(&app, project_id, &maintainer.address, "maintainer").await;
+
+    let req = app
+        .signed(&maintainer, Request::post("/closed_project"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(json!({ "project_id": project_id }).to_string()))
+        .unwrap();
+    assert_eq!(app.request(req).await.status(), StatusCode::OK);
+
+    assert_eq!(
+        app.balance(&owner.address, "STRK").await,
+        BigDecimal::from(1000)
+    );
+    assert_eq!(
+        app.balance(&maintainer.address, "STRK").await,
+        BigDecimal::from(0)
+    );
+}
+
+#[tokio::test]
+async fn test_close_project_viewer_forbidden() {
+    let app = TestApp::new().await;
+    let owner = app.wallet();
+    let viewer = app.wallet();
+    let project_id = create_project(&app, &owner, None).await;
+    add_member(&app, project_id, &viewer.address, "viewer").await;
+
+    let req = app
+        .signed(&viewer, Request::post("/closed_project"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(json!({ "project_id": project_id }).to_string()))
+        .unwrap();
+    assert_eq!(app.request(req).await.status(), StatusCode::FORBIDDEN);
+
+    let closed_at: Option<chrono::DateTime<chrono::Utc>> =
+        sqlx::query_scalar("SELECT closed_at FROM projects WHERE id = $1")
+            .bind(project_id)
+            .fetch_one(&app.db.pool)
+            .await
+            .unwrap();
+    assert!(closed_at.is_none());
 }
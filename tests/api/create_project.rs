@@ -1,8 +1,9 @@
-use crate::helpers::TestApp;
+use crate::helpers::{TestApp, generate_address};
 use axum::{
     body::Body,
     http::{Request, StatusCode},
 };
+use bigdecimal::BigDecimal;
 use serde_json::json;
 use sqlx::Row;
 
@@ -12,16 +13,15 @@ async fn test_create_project_ok() {
     let db = &app.db;
 
     // Ensure the test user exists in escrow_users
-    let owner_address = "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+    let owner = app.wallet();
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
-        .bind(owner_address)
+        .bind(&owner.address)
         .execute(&db.pool)
         .await
         .expect("Failed to insert test escrow user");
 
     // Prepare a valid project creation payload
     let payload = json!({
-        "owner_address": owner_address,
         "contract_address": "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcd",
         "name": "Test Project",
         "description": "A valid project description for testing.",
@@ -35,7 +35,9 @@ async fn test_create_project_ok() {
         "bounty_expiry_date": null
     });
 
-    let req = Request::post("/create_project")
+    let req = app
+        .signed(&owner, Request::post("/create_project"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -61,15 +63,17 @@ async fn test_create_project_ok() {
 #[tokio::test]
 async fn test_create_project_missing_required_field() {
     let app = TestApp::new().await;
+    let owner = app.wallet();
     let payload = serde_json::json!({
-        // Missing owner_address
-        "contract_address": "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcd",
+        // Missing contract_address
         "name": "Test Project",
         "description": "A valid project description for testing.",
         "contact_info": "contact@example.com",
         "tags": ["DeFi"]
     });
-    let req = Request::post("/create_project")
+    let req = app
+        .signed(&owner, Request::post("/create_project"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -82,15 +86,17 @@ async fn test_create_project_missing_required_field() {
 #[tokio::test]
 async fn test_create_project_invalid_address() {
     let app = TestApp::new().await;
+    let owner = app.wallet();
     let payload = serde_json::json!({
-        "owner_address": "invalid_address",
         "contract_address": "invalid_address",
         "name": "Test Project",
         "description": "A valid project description for testing.",
         "contact_info": "contact@example.com",
         "tags": ["DeFi"]
     });
-    let req = Request::post("/create_project")
+    let req = app
+        .signed(&owner, Request::post("/create_project"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -100,17 +106,99 @@ async fn test_create_project_invalid_address() {
     assert_eq!(status, StatusCode::BAD_REQUEST);
 }
 
+#[tokio::test]
+async fn test_create_project_bounty_is_funded_from_balance() {
+    let app = TestApp::new().await;
+    let owner = app.wallet();
+    app.fund(&owner.address, "STRK", BigDecimal::from(1500))
+        .await;
+    let contract_address = generate_address();
+    let payload = json!({
+        "contract_address": contract_address,
+        "name": "Funded Project",
+        "description": "A project funding its bounty on creation.",
+        "contact_info": "contact@example.com",
+        "tags": ["DeFi"],
+        "bounty_amount": "1000",
+        "bounty_currency": "STRK",
+        "bounty_expiry_date": chrono::Utc::now() + chrono::Duration::days(30)
+    });
+    let req = app
+        .signed(&owner, Request::post("/create_project"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    assert_eq!(app.request(req).await.status(), StatusCode::CREATED);
+
+    assert_eq!(
+        app.balance(&owner.address, "STRK").await,
+        BigDecimal::from(500)
+    );
+    let pool: BigDecimal = sqlx::query_scalar(
+        r#"
+        SELECT SUM(lp.amount)
+        FROM ledger_postings lp
+        JOIN ledger_accounts a ON a.id = lp.account_id
+        JOIN projects p ON p.id = a.project_id
+        WHERE a.kind = 'project_pool' AND p.contract_address = $1
+        "#,
+    )
+    .bind(&contract_address)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap();
+    assert_eq!(pool, BigDecimal::from(1000));
+}
+
+#[tokio::test]
+async fn test_create_project_unfunded_bounty_rejected() {
+    let app = TestApp::new().await;
+    let owner = app.wallet();
+    app.fund(&owner.address, "STRK", BigDecimal::from(100))
+        .await;
+    let contract_address = generate_address();
+    let payload = json!({
+        "contract_address": contract_address,
+        "name": "Unfunded Project",
+        "description": "A project whose owner cannot cover its bounty.",
+        "contact_info": "contact@example.com",
+        "tags": ["DeFi"],
+        "bounty_amount": "1000",
+        "bounty_currency": "STRK",
+        "bounty_expiry_date": chrono::Utc::now() + chrono::Duration::days(30)
+    });
+    let req = app
+        .signed(&owner, Request::post("/create_project"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    assert_eq!(app.request(req).await.status(), StatusCode::BAD_REQUEST);
+
+    let created: bool =
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM projects WHERE contract_address = $1)")
+            .bind(&contract_address)
+            .fetch_one(&app.db.pool)
+            .await
+            .unwrap();
+    assert!(!created);
+    assert_eq!(
+        app.balance(&owner.address, "STRK").await,
+        BigDecimal::from(100)
+    );
+}
+
 #[tokio::test]
 async fn test_create_project_partial_bounty_fields() {
     let app = TestApp::new().await;
-    let owner_address = "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+    let owner = app.wallet();
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
-        .bind(owner_address)
+        .bind(&owner.address)
         .execute(&app.db.pool)
         .await
         .expect("Failed to insert test escrow user");
     let payload = serde_json::json!({
-        "owner_address": owner_address,
         "contract_address": "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabce",
         "name": "Test Project",
         "description": "A valid project description for testing.",
@@ -119,7 +207,9 @@ async fn test_create_project_partial_bounty_fields() {
         "bounty_amount": "1000.00"
         // Missing bounty_currency and bounty_expiry_date
     });
-    let req = Request::post("/create_project")
+    let req = app
+        .signed(&owner, Request::post("/create_project"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -133,15 +223,14 @@ async fn test_create_project_partial_bounty_fields() {
 async fn test_create_project_duplicate_contract_address() {
     let app = TestApp::new().await;
     let db = &app.db;
-    let owner_address = "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+    let owner = app.wallet();
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
-        .bind(owner_address)
+        .bind(&owner.address)
         .execute(&db.pool)
         .await
         .expect("Failed to insert test escrow user");
     let contract_address = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcf";
     let payload = serde_json::json!({
-        "owner_address": owner_address,
         "contract_address": contract_address,
         "name": "Test Project",
         "description": "A valid project description for testing.",
@@ -149,7 +238,9 @@ async fn test_create_project_duplicate_contract_address() {
         "tags": ["DeFi"]
     });
 
-    let req1 = Request::post("/create_project")
+    let req1 = app
+        .signed(&owner, Request::post("/create_project"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -158,7 +249,9 @@ async fn test_create_project_duplicate_contract_address() {
 
     assert_eq!(status1, StatusCode::CREATED);
 
-    let req2 = Request::post("/create_project")
+    let req2 = app
+        .signed(&owner, Request::post("/create_project"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -177,21 +270,22 @@ async fn test_create_project_duplicate_contract_address() {
 #[tokio::test]
 async fn test_create_project_invalid_contact_info() {
     let app = TestApp::new().await;
-    let owner_address = "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+    let owner = app.wallet();
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
-        .bind(owner_address)
+        .bind(&owner.address)
         .execute(&app.db.pool)
         .await
         .expect("Failed to insert test escrow user");
     let payload = serde_json::json!({
-        "owner_address": owner_address,
         "contract_address": "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabdd",
         "name": "Test Project",
         "description": "A valid project description for testing.",
         "contact_info": "not-an-email-or-url",
         "tags": ["DeFi"]
     });
-    let req = Request::post("/create_project")
+    let req = app
+        .signed(&owner, Request::post("/create_project"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
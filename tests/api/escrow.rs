@@ -1,4 +1,4 @@
-use crate::helpers::{TestApp, generate_address};
+use crate::helpers::{TestApp, TestWallet, generate_address};
 use axum::{
     body::Body,
     http::{Request, StatusCode},
@@ -10,18 +10,12 @@ use serde_json::json;
 async fn allocate_bounty_happy_path() {
     let app = TestApp::new().await;
     let db = &app.db;
-    use bigdecimal::BigDecimal;
 
-    // Insert a user into escrow_users with a sufficient balance
-    let wallet = generate_address();
-    sqlx::query(
-        "INSERT INTO escrow_users (wallet_address, balance) VALUES ($1, $2) ON CONFLICT DO NOTHING",
-    )
-    .bind(&wallet)
-    .bind(BigDecimal::from(1000))
-    .execute(&db.pool)
-    .await
-    .expect("Failed to insert user");
+    // Fund the user's USDT escrow balance
+    let signer = app.wallet();
+    let wallet = signer.address.clone();
+    app.fund(&wallet, "USDT", bigdecimal::BigDecimal::from(1000))
+        .await;
 
     // Insert a project owned by the user
     let contract_address = generate_address();
@@ -39,13 +33,14 @@ async fn allocate_bounty_happy_path() {
 
     // Prepare payload
     let payload = serde_json::json!({
-        "wallet_address": wallet,
         "project_contract_address": contract_address,
         "amount": "100.0",
         "currency": "USDT",
         "bounty_expiry_date": chrono::Utc::now() + chrono::Duration::days(30),
     });
-    let req = axum::http::Request::post("/allocate_bounty")
+    let req = app
+        .signed(&signer, axum::http::Request::post("/allocate_bounty"))
+        .await
         .header("content-type", "application/json")
         .body(axum::body::Body::from(payload.to_string()))
         .unwrap();
@@ -57,17 +52,12 @@ async fn allocate_bounty_happy_path() {
 async fn allocate_bounty_invalid_amount() {
     let app = TestApp::new().await;
     let db = &app.db;
-    let wallet = generate_address();
+    let signer = app.wallet();
+    let wallet = signer.address.clone();
     let contract_address = generate_address();
     // Insert user and project
-    sqlx::query(
-        "INSERT INTO escrow_users (wallet_address, balance) VALUES ($1, $2) ON CONFLICT DO NOTHING",
-    )
-    .bind(&wallet)
-    .bind(bigdecimal::BigDecimal::from(1000))
-    .execute(&db.pool)
-    .await
-    .unwrap();
+    app.fund(&wallet, "USDT", bigdecimal::BigDecimal::from(1000))
+        .await;
     sqlx::query(
         "INSERT INTO projects (owner_address, contract_address, name, description, contact_info) VALUES ($1, $2, $3, $4, $5) ON CONFLICT DO NOTHING",
     )
@@ -81,13 +71,14 @@ async fn allocate_bounty_invalid_amount() {
     .unwrap();
     // Zero amount
     let payload = json!({
-        "wallet_address": wallet,
         "project_contract_address": contract_address,
         "amount": "0",
         "currency": "USD",
         "bounty_expiry_date": chrono::Utc::now().to_rfc3339(),
     });
-    let req = Request::post("/allocate_bounty")
+    let req = app
+        .signed(&signer, Request::post("/allocate_bounty"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -98,17 +89,18 @@ async fn allocate_bounty_invalid_amount() {
 #[tokio::test]
 async fn allocate_bounty_invalid_address_format() {
     let app: TestApp = TestApp::new().await;
-    let wallet = "not_a_valid_address".to_string();
+    let signer = app.wallet();
     let contract_address = "0x123".to_string();
 
     let payload = json!({
-        "wallet_address": wallet,
         "project_contract_address": contract_address,
         "amount": "100.0",
         "currency": "USD",
         "bounty_expiry_date": chrono::Utc::now().to_rfc3339(),
     });
-    let req = Request::post("/allocate_bounty")
+    let req = app
+        .signed(&signer, Request::post("/allocate_bounty"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -120,7 +112,8 @@ async fn allocate_bounty_invalid_address_format() {
 async fn allocate_bounty_escrow_user_not_found() {
     let app = TestApp::new().await;
     let db = &app.db;
-    let wallet = generate_address();
+    let signer = app.wallet();
+    let wallet = signer.address.clone();
     let contract_address = generate_address();
     // Only insert project
     sqlx::query(
@@ -135,13 +128,14 @@ async fn allocate_bounty_escrow_user_not_found() {
     .await
     .unwrap();
     let payload = json!({
-        "wallet_address": wallet,
         "project_contract_address": contract_address,
         "amount": "100.0",
         "currency": "USD",
         "bounty_expiry_date": chrono::Utc::now().to_rfc3339(),
     });
-    let req = Request::post("/allocate_bounty")
+    let req = app
+        .signed(&signer, Request::post("/allocate_bounty"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -153,17 +147,12 @@ async fn allocate_bounty_escrow_user_not_found() {
 async fn allocate_bounty_insufficient_balance() {
     let app = TestApp::new().await;
     let db = &app.db;
-    let wallet = generate_address();
+    let signer = app.wallet();
+    let wallet = signer.address.clone();
     let contract_address = generate_address();
     // Insert user with low balance and project
-    sqlx::query(
-        "INSERT INTO escrow_users (wallet_address, balance) VALUES ($1, $2) ON CONFLICT DO NOTHING",
-    )
-    .bind(&wallet)
-    .bind(bigdecimal::BigDecimal::from(10))
-    .execute(&db.pool)
-    .await
-    .unwrap();
+    app.fund(&wallet, "USDT", bigdecimal::BigDecimal::from(10))
+        .await;
     sqlx::query(
         "INSERT INTO projects (owner_address, contract_address, name, description, contact_info) VALUES ($1, $2, $3, $4, $5) ON CONFLICT DO NOTHING",
     )
@@ -176,13 +165,14 @@ async fn allocate_bounty_insufficient_balance() {
     .await
     .unwrap();
     let payload = json!({
-        "wallet_address": wallet,
         "project_contract_address": contract_address,
         "amount": "100.0",
         "currency": "USD",
         "bounty_expiry_date": chrono::Utc::now().to_rfc3339(),
     });
-    let req = Request::post("/allocate_bounty")
+    let req = app
+        .signed(&signer, Request::post("/allocate_bounty"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -193,26 +183,21 @@ async fn allocate_bounty_insufficient_balance() {
 #[tokio::test]
 async fn allocate_bounty_project_not_found() {
     let app = TestApp::new().await;
-    let db = &app.db;
-    let wallet = generate_address();
+    let signer = app.wallet();
+    let wallet = signer.address.clone();
     let contract_address = generate_address();
     // Insert user only
-    sqlx::query(
-        "INSERT INTO escrow_users (wallet_address, balance) VALUES ($1, $2) ON CONFLICT DO NOTHING",
-    )
-    .bind(&wallet)
-    .bind(bigdecimal::BigDecimal::from(1000))
-    .execute(&db.pool)
-    .await
-    .unwrap();
+    app.fund(&wallet, "USDT", bigdecimal::BigDecimal::from(1000))
+        .await;
     let payload = json!({
-        "wallet_address": wallet,
         "project_contract_address": contract_address,
         "amount": "100.0",
         "currency": "USD",
         "bounty_expiry_date": chrono::Utc::now().to_rfc3339(),
     });
-    let req = Request::post("/allocate_bounty")
+    let req = app
+        .signed(&signer, Request::post("/allocate_bounty"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -224,18 +209,13 @@ async fn allocate_bounty_project_not_found() {
 async fn allocate_bounty_user_not_owner() {
     let app = TestApp::new().await;
     let db = &app.db;
-    let wallet = generate_address();
+    let signer = app.wallet();
+    let wallet = signer.address.clone();
     let other_wallet = generate_address();
     let contract_address = generate_address();
     // Insert user and project owned by someone else
-    sqlx::query(
-        "INSERT INTO escrow_users (wallet_address, balance) VALUES ($1, $2) ON CONFLICT DO NOTHING",
-    )
-    .bind(&wallet)
-    .bind(bigdecimal::BigDecimal::from(1000))
-    .execute(&db.pool)
-    .await
-    .unwrap();
+    app.fund(&wallet, "USDT", bigdecimal::BigDecimal::from(1000))
+        .await;
     sqlx::query(
         "INSERT INTO projects (owner_address, contract_address, name, description, contact_info) VALUES ($1, $2, $3, $4, $5) ON CONFLICT DO NOTHING",
     )
@@ -248,16 +228,130 @@ async fn allocate_bounty_user_not_owner() {
     .await
     .unwrap();
     let payload = json!({
-        "wallet_address": wallet,
         "project_contract_address": contract_address,
         "amount": "100.0",
         "currency": "USDT",
         "bounty_expiry_date": chrono::Utc::now() + chrono::Duration::days(30),
     });
-    let req = Request::post("/allocate_bounty")
+    let req = app
+        .signed(&signer, Request::post("/allocate_bounty"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
     let res = app.request(req).await;
-    assert_eq!(res.status(), StatusCode::OK);
+    // Only the project's owner and maintainers fund its bounty.
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    assert_eq!(
+        app.balance(&wallet, "USDT").await,
+        bigdecimal::BigDecimal::from(1000)
+    );
+}
+
+async fn allocate(
+    app: &TestApp,
+    signer: &TestWallet,
+    contract_address: &str,
+    currency: &str,
+) -> StatusCode {
+    let payload = json!({
+        "project_contract_address": contract_address,
+        "amount": "100.0",
+        "currency": currency,
+        "bounty_expiry_date": chrono::Utc::now() + chrono::Duration::days(30),
+    });
+    let req = app
+        .signed(signer, Request::post("/allocate_bounty"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    app.request(req).await.status()
+}
+
+#[tokio::test]
+async fn allocate_bounty_debits_matching_currency_only() {
+    let app = TestApp::new().await;
+    let signer = app.wallet();
+    let wallet = signer.address.clone();
+    let contract_address = generate_address();
+    app.fund(&wallet, "USDC", bigdecimal::BigDecimal::from(1000))
+        .await;
+    app.fund(&wallet, "STRK", bigdecimal::BigDecimal::from(50))
+        .await;
+    sqlx::query(
+        "INSERT INTO projects (owner_address, contract_address, name, description, contact_info) VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(&wallet)
+    .bind(&contract_address)
+    .bind("Test Project")
+    .bind("A test project.")
+    .bind("test@example.com")
+    .execute(&app.db.pool)
+    .await
+    .unwrap();
+
+    // 100 STRK is not covered by 1000 USDC.
+    assert_eq!(
+        allocate(&app, &signer, &contract_address, "STRK").await,
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        allocate(&app, &signer, &contract_address, "USDC").await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        app.balance(&wallet, "USDC").await,
+        bigdecimal::BigDecimal::from(900)
+    );
+    assert_eq!(
+        app.balance(&wallet, "STRK").await,
+        bigdecimal::BigDecimal::from(50)
+    );
+}
+
+#[tokio::test]
+async fn allocate_bounty_keeps_existing_currency() {
+    let app = TestApp::new().await;
+    let signer = app.wallet();
+    let wallet = signer.address.clone();
+    let contract_address = generate_address();
+    app.fund(&wallet, "USDC", bigdecimal::BigDecimal::from(1000))
+        .await;
+    app.fund(&wallet, "STRK", bigdecimal::BigDecimal::from(1000))
+        .await;
+    sqlx::query(
+        "INSERT INTO projects (owner_address, contract_address, name, description, contact_info) VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(&wallet)
+    .bind(&contract_address)
+    .bind("Test Project")
+    .bind("A test project.")
+    .bind("test@example.com")
+    .execute(&app.db.pool)
+    .await
+    .unwrap();
+
+    assert_eq!(
+        allocate(&app, &signer, &contract_address, "USDC").await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        allocate(&app, &signer, &contract_address, "STRK").await,
+        StatusCode::CONFLICT
+    );
+
+    let (amount, currency): (bigdecimal::BigDecimal, String) = sqlx::query_as(
+        "SELECT bounty_amount, bounty_currency FROM projects WHERE contract_address = $1",
+    )
+    .bind(&contract_address)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap();
+    assert_eq!(amount, bigdecimal::BigDecimal::from(100));
+    assert_eq!(currency, "USDC");
+    assert_eq!(
+        app.balance(&wallet, "STRK").await,
+        bigdecimal::BigDecimal::from(1000)
+    );
 }
@@ -1,26 +1,358 @@
-use std::sync::Once;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, Once},
+};
 
-use axum::{Router, body::Body, extract::Request, response::Response};
-use fortichain_server::{AppState, Configuration, api_router, db::Db, telemetry};
+use async_trait::async_trait;
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    extract::Request,
+    http::{StatusCode, request::Builder},
+    response::Response,
+};
+use bigdecimal::BigDecimal;
+use fortichain_server::{
+    AppState, Configuration, api_router,
+    chain::{
+        ChainTransactionStatus, ConfirmationSource, GasPrices, LocalPayoutSigner, PayoutNode,
+        SignatureVerifier, SignedInvoke, SubmitError, typed_data_hash,
+    },
+    db::Db,
+    jobs::{
+        ChainWatcher, PayoutSummary, PayoutWorker, SettlementSummary, WatchSummary,
+        settle_pending_transactions,
+    },
+    telemetry,
+    verification::OwnershipVerifier,
+};
 use rand::Rng;
+use serde_json::{Value, json};
 use sqlx::{Connection, Executor, PgConnection};
+use starknet::core::utils::get_selector_from_name;
+use starknet::{
+    core::{crypto::Signature, types::Felt},
+    signers::{SigningKey, VerifyingKey},
+};
 use tower::ServiceExt;
 use uuid::Uuid;
+use wiremock::{Mock, MockServer, Respond, ResponseTemplate, matchers::method};
 
 static TRACING: Once = Once::new();
 
+pub const STRK: &str = "0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d";
+pub const ESCROW_CONTRACT: &str = "0x0123";
+
+/// A Starknet node serving `starknet_blockNumber`, `starknet_getBlockWithTxHashes` and
+/// `starknet_getEvents` from an in-memory chain.
+#[derive(Clone, Default)]
+pub struct MockChain {
+    state: Arc<Mutex<ChainState>>,
+}
+
+#[derive(Default)]
+struct ChainState {
+    /// Hash of every block, indexed by number.
+    blocks: Vec<Felt>,
+    /// Events with the block they were emitted in.
+    events: Vec<(u64, Value)>,
+    /// Bumped on every reorg so replaced blocks get new hashes.
+    fork: u64,
+}
+
+impl MockChain {
+    pub async fn start(blocks: u64) -> (MockServer, Self) {
+        let chain = Self::default();
+        chain.mine(blocks);
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(chain.clone())
+            .mount(&server)
+            .await;
+        (server, chain)
+    }
+
+    /// Appends `blocks` blocks, returning the number of the last one.
+    pub fn mine(&self, blocks: u64) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        for _ in 0..blocks {
+            let number = state.blocks.len() as u64;
+            let hash = Felt::from((state.fork << 32) + number + 1);
+            state.blocks.push(hash);
+        }
+        state.blocks.len() as u64 - 1
+    }
+
+    /// Emits a `Deposit` of `amount` whole tokens from `depositor` in block `block`.
+    pub fn deposit(
+        &self,
+        block: u64,
+        transaction_hash: &str,
+        depositor: &str,
+        token: &str,
+        amount: u64,
+    ) {
+        let raw = Felt::from(amount) * Felt::from(10u128.pow(18));
+        self.deposit_raw(block, transaction_hash, depositor, token, raw);
+    }
+
+    /// Emits a `Deposit` of `raw` base units of `token`.
+    fn deposit_raw(
+        &self,
+        block: u64,
+        transaction_hash: &str,
+        depositor: &str,
+        token: &str,
+        raw: Felt,
+    ) {
+        let event = json!({
+            "from_address": ESCROW_CONTRACT,
+            "keys": [
+                get_selector_from_name("Deposit").unwrap().to_hex_string(),
+                depositor,
+            ],
+            "data": [token, raw.to_hex_string(), "0x0"],
+            "transaction_hash": transaction_hash,
+        });
+        self.state.lock().unwrap().events.push((block, event));
+    }
+
+    /// Replaces every block from `from` on with as many new ones, dropping their events.
+    pub fn reorg(&self, from: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.fork += 1;
+        let height = state.blocks.len() as u64;
+        state.blocks.truncate(from as usize);
+        state.events.retain(|(block, _)| *block < from);
+        drop(state);
+        self.mine(height - from);
+    }
+
+    fn result(&self, method: &str, params: &Value) -> Result<Value, Value> {
+        let state = self.state.lock().unwrap();
+        match method {
+            "starknet_blockNumber" => Ok(json!(state.blocks.len() as u64 - 1)),
+            "starknet_getBlockWithTxHashes" => {
+                let number = params["block_id"]["block_number"].as_u64().unwrap();
+                let Some(hash) = state.blocks.get(number as usize) else {
+                    return Err(json!({ "code": 24, "message": "Block not found" }));
+                };
+                let parent = number
+                    .checked_sub(1)
+                    .map_or(Felt::ZERO, |parent| state.blocks[parent as usize]);
+                let price = json!({ "price_in_fri": "0x1", "price_in_wei": "0x1" });
+                Ok(json!({
+                    "status": "ACCEPTED_ON_L2",
+                    "block_hash": hash.to_hex_string(),
+                    "parent_hash": parent.to_hex_string(),
+                    "block_number": number,
+                    "new_root": "0x0",
+                    "timestamp": 0,
+                    "sequencer_address": "0x0",
+                    "l1_gas_price": price,
+                    "l2_gas_price": price,
+                    "l1_data_gas_price": price,
+                    "l1_da_mode": "BLOB",
+                    "starknet_version": "0.13.5",
+                    "transactions": [],
+                }))
+            }
+            "starknet_getEvents" => {
+                let filter = &params["filter"];
+                let from = filter["from_block"]["block_number"].as_u64().unwrap();
+                let to = filter["to_block"]["block_number"].as_u64().unwrap();
+                let chunk_size = filter["chunk_size"].as_u64().unwrap() as usize;
+                let offset = filter["continuation_token"]
+                    .as_str()
+                    .map_or(0, |token| token.parse().unwrap());
+
+                let matching: Vec<Value> = state
+                    .events
+                    .iter()
+                    .filter(|(block, _)| (from..=to).contains(block))
+                    .map(|(block, event)| {
+                        let mut event = event.clone();
+                        event["block_number"] = json!(block);
+                        event["block_hash"] = json!(state.blocks[*block as usize].to_hex_string());
+                        event
+                    })
+                    .collect();
+                let next = offset + chunk_size;
+                Ok(json!({
+                    "events": matching.iter().skip(offset).take(chunk_size).collect::<Vec<_>>(),
+                    "continuation_token": (next < matching.len()).then(|| next.to_string()),
+                }))
+            }
+            other => panic!("Unexpected JSON-RPC method {other}"),
+        }
+    }
+}
+
+impl Respond for MockChain {
+    fn respond(&self, request: &wiremock::Request) -> ResponseTemplate {
+        let request: Value = request.body_json().unwrap();
+        let method = request["method"].as_str().unwrap();
+        let body = match self.result(method, &request["params"]) {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }),
+            Err(error) => json!({ "jsonrpc": "2.0", "id": request["id"], "error": error }),
+        };
+        ResponseTemplate::new(200).set_body_json(body)
+    }
+}
+
+/// A chain watcher following the escrow contract on `server`.
+pub fn watcher(app: &TestApp, server: &MockServer) -> ChainWatcher {
+    let mut configuration = Configuration::clone(&Configuration::new());
+    configuration.starknet_rpc_url = server.uri();
+    configuration.escrow_contract_address = Some(ESCROW_CONTRACT.to_string());
+    ChainWatcher::new(app.db.pool.clone(), &configuration)
+        .unwrap()
+        .expect("Escrow contract is configured")
+}
+
 pub struct TestApp {
     pub router: Router,
     pub db: Db,
+    pub keyring: Arc<Keyring>,
+    pub chain: FakeChain,
+    pub payout_node: Arc<FakePayoutNode>,
+    /// The chain the escrow contract emits its `Deposit` events on.
+    escrow_chain: MockChain,
+    escrow_node: MockServer,
+    payout_signer: Arc<LocalPayoutSigner>,
+}
+
+/// Stands in for on-chain account contracts: each test wallet is registered with the
+/// public key of the locally generated keypair it signs with.
+#[derive(Default)]
+pub struct Keyring {
+    keys: Mutex<HashMap<Felt, VerifyingKey>>,
+}
+
+#[async_trait]
+impl SignatureVerifier for Keyring {
+    async fn verify(&self, account: Felt, hash: Felt, signature: &[Felt]) -> anyhow::Result<bool> {
+        let keys = self.keys.lock().unwrap();
+        let (Some(key), [r, s]) = (keys.get(&account), signature) else {
+            return Ok(false);
+        };
+        Ok(key.verify(&hash, &Signature { r: *r, s: *s })?)
+    }
+}
+
+/// Stands in for the chain when settling escrow transactions: hashes it has not been told about
+/// are still pending.
+#[derive(Default)]
+pub struct FakeChain {
+    statuses: Mutex<HashMap<String, ChainTransactionStatus>>,
+}
+
+impl FakeChain {
+    pub fn set(&self, transaction_hash: &str, status: ChainTransactionStatus) {
+        self.statuses
+            .lock()
+            .unwrap()
+            .insert(transaction_hash.to_string(), status);
+    }
+}
+
+#[async_trait]
+impl ConfirmationSource for FakeChain {
+    async fn status(&self, transaction_hash: &str) -> anyhow::Result<ChainTransactionStatus> {
+        Ok(self
+            .statuses
+            .lock()
+            .unwrap()
+            .get(transaction_hash)
+            .copied()
+            .unwrap_or(ChainTransactionStatus::Pending))
+    }
+}
+
+/// Stands in for the node withdrawals are paid out through. It accepts every transaction unless
+/// told otherwise, and keeps the ones it accepted.
+#[derive(Default)]
+pub struct FakePayoutNode {
+    pub accepted: Mutex<Vec<SignedInvoke>>,
+    outcome: Mutex<Option<NodeOutcome>>,
+}
+
+#[derive(Clone, Copy)]
+pub enum NodeOutcome {
+    Reject,
+    /// Takes the transaction, but the response never arrives.
+    Lost,
+}
+
+impl FakePayoutNode {
+    /// How the node answers from now on; `None` to accept again.
+    pub fn answer(&self, outcome: Option<NodeOutcome>) {
+        *self.outcome.lock().unwrap() = outcome;
+    }
+}
+
+#[async_trait]
+impl PayoutNode for FakePayoutNode {
+    async fn nonce(&self, _account: Felt) -> anyhow::Result<Felt> {
+        Ok(Felt::from(self.accepted.lock().unwrap().len()))
+    }
+
+    async fn gas_prices(&self) -> anyhow::Result<GasPrices> {
+        Ok(GasPrices {
+            l1_gas: 1,
+            l2_gas: 1,
+            l1_data_gas: 1,
+        })
+    }
+
+    async fn submit(&self, invoke: &SignedInvoke) -> Result<Felt, SubmitError> {
+        let outcome = *self.outcome.lock().unwrap();
+        match outcome {
+            Some(NodeOutcome::Reject) => Err(SubmitError::Rejected(
+                "Account balance is smaller than the transaction's maximal fee".to_string(),
+            )),
+            Some(NodeOutcome::Lost) => {
+                self.accepted.lock().unwrap().push(invoke.clone());
+                Err(SubmitError::Unknown(anyhow::anyhow!("connection reset")))
+            }
+            None => {
+                self.accepted.lock().unwrap().push(invoke.clone());
+                Ok(invoke.transaction_hash)
+            }
+        }
+    }
+}
+
+/// A wallet backed by a locally generated Stark keypair.
+pub struct TestWallet {
+    pub address: String,
+    account: Felt,
+    signing_key: SigningKey,
+}
+
+impl TestWallet {
+    /// Signs SNIP-12 `typed_data` as this wallet, returning the signature's hex felts.
+    pub fn sign(&self, typed_data: &serde_json::Value) -> Vec<String> {
+        let hash = typed_data_hash(typed_data.clone(), self.account).unwrap();
+        let signature = self.signing_key.sign(&hash).unwrap();
+        vec![signature.r.to_hex_string(), signature.s.to_hex_string()]
+    }
 }
 
 impl TestApp {
     pub async fn new() -> Self {
+        Self::with_configuration(|_| {}).await
+    }
+
+    /// Starts the app with the configuration from the environment, adjusted by `configure`.
+    pub async fn with_configuration(configure: impl FnOnce(&mut Configuration)) -> Self {
         dotenvy::dotenv().ok();
         unsafe { std::env::set_var("PORT", "0") };
 
         TRACING.call_once(telemetry::setup_tracing);
-        let cfg = Configuration::new();
+        let mut cfg = Configuration::clone(&Configuration::new());
+        configure(&mut cfg);
+        let cfg = Arc::new(cfg);
 
         let db_str = create_test_db(&cfg.database_url).await;
         let db = Db::new(&db_str, cfg.max_db_connections)
@@ -29,16 +361,206 @@ impl TestApp {
         tracing::debug!("Running migrations");
         db.migrate().await.expect("Failed to run migrations");
 
+        let keyring = Arc::new(Keyring::default());
+        let ownership_verifier = Arc::new(
+            OwnershipVerifier::from_configuration(&cfg, keyring.clone())
+                .expect("Failed to build ownership verifier"),
+        );
         let router = api_router(AppState {
             configuration: cfg,
             db: db.clone(),
+            signature_verifier: keyring.clone(),
+            ownership_verifier,
         });
-        Self { router, db }
+        let (escrow_node, escrow_chain) = MockChain::start(1).await;
+        Self {
+            router,
+            db,
+            keyring,
+            chain: FakeChain::default(),
+            payout_node: Arc::new(FakePayoutNode::default()),
+            escrow_chain,
+            escrow_node,
+            payout_signer: Arc::new(payout_signer()),
+        }
+    }
+
+    /// Runs the payout worker once against the fake node.
+    pub async fn pay_out(&self) -> PayoutSummary {
+        PayoutWorker::new(
+            self.db.pool.clone(),
+            self.payout_signer.clone(),
+            self.payout_node.clone(),
+            &Configuration::new().escrow_tokens,
+        )
+        .expect("Failed to build payout worker")
+        .run()
+        .await
+        .expect("Failed to pay out withdrawals")
+    }
+
+    /// Marks `transaction_hash` as final on chain and settles every pending transaction.
+    pub async fn confirm(&self, transaction_hash: &str) -> SettlementSummary {
+        self.chain
+            .set(transaction_hash, ChainTransactionStatus::Confirmed);
+        self.settle().await
+    }
+
+    /// Runs the confirmation job once against the fake chain.
+    pub async fn settle(&self) -> SettlementSummary {
+        settle_pending_transactions(&self.db.pool, &self.chain)
+            .await
+            .expect("Failed to settle pending transactions")
+    }
+
+    /// Mines a block with the escrow contract's `Deposit` of `amount` whole `currency` tokens from
+    /// `depositor` in `transaction_hash`, then runs the chain watcher once to credit it.
+    pub async fn deposit_on_chain(
+        &self,
+        depositor: &str,
+        transaction_hash: &str,
+        currency: &str,
+        amount: u64,
+    ) -> WatchSummary {
+        let token = Configuration::new()
+            .escrow_tokens
+            .iter()
+            .find(|token| token.currency == currency)
+            .cloned()
+            .expect("Currency is an escrow token");
+        let raw = Felt::from(amount) * Felt::from(10u128.pow(token.decimals));
+        let block = self.escrow_chain.mine(1);
+        self.escrow_chain
+            .deposit_raw(block, transaction_hash, depositor, &token.address, raw);
+        watcher(self, &self.escrow_node)
+            .poll()
+            .await
+            .expect("Failed to watch the escrow contract")
     }
 
     pub async fn request(&self, req: Request<Body>) -> Response<Body> {
         self.router.clone().oneshot(req).await.unwrap()
     }
+
+    /// Creates a wallet whose signatures the app will accept.
+    pub fn wallet(&self) -> TestWallet {
+        let account = Felt::from_bytes_be_slice(&rand::rng().random::<[u8; 31]>());
+        let signing_key = SigningKey::from_random();
+        self.keyring
+            .keys
+            .lock()
+            .unwrap()
+            .insert(account, signing_key.verifying_key());
+
+        TestWallet {
+            address: account.to_fixed_hex_string(),
+            account,
+            signing_key,
+        }
+    }
+
+    /// Requests a challenge for `wallet` and adds the signed authentication headers to `req`.
+    pub async fn signed(&self, wallet: &TestWallet, req: Builder) -> Builder {
+        let challenge = Request::post("/auth/challenge")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                json!({ "wallet_address": wallet.address }).to_string(),
+            ))
+            .unwrap();
+        let res = self.request(challenge).await;
+        let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let challenge: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        let hash = typed_data_hash(challenge["typed_data"].clone(), wallet.account).unwrap();
+        let signature = wallet.signing_key.sign(&hash).unwrap();
+
+        req.header("x-wallet-address", &wallet.address)
+            .header("x-wallet-nonce", challenge["nonce"].as_str().unwrap())
+            .header(
+                "x-wallet-signature",
+                format!(
+                    "{},{}",
+                    signature.r.to_hex_string(),
+                    signature.s.to_hex_string()
+                ),
+            )
+    }
+
+    /// Registers `wallet_address` as a platform user holding `role`.
+    pub async fn grant_role(&self, wallet_address: &str, role: &str) {
+        sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
+            .bind(wallet_address)
+            .execute(&self.db.pool)
+            .await
+            .expect("Failed to insert user");
+        sqlx::query("INSERT INTO user_roles (wallet_address, role) VALUES ($1, $2::user_type)")
+            .bind(wallet_address)
+            .bind(role)
+            .execute(&self.db.pool)
+            .await
+            .expect("Failed to grant role");
+    }
+
+    /// Sets `wallet_address`'s escrow balance in `currency`, opening the escrow account if needed.
+    pub async fn fund(&self, wallet_address: &str, currency: &str, amount: BigDecimal) {
+        sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
+            .bind(wallet_address)
+            .execute(&self.db.pool)
+            .await
+            .expect("Failed to insert user");
+        sqlx::query(
+            r#"
+            INSERT INTO escrow_balances (wallet_address, currency, balance)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (wallet_address, currency) DO UPDATE SET balance = EXCLUDED.balance
+            "#,
+        )
+        .bind(wallet_address)
+        .bind(currency)
+        .bind(amount)
+        .execute(&self.db.pool)
+        .await
+        .expect("Failed to fund escrow balance");
+    }
+
+    /// The escrow balance of `wallet_address` in `currency`, zero if it has none.
+    pub async fn balance(&self, wallet_address: &str, currency: &str) -> BigDecimal {
+        sqlx::query_scalar(
+            "SELECT balance FROM escrow_balances WHERE wallet_address = $1 AND currency = $2",
+        )
+        .bind(wallet_address)
+        .bind(currency)
+        .fetch_optional(&self.db.pool)
+        .await
+        .expect("Failed to fetch balance")
+        .unwrap_or_default()
+    }
+
+    /// Signs in as `wallet` and returns the issued session, including both tokens.
+    pub async fn login(&self, wallet: &TestWallet) -> serde_json::Value {
+        let req = self
+            .signed(wallet, Request::post("/auth/login"))
+            .await
+            .body(Body::empty())
+            .unwrap();
+        let res = self.request(req).await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+        let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+}
+
+/// A hot wallet with a fresh key. Signing never reaches its RPC URL.
+fn payout_signer() -> LocalPayoutSigner {
+    let address = Felt::from_bytes_be_slice(&rand::rng().random::<[u8; 31]>());
+    let private_key = SigningKey::from_random().secret_scalar();
+    LocalPayoutSigner::new(
+        "http://localhost:5050/rpc",
+        "SN_SEPOLIA",
+        &address.to_hex_string(),
+        &private_key.to_hex_string(),
+    )
+    .expect("Failed to build payout signer")
 }
 
 pub async fn create_test_db(db_str: &str) -> String {
@@ -0,0 +1,332 @@
+use crate::helpers::{TestApp, TestWallet, generate_address};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use bigdecimal::BigDecimal;
+use fortichain_server::{
+    IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER, jobs::delete_expired_idempotency_keys,
+};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+
+/// Sends a signed `POST` with a fresh signature, as a client retrying it would.
+async fn post(
+    app: &TestApp,
+    wallet: &TestWallet,
+    uri: &str,
+    key: Option<&str>,
+    payload: &Value,
+) -> axum::response::Response {
+    let mut req = app
+        .signed(wallet, Request::post(uri))
+        .await
+        .header("content-type", "application/json");
+    if let Some(key) = key {
+        req = req.header(IDEMPOTENCY_KEY_HEADER, key);
+    }
+    app.request(req.body(Body::from(payload.to_string())).unwrap())
+        .await
+}
+
+fn replayed(res: &axum::response::Response) -> bool {
+    res.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_some()
+}
+
+async fn transactions(app: &TestApp, wallet: &TestWallet, kind: &str) -> i64 {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM escrow_transactions WHERE wallet_address = $1 AND type::text = $2",
+    )
+    .bind(&wallet.address)
+    .bind(kind)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn retried_withdrawal_is_executed_once() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    app.fund(&wallet.address, "STRK", BigDecimal::from(100))
+        .await;
+    let withdrawal = json!({ "amount": "30", "currency": "STRK" });
+
+    let res = post(&app, &wallet, "/withdraw", Some("withdraw-1"), &withdrawal).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert!(!replayed(&res));
+
+    let res = post(&app, &wallet, "/withdraw", Some("withdraw-1"), &withdrawal).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert!(replayed(&res));
+
+    assert_eq!(transactions(&app, &wallet, "withdrawal").await, 1);
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(70)
+    );
+
+    // A new key is a new withdrawal.
+    let res = post(&app, &wallet, "/withdraw", Some("withdraw-2"), &withdrawal).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert!(!replayed(&res));
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(40)
+    );
+}
+
+#[tokio::test]
+async fn retried_deposit_is_recorded_once() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    let deposit = json!({
+        "amount": "100",
+        "currency": "STRK",
+        "transaction_hash": format!("0x{}", "ef".repeat(31) + "1"),
+    });
+
+    for _ in 0..2 {
+        let res = post(&app, &wallet, "/deposit", Some("deposit-1"), &deposit).await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+    }
+
+    assert_eq!(transactions(&app, &wallet, "deposit").await, 1);
+}
+
+#[tokio::test]
+async fn retried_bounty_allocation_is_executed_once() {
+    let app = TestApp::new().await;
+    let owner = app.wallet();
+    app.fund(&owner.address, "USDT", BigDecimal::from(1000))
+        .await;
+    let contract_address = generate_address();
+    sqlx::query(
+        "INSERT INTO projects (owner_address, contract_address, name, description, contact_info) \
+         VALUES ($1, $2, 'Test Project', 'A test project.', 'test@example.com')",
+    )
+    .bind(&owner.address)
+    .bind(&contract_address)
+    .execute(&app.db.pool)
+    .await
+    .unwrap();
+
+    let allocation = json!({
+        "project_contract_address": contract_address,
+        "amount": "100",
+        "currency": "USDT",
+        "bounty_expiry_date": chrono::Utc::now() + chrono::Duration::days(30),
+    });
+    for _ in 0..3 {
+        let res = post(
+            &app,
+            &owner,
+            "/allocate_bounty",
+            Some("bounty-1"),
+            &allocation,
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    assert_eq!(transactions(&app, &owner, "bounty_allocation").await, 1);
+    assert_eq!(
+        app.balance(&owner.address, "USDT").await,
+        BigDecimal::from(900)
+    );
+}
+
+#[tokio::test]
+async fn rejected_request_is_replayed_as_rejected() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    app.fund(&wallet.address, "STRK", BigDecimal::from(10))
+        .await;
+    let withdrawal = json!({ "amount": "30", "currency": "STRK" });
+
+    let res = post(&app, &wallet, "/withdraw", Some("too-much"), &withdrawal).await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    app.fund(&wallet.address, "STRK", BigDecimal::from(100))
+        .await;
+    let res = post(&app, &wallet, "/withdraw", Some("too-much"), &withdrawal).await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert!(replayed(&res));
+    assert_eq!(transactions(&app, &wallet, "withdrawal").await, 0);
+}
+
+#[tokio::test]
+async fn key_reused_for_another_request_is_rejected() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    app.fund(&wallet.address, "STRK", BigDecimal::from(100))
+        .await;
+
+    let res = post(
+        &app,
+        &wallet,
+        "/withdraw",
+        Some("reused"),
+        &json!({ "amount": "30", "currency": "STRK" }),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+
+    let res = post(
+        &app,
+        &wallet,
+        "/withdraw",
+        Some("reused"),
+        &json!({ "amount": "50", "currency": "STRK" }),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert!(body["errors"]["idempotency_key"].is_array());
+
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(70)
+    );
+}
+
+#[tokio::test]
+async fn keys_are_scoped_to_the_wallet() {
+    let app = TestApp::new().await;
+    let withdrawal = json!({ "amount": "30", "currency": "STRK" });
+
+    for wallet in [app.wallet(), app.wallet()] {
+        app.fund(&wallet.address, "STRK", BigDecimal::from(100))
+            .await;
+        let res = post(&app, &wallet, "/withdraw", Some("shared"), &withdrawal).await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+        assert!(!replayed(&res));
+        assert_eq!(
+            app.balance(&wallet.address, "STRK").await,
+            BigDecimal::from(70)
+        );
+    }
+}
+
+#[tokio::test]
+async fn key_of_request_in_progress_conflicts() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    app.fund(&wallet.address, "STRK", BigDecimal::from(100))
+        .await;
+    let withdrawal = json!({ "amount": "30", "currency": "STRK" });
+
+    let mut request_hash = Sha256::new();
+    request_hash.update(format!("POST\n/withdraw\n{withdrawal}"));
+    sqlx::query(
+        "INSERT INTO idempotency_keys (wallet_address, idempotency_key, request_hash) \
+         VALUES ($1, 'running', $2)",
+    )
+    .bind(&wallet.address)
+    .bind(hex::encode(request_hash.finalize()))
+    .execute(&app.db.pool)
+    .await
+    .unwrap();
+
+    let res = post(&app, &wallet, "/withdraw", Some("running"), &withdrawal).await;
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(100)
+    );
+}
+
+#[tokio::test]
+async fn expired_key_is_claimed_anew() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    app.fund(&wallet.address, "STRK", BigDecimal::from(100))
+        .await;
+    let withdrawal = json!({ "amount": "30", "currency": "STRK" });
+
+    let res = post(&app, &wallet, "/withdraw", Some("stale"), &withdrawal).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    sqlx::query(
+        "UPDATE idempotency_keys SET created_at = NOW() - INTERVAL '25 hours' \
+         WHERE wallet_address = $1",
+    )
+    .bind(&wallet.address)
+    .execute(&app.db.pool)
+    .await
+    .unwrap();
+
+    let res = post(&app, &wallet, "/withdraw", Some("stale"), &withdrawal).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert!(!replayed(&res));
+    assert_eq!(transactions(&app, &wallet, "withdrawal").await, 2);
+}
+
+#[tokio::test]
+async fn expired_keys_are_swept() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    for (key, age) in [("old", "25 hours"), ("recent", "1 hour")] {
+        sqlx::query(
+            "INSERT INTO idempotency_keys (wallet_address, idempotency_key, request_hash, created_at) \
+             VALUES ($1, $2, '', NOW() - $3::interval)",
+        )
+        .bind(&wallet.address)
+        .bind(key)
+        .bind(age)
+        .execute(&app.db.pool)
+        .await
+        .unwrap();
+    }
+
+    delete_expired_idempotency_keys(&app.db.pool).await.unwrap();
+
+    let keys: Vec<String> = sqlx::query_scalar(
+        "SELECT idempotency_key FROM idempotency_keys WHERE wallet_address = $1",
+    )
+    .bind(&wallet.address)
+    .fetch_all(&app.db.pool)
+    .await
+    .unwrap();
+    assert_eq!(keys, ["recent"]);
+}
+
+#[tokio::test]
+async fn session_authenticated_retry_is_replayed() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    app.fund(&wallet.address, "STRK", BigDecimal::from(100))
+        .await;
+    let session = app.login(&wallet).await;
+    let token = session["access_token"].as_str().unwrap();
+    let withdrawal = json!({ "amount": "30", "currency": "STRK" });
+
+    for attempt in 0..2 {
+        let req = Request::post("/withdraw")
+            .header("authorization", format!("Token {token}"))
+            .header("content-type", "application/json")
+            .header(IDEMPOTENCY_KEY_HEADER, "session-withdrawal")
+            .body(Body::from(withdrawal.to_string()))
+            .unwrap();
+        let res = app.request(req).await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+        assert_eq!(replayed(&res), attempt > 0);
+    }
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(70)
+    );
+}
+
+#[tokio::test]
+async fn key_requires_authentication() {
+    let app = TestApp::new().await;
+    let req = Request::post("/withdraw")
+        .header("content-type", "application/json")
+        .header(IDEMPOTENCY_KEY_HEADER, "anonymous")
+        .body(Body::from(
+            json!({ "amount": "30", "currency": "STRK" }).to_string(),
+        ))
+        .unwrap();
+    assert_eq!(app.request(req).await.status(), StatusCode::UNAUTHORIZED);
+}
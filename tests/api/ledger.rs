@@ -0,0 +1,214 @@
+use crate::helpers::{TestApp, TestWallet, generate_address};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use bigdecimal::BigDecimal;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+async fn post(app: &TestApp, wallet: &TestWallet, uri: &str, payload: Value) -> StatusCode {
+    let req = app
+        .signed(wallet, Request::post(uri))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    app.request(req).await.status()
+}
+
+async fn check(app: &TestApp, admin: &TestWallet) -> Value {
+    let req = app
+        .signed(admin, Request::get("/admin/ledger/check"))
+        .await
+        .body(Body::empty())
+        .unwrap();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+fn tx_hash(n: u8) -> String {
+    format!("0x{}{n}", "7b".repeat(31))
+}
+
+async fn create_project(app: &TestApp, owner_address: &str) -> (Uuid, String) {
+    let contract_address = generate_address();
+    let project_id = sqlx::query_scalar(
+        r#"
+        INSERT INTO projects (owner_address, contract_address, name, description, contact_info)
+        VALUES ($1, $2, 'Ledger Project', 'A project for ledger tests', 'test@example.com')
+        RETURNING id
+        "#,
+    )
+    .bind(owner_address)
+    .bind(&contract_address)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap();
+    (project_id, contract_address)
+}
+
+#[tokio::test]
+async fn escrow_flows_keep_the_ledger_balanced() {
+    let app = TestApp::new().await;
+    let admin = app.wallet();
+    let owner = app.wallet();
+    app.grant_role(&admin.address, "admin").await;
+    let (project_id, contract_address) = create_project(&app, &owner.address).await;
+
+    assert_eq!(
+        post(
+            &app,
+            &owner,
+            "/deposit",
+            json!({ "amount": "1000", "currency": "STRK", "transaction_hash": tx_hash(1) }),
+        )
+        .await,
+        StatusCode::CREATED
+    );
+    app.deposit_on_chain(&owner.address, &tx_hash(1), "STRK", 1000)
+        .await;
+    assert_eq!(
+        post(
+            &app,
+            &owner,
+            "/allocate_bounty",
+            json!({
+                "project_contract_address": contract_address,
+                "amount": "600",
+                "currency": "STRK",
+                "bounty_expiry_date": chrono::Utc::now() + chrono::Duration::days(30),
+            }),
+        )
+        .await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        post(
+            &app,
+            &owner,
+            "/withdraw",
+            json!({ "amount": "100", "currency": "STRK" }),
+        )
+        .await,
+        StatusCode::CREATED
+    );
+    assert_eq!(
+        post(
+            &app,
+            &owner,
+            "/closed_project",
+            json!({ "project_id": project_id }),
+        )
+        .await,
+        StatusCode::OK
+    );
+
+    let report = check(&app, &admin).await;
+    assert_eq!(report["balanced"], true, "{report}");
+    assert_eq!(report["violations"], json!([]));
+
+    // Every flow booked exactly one journal entry of two postings.
+    let (entries, postings): (i64, i64) = sqlx::query_as(
+        "SELECT (SELECT COUNT(*) FROM journal_entries), (SELECT COUNT(*) FROM ledger_postings)",
+    )
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap();
+    assert_eq!((entries, postings), (4, 8));
+
+    let user_balance: BigDecimal = sqlx::query_scalar(
+        r#"
+        SELECT SUM(p.amount)
+        FROM ledger_postings p
+        JOIN ledger_accounts a ON a.id = p.account_id
+        WHERE a.kind = 'user' AND a.wallet_address = $1 AND a.currency = 'STRK'
+        "#,
+    )
+    .bind(&owner.address)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap();
+    assert_eq!(user_balance, BigDecimal::from(900));
+    assert_eq!(
+        app.balance(&owner.address, "STRK").await,
+        BigDecimal::from(900)
+    );
+}
+
+#[tokio::test]
+async fn checker_reports_balances_changed_outside_the_ledger() {
+    let app = TestApp::new().await;
+    let admin = app.wallet();
+    let wallet = generate_address();
+    app.grant_role(&admin.address, "admin").await;
+    app.fund(&wallet, "USDC", BigDecimal::from(50)).await;
+
+    let report = check(&app, &admin).await;
+    assert_eq!(report["balanced"], false);
+    assert_eq!(report["violations"][0]["kind"], "balance_mismatch");
+    assert_eq!(report["violations"][0]["wallet_address"], wallet);
+    assert_eq!(report["violations"][0]["currency"], "USDC");
+}
+
+#[tokio::test]
+async fn ledger_check_requires_admin() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+
+    let req = app
+        .signed(&wallet, Request::get("/admin/ledger/check"))
+        .await
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(app.request(req).await.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn ledger_is_append_only_and_entries_must_balance() {
+    let app = TestApp::new().await;
+    let owner = app.wallet();
+    assert_eq!(
+        post(
+            &app,
+            &owner,
+            "/deposit",
+            json!({ "amount": "10", "currency": "STRK", "transaction_hash": tx_hash(3) }),
+        )
+        .await,
+        StatusCode::CREATED
+    );
+    app.deposit_on_chain(&owner.address, &tx_hash(3), "STRK", 10)
+        .await;
+
+    let result = sqlx::query("UPDATE ledger_postings SET amount = amount * 2")
+        .execute(&app.db.pool)
+        .await;
+    assert!(result.is_err());
+    let result = sqlx::query("DELETE FROM journal_entries")
+        .execute(&app.db.pool)
+        .await;
+    assert!(result.is_err());
+
+    // A one-sided entry is refused when its transaction commits.
+    let mut tx = app.db.pool.begin().await.unwrap();
+    let entry_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO journal_entries (description) VALUES ('Unbalanced') RETURNING id",
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO ledger_postings (journal_entry_id, account_id, amount)
+        SELECT $1, id, 10 FROM ledger_accounts WHERE kind = 'platform'
+        "#,
+    )
+    .bind(entry_id)
+    .execute(&mut *tx)
+    .await
+    .unwrap();
+    assert!(tx.commit().await.is_err());
+}
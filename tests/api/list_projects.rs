@@ -0,0 +1,253 @@
+use crate::helpers::{TestApp, generate_address};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use serde_json::Value;
+use uuid::Uuid;
+
+struct Project {
+    name: &'static str,
+    owner_address: String,
+    verified: bool,
+    bounty: Option<(i64, &'static str, i64)>,
+    closed: bool,
+    tags: &'static [&'static str],
+}
+
+impl Project {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            owner_address: generate_address(),
+            verified: false,
+            bounty: None,
+            closed: false,
+            tags: &[],
+        }
+    }
+
+    /// A bounty of `amount` in `currency` expiring in `days`.
+    fn bounty(mut self, amount: i64, currency: &'static str, days: i64) -> Self {
+        self.bounty = Some((amount, currency, days));
+        self
+    }
+
+    async fn insert(self, app: &TestApp) -> Uuid {
+        let (amount, currency, expiry) = match self.bounty {
+            Some((amount, currency, days)) => (
+                Some(amount),
+                Some(currency),
+                Some(Utc::now() + Duration::days(days)),
+            ),
+            None => (None, None, None),
+        };
+        let id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO projects (
+                name, owner_address, contract_address, description, contact_info, is_verified,
+                verification_date, repository_url, bounty_amount, bounty_currency,
+                bounty_expiry_date, closed_at
+            )
+            VALUES (
+                $1, $2, $3, 'A test project.', 'test@example.com', $4,
+                CASE WHEN $4 THEN NOW() END,
+                CASE WHEN $4 THEN 'https://github.com/fortichain/test' END,
+                $5, $6, $7, CASE WHEN $8 THEN NOW() END
+            )
+            RETURNING id
+            "#,
+        )
+        .bind(self.name)
+        .bind(&self.owner_address)
+        .bind(generate_address())
+        .bind(self.verified)
+        .bind(amount.map(bigdecimal::BigDecimal::from))
+        .bind(currency)
+        .bind(expiry)
+        .bind(self.closed)
+        .fetch_one(&app.db.pool)
+        .await
+        .unwrap();
+
+        for tag in self.tags {
+            sqlx::query(
+                r#"
+                WITH tag AS (
+                    INSERT INTO tags (name) VALUES ($2)
+                    ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name
+                    RETURNING id
+                )
+                INSERT INTO project_tags (project_id, tag_id) SELECT $1, id FROM tag
+                "#,
+            )
+            .bind(id)
+            .bind(tag)
+            .execute(&app.db.pool)
+            .await
+            .unwrap();
+        }
+        id
+    }
+}
+
+async fn list(app: &TestApp, query: &str) -> (StatusCode, Value) {
+    let req = Request::get(format!("/projects{query}"))
+        .body(Body::empty())
+        .unwrap();
+    let res = app.request(req).await;
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+async fn names(app: &TestApp, query: &str) -> Vec<String> {
+    let (status, body) = list(app, query).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    body["projects"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|project| project["name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn lists_projects_with_tags_and_bounty() {
+    let app = TestApp::new().await;
+    let id = Project {
+        tags: &["nft", "defi"],
+        ..Project::new("Vault").bounty(500, "USDC", 30)
+    }
+    .insert(&app)
+    .await;
+
+    let (status, body) = list(&app, "").await;
+    assert_eq!(status, StatusCode::OK);
+    let project = &body["projects"][0];
+    assert_eq!(project["id"], id.to_string());
+    assert_eq!(project["tags"], serde_json::json!(["defi", "nft"]));
+    assert_eq!(project["bounty_currency"], "USDC");
+    assert!(project["bounty_amount"].is_string());
+    assert!(project["bounty_expiry_date"].is_string());
+    assert!(project["closed_at"].is_null());
+    assert!(body["next_cursor"].is_null());
+
+    let req = Request::get(format!("/projects/{id}"))
+        .body(Body::empty())
+        .unwrap();
+    let body = to_bytes(app.request(req).await.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let detail: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(detail["tags"], serde_json::json!(["defi", "nft"]));
+    assert_eq!(detail["bounty_currency"], "USDC");
+}
+
+#[tokio::test]
+async fn filters_combine() {
+    let app = TestApp::new().await;
+    let owner = generate_address();
+    Project {
+        verified: true,
+        tags: &["defi", "nft"],
+        ..Project::new("Verified DeFi").bounty(100, "STRK", 10)
+    }
+    .insert(&app)
+    .await;
+    Project {
+        tags: &["defi"],
+        owner_address: owner.clone(),
+        ..Project::new("Unverified DeFi").bounty(100, "USDT", 40)
+    }
+    .insert(&app)
+    .await;
+    Project {
+        verified: true,
+        closed: true,
+        ..Project::new("Closed").bounty(100, "STRK", 5)
+    }
+    .insert(&app)
+    .await;
+    Project::new("No Bounty").insert(&app).await;
+
+    assert_eq!(names(&app, "?verified=true").await.len(), 2);
+    assert_eq!(
+        names(&app, "?has_active_bounty=true&sort=bounty_expiry").await,
+        ["Verified DeFi", "Unverified DeFi"]
+    );
+    assert_eq!(names(&app, "?currency=USDT").await, ["Unverified DeFi"]);
+    assert_eq!(
+        names(&app, "?tags=defi&sort=bounty_expiry").await,
+        ["Verified DeFi", "Unverified DeFi"]
+    );
+    assert_eq!(names(&app, "?tags=defi,nft").await, ["Verified DeFi"]);
+    assert_eq!(
+        names(&app, &format!("?owner_address={owner}")).await,
+        ["Unverified DeFi"]
+    );
+    assert_eq!(names(&app, "?closed=true").await, ["Closed"]);
+    assert_eq!(names(&app, "?closed=false").await.len(), 3);
+    let before = (Utc::now() + Duration::days(20)).format("%Y-%m-%dT%H:%M:%SZ");
+    assert_eq!(
+        names(
+            &app,
+            &format!("?expires_before={before}&sort=bounty_expiry")
+        )
+        .await,
+        ["Closed", "Verified DeFi"]
+    );
+}
+
+#[tokio::test]
+async fn pages_through_every_sort() {
+    let app = TestApp::new().await;
+    for (name, amount, days) in [("Alpha", 300, 30), ("Bravo", 100, 10), ("Charlie", 200, 20)] {
+        Project::new(name)
+            .bounty(amount, "STRK", days)
+            .insert(&app)
+            .await;
+    }
+    Project::new("Delta").insert(&app).await;
+
+    for (sort, expected) in [
+        ("created_at", ["Delta", "Charlie", "Bravo", "Alpha"]),
+        ("bounty_amount", ["Alpha", "Charlie", "Bravo", "Delta"]),
+        ("bounty_expiry", ["Bravo", "Charlie", "Alpha", "Delta"]),
+    ] {
+        let mut seen = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let query = match &cursor {
+                Some(cursor) => format!("?sort={sort}&limit=1&cursor={cursor}"),
+                None => format!("?sort={sort}&limit=1"),
+            };
+            let (status, body) = list(&app, &query).await;
+            assert_eq!(status, StatusCode::OK, "{body}");
+            for project in body["projects"].as_array().unwrap() {
+                seen.push(project["name"].as_str().unwrap().to_string());
+            }
+            match body["next_cursor"].as_str() {
+                Some(next) => cursor = Some(next.to_string()),
+                None => break,
+            }
+        }
+        assert_eq!(seen, expected, "sorted by {sort}");
+    }
+}
+
+#[tokio::test]
+async fn invalid_listing_parameters_are_rejected() {
+    let app = TestApp::new().await;
+    for query in [
+        "?currency=DOGE",
+        "?owner_address=0x123",
+        "?cursor=garbage",
+        "?limit=0",
+        "?sort=name",
+    ] {
+        let (status, _) = list(&app, query).await;
+        assert!(status.is_client_error(), "{query} returned {status}");
+    }
+}
@@ -1,11 +1,33 @@
+mod auth;
+mod bounty_expiry;
+mod chain_watcher;
 mod close_project;
 mod create_project;
 mod escrow;
 mod health_check;
 mod helpers;
+mod idempotency;
+mod ledger;
+mod list_projects;
 mod newsletter;
+mod pause_project;
+mod payouts;
+mod project_members;
+mod project_rewards;
+mod project_scope;
+mod project_verification;
 mod projects;
+mod reconciliation;
 mod report;
+mod report_lifecycle;
+mod roles;
+mod search;
+mod sessions;
 mod support_tickets;
+mod tags;
 mod transaction;
+mod transaction_history;
+mod transaction_lifecycle;
+mod update_project;
 mod validator;
+mod withdrawal_approvals;
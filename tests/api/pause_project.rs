@@ -0,0 +1,171 @@
+use crate::helpers::{TestApp, TestWallet, generate_address};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, request::Builder},
+};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+async fn create_project(app: &TestApp, owner: &TestWallet) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO projects (name, owner_address, contract_address, description, contact_info)
+        VALUES ('Vault Protocol', $1, $2, 'Yield vaults for stablecoins.', 'test@example.com')
+        RETURNING id
+        "#,
+    )
+    .bind(&owner.address)
+    .bind(generate_address())
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap()
+}
+
+async fn send(
+    app: &TestApp,
+    wallet: &TestWallet,
+    req: Builder,
+    payload: Value,
+) -> (StatusCode, Value) {
+    let req = app
+        .signed(wallet, req)
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let res = app.request(req).await;
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+async fn pause(app: &TestApp, wallet: &TestWallet, project_id: Uuid) -> (StatusCode, Value) {
+    send(
+        app,
+        wallet,
+        Request::post(format!("/projects/{project_id}/pause")),
+        Value::Null,
+    )
+    .await
+}
+
+async fn resume(app: &TestApp, wallet: &TestWallet, project_id: Uuid) -> (StatusCode, Value) {
+    send(
+        app,
+        wallet,
+        Request::post(format!("/projects/{project_id}/resume")),
+        Value::Null,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn paused_project_rejects_new_reports_until_resumed() {
+    let app = TestApp::new().await;
+    let (owner, researcher) = (app.wallet(), app.wallet());
+    app.grant_role(&researcher.address, "researcher").await;
+    let project_id = create_project(&app, &owner).await;
+    let report = json!({
+        "project_id": project_id,
+        "scope_asset_id": Uuid::now_v7(),
+        "title": "Re-entrancy in withdraw",
+        "body": "The withdraw function updates balances after the external call, allowing re-entrancy.",
+    });
+
+    let (status, project) = pause(&app, &owner, project_id).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(project["status"], "paused");
+    assert!(project["paused_at"].is_string());
+    assert_eq!(project["closed_at"], Value::Null);
+
+    let (status, body) = send(
+        &app,
+        &researcher,
+        Request::post("/report/submit"),
+        report.clone(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "project_paused");
+
+    let (status, page) = send(
+        &app,
+        &owner,
+        Request::get("/projects?status=paused"),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["projects"].as_array().unwrap().len(), 1);
+    assert_eq!(page["projects"][0]["status"], "paused");
+
+    let (status, project) = resume(&app, &owner, project_id).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(project["status"], "active");
+    assert_eq!(project["paused_at"], Value::Null);
+
+    // Submissions are open again and only fail on the made-up asset.
+    let (status, _) = send(&app, &researcher, Request::post("/report/submit"), report).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let (_, revisions) = send(
+        &app,
+        &owner,
+        Request::get(format!("/projects/{project_id}/revisions")),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(
+        revisions[0]["changes"],
+        json!({ "status": { "from": "paused", "to": "active" } })
+    );
+    assert_eq!(
+        revisions[1]["changes"],
+        json!({ "status": { "from": "active", "to": "paused" } })
+    );
+}
+
+#[tokio::test]
+async fn only_the_owner_pauses_an_open_project_once() {
+    let app = TestApp::new().await;
+    let (owner, stranger) = (app.wallet(), app.wallet());
+    let project_id = create_project(&app, &owner).await;
+
+    let (status, _) = pause(&app, &stranger, project_id).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, body) = resume(&app, &owner, project_id).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "project_not_paused");
+
+    assert_eq!(pause(&app, &owner, project_id).await.0, StatusCode::OK);
+    let (status, body) = pause(&app, &owner, project_id).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "project_paused");
+
+    // Closing a paused project works as before, and the project stays closed.
+    sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1)")
+        .bind(&owner.address)
+        .execute(&app.db.pool)
+        .await
+        .unwrap();
+    let (status, _) = send(
+        &app,
+        &owner,
+        Request::post("/closed_project"),
+        json!({ "project_id": project_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = resume(&app, &owner, project_id).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "project_closed");
+
+    let (_, project) = send(
+        &app,
+        &owner,
+        Request::get(format!("/projects/{project_id}")),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(project["status"], "closed");
+}
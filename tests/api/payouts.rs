@@ -0,0 +1,256 @@
+use crate::helpers::{NodeOutcome, TestApp, TestWallet};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use bigdecimal::BigDecimal;
+use fortichain_server::jobs::{MAX_PAYOUT_ATTEMPTS, PayoutSummary};
+use serde_json::{Value, json};
+use starknet::core::types::Felt;
+
+async fn send(
+    app: &TestApp,
+    wallet: &TestWallet,
+    req: axum::http::request::Builder,
+    payload: Value,
+) -> (StatusCode, Value) {
+    let req = app
+        .signed(wallet, req)
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let res = app.request(req).await;
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+/// A wallet holding 100 STRK, withdrawing 40 of them.
+async fn withdrawing_wallet(app: &TestApp) -> TestWallet {
+    let wallet = app.wallet();
+    let deposit_hash = format!("0x{}", "7b".repeat(31) + "c");
+    let deposit = json!({ "amount": "100", "currency": "STRK", "transaction_hash": deposit_hash });
+    let (status, _) = send(app, &wallet, Request::post("/deposit"), deposit).await;
+    assert_eq!(status, StatusCode::CREATED);
+    app.deposit_on_chain(&wallet.address, &deposit_hash, "STRK", 100)
+        .await;
+
+    let withdrawal = json!({ "amount": "40", "currency": "STRK" });
+    let (status, _) = send(app, &wallet, Request::post("/withdraw"), withdrawal).await;
+    assert_eq!(status, StatusCode::CREATED);
+    wallet
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct Withdrawal {
+    status: String,
+    transaction_hash: Option<String>,
+    payout_attempts: i32,
+    payout_error: Option<String>,
+}
+
+async fn withdrawal(app: &TestApp, wallet: &TestWallet) -> Withdrawal {
+    sqlx::query_as(
+        "SELECT status::text, transaction_hash, payout_attempts, payout_error \
+         FROM escrow_transactions WHERE wallet_address = $1 AND type = 'withdrawal'",
+    )
+    .bind(&wallet.address)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap()
+}
+
+/// Makes a withdrawal waiting out its backoff due again.
+async fn skip_backoff(app: &TestApp) {
+    sqlx::query(
+        "UPDATE escrow_transactions SET payout_next_attempt_at = NOW() - INTERVAL '1 second'",
+    )
+    .execute(&app.db.pool)
+    .await
+    .unwrap();
+}
+
+async fn ledger_is_balanced(app: &TestApp) -> bool {
+    let admin = app.wallet();
+    app.grant_role(&admin.address, "admin").await;
+    let req = app
+        .signed(&admin, Request::get("/admin/ledger/check"))
+        .await
+        .body(Body::empty())
+        .unwrap();
+    let body = to_bytes(app.request(req).await.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let check: Value = serde_json::from_slice(&body).unwrap();
+    check["balanced"] == true
+}
+
+#[tokio::test]
+async fn withdrawal_is_paid_out_and_settled() {
+    let app = TestApp::new().await;
+    let wallet = withdrawing_wallet(&app).await;
+    assert_eq!(withdrawal(&app, &wallet).await.transaction_hash, None);
+
+    assert_eq!(
+        app.pay_out().await,
+        PayoutSummary {
+            submitted: 1,
+            ..Default::default()
+        }
+    );
+
+    let accepted = app.payout_node.accepted.lock().unwrap().clone();
+    assert_eq!(accepted.len(), 1);
+    let recipient = Felt::from_hex(&wallet.address).unwrap();
+    let amount = Felt::from(40_000_000_000_000_000_000u128);
+    assert!(
+        accepted[0]
+            .transaction
+            .calldata
+            .ends_with(&[recipient, amount, Felt::ZERO])
+    );
+
+    let paid = withdrawal(&app, &wallet).await;
+    assert_eq!(paid.status, "pending");
+    assert_eq!(paid.payout_attempts, 1);
+    let hash = paid.transaction_hash.unwrap();
+    assert_eq!(hash, accepted[0].transaction_hash.to_fixed_hex_string());
+
+    // A submitted withdrawal is left to the confirmation job.
+    assert_eq!(app.pay_out().await.submitted, 0);
+    assert_eq!(app.payout_node.accepted.lock().unwrap().len(), 1);
+
+    assert_eq!(app.confirm(&hash).await.confirmed, 1);
+    assert_eq!(withdrawal(&app, &wallet).await.status, "completed");
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(60)
+    );
+    assert!(ledger_is_balanced(&app).await);
+}
+
+#[tokio::test]
+async fn rejected_payout_backs_off_and_is_retried() {
+    let app = TestApp::new().await;
+    let wallet = withdrawing_wallet(&app).await;
+
+    app.payout_node.answer(Some(NodeOutcome::Reject));
+    assert_eq!(app.pay_out().await.rejected, 1);
+    let rejected = withdrawal(&app, &wallet).await;
+    assert_eq!(rejected.status, "pending");
+    assert_eq!(rejected.transaction_hash, None);
+    assert_eq!(rejected.payout_attempts, 1);
+    assert!(rejected.payout_error.is_some());
+
+    // Not retried before its backoff ran out.
+    app.payout_node.answer(None);
+    assert_eq!(app.pay_out().await, PayoutSummary::default());
+
+    skip_backoff(&app).await;
+    assert_eq!(app.pay_out().await.submitted, 1);
+    let paid = withdrawal(&app, &wallet).await;
+    assert_eq!(paid.payout_attempts, 2);
+    assert_eq!(paid.payout_error, None);
+    assert!(paid.transaction_hash.is_some());
+}
+
+#[tokio::test]
+async fn payout_is_refunded_after_too_many_rejections() {
+    let app = TestApp::new().await;
+    let wallet = withdrawing_wallet(&app).await;
+    app.payout_node.answer(Some(NodeOutcome::Reject));
+
+    for _ in 1..MAX_PAYOUT_ATTEMPTS {
+        assert_eq!(app.pay_out().await.rejected, 1);
+        skip_backoff(&app).await;
+    }
+    assert_eq!(app.pay_out().await.failed, 1);
+
+    let failed = withdrawal(&app, &wallet).await;
+    assert_eq!(failed.status, "failed");
+    assert_eq!(failed.payout_attempts, MAX_PAYOUT_ATTEMPTS);
+    assert!(failed.payout_error.is_some());
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(100)
+    );
+    assert!(ledger_is_balanced(&app).await);
+
+    skip_backoff(&app).await;
+    assert_eq!(app.pay_out().await, PayoutSummary::default());
+}
+
+#[tokio::test]
+async fn payout_with_unknown_outcome_is_not_signed_again() {
+    let app = TestApp::new().await;
+    let wallet = withdrawing_wallet(&app).await;
+
+    app.payout_node.answer(Some(NodeOutcome::Lost));
+    assert_eq!(app.pay_out().await, PayoutSummary::default());
+    let hash = withdrawal(&app, &wallet).await.transaction_hash.unwrap();
+
+    app.payout_node.answer(None);
+    skip_backoff(&app).await;
+    assert_eq!(app.pay_out().await, PayoutSummary::default());
+    assert_eq!(app.payout_node.accepted.lock().unwrap().len(), 1);
+
+    assert_eq!(app.confirm(&hash).await.confirmed, 1);
+    assert_eq!(withdrawal(&app, &wallet).await.status, "completed");
+}
+
+#[tokio::test]
+async fn admin_can_pause_payouts() {
+    let app = TestApp::new().await;
+    let wallet = withdrawing_wallet(&app).await;
+    let admin = app.wallet();
+    app.grant_role(&admin.address, "admin").await;
+
+    let (status, _) = send(
+        &app,
+        &wallet,
+        Request::put("/admin/payouts"),
+        json!({ "paused": true }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = send(
+        &app,
+        &admin,
+        Request::put("/admin/payouts"),
+        json!({ "paused": true }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(&app, &admin, Request::get("/admin/payouts"), Value::Null).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["paused"], true);
+    assert_eq!(body["updated_by"], admin.address);
+    assert_eq!(body["queued"], 1);
+
+    assert_eq!(
+        app.pay_out().await,
+        PayoutSummary {
+            paused: true,
+            ..Default::default()
+        }
+    );
+    assert!(app.payout_node.accepted.lock().unwrap().is_empty());
+    assert_eq!(withdrawal(&app, &wallet).await.transaction_hash, None);
+
+    let (status, _) = send(
+        &app,
+        &admin,
+        Request::put("/admin/payouts"),
+        json!({ "paused": false }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(app.pay_out().await.submitted, 1);
+
+    let (_, body) = send(&app, &admin, Request::get("/admin/payouts"), Value::Null).await;
+    assert_eq!(body["paused"], false);
+    assert_eq!(body["queued"], 0);
+}
@@ -0,0 +1,382 @@
+use crate::helpers::{TestApp, TestWallet, generate_address};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, request::Builder},
+};
+use bigdecimal::BigDecimal;
+use fortichain_server::ledger::draw_bounty_funds;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+async fn create_project(app: &TestApp, owner: &TestWallet) -> (Uuid, String) {
+    let contract_address = generate_address();
+    let project_id = sqlx::query_scalar(
+        r#"
+        INSERT INTO projects (name, owner_address, contract_address, description, contact_info)
+        VALUES ('Vault Protocol', $1, $2, 'Yield vaults for stablecoins.', 'test@example.com')
+        RETURNING id
+        "#,
+    )
+    .bind(&owner.address)
+    .bind(&contract_address)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap();
+    (project_id, contract_address)
+}
+
+async fn send(
+    app: &TestApp,
+    wallet: &TestWallet,
+    req: Builder,
+    payload: Value,
+) -> (StatusCode, Value) {
+    let req = app
+        .signed(wallet, req)
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let res = app.request(req).await;
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+async fn invite(
+    app: &TestApp,
+    owner: &TestWallet,
+    project_id: Uuid,
+    member: &TestWallet,
+    role: &str,
+) -> (StatusCode, Value) {
+    send(
+        app,
+        owner,
+        Request::post(format!("/projects/{project_id}/members")),
+        json!({ "wallet_address": member.address, "role": role }),
+    )
+    .await
+}
+
+async fn accept(app: &TestApp, member: &TestWallet, project_id: Uuid) -> (StatusCode, Value) {
+    send(
+        app,
+        member,
+        Request::post(format!("/projects/{project_id}/members/accept")),
+        Value::Null,
+    )
+    .await
+}
+
+async fn join(
+    app: &TestApp,
+    owner: &TestWallet,
+    project_id: Uuid,
+    member: &TestWallet,
+    role: &str,
+) {
+    assert_eq!(
+        invite(app, owner, project_id, member, role).await.0,
+        StatusCode::CREATED
+    );
+    assert_eq!(accept(app, member, project_id).await.0, StatusCode::OK);
+}
+
+async fn allocate(
+    app: &TestApp,
+    wallet: &TestWallet,
+    contract_address: &str,
+    amount: u32,
+) -> StatusCode {
+    app.fund(&wallet.address, "STRK", BigDecimal::from(amount))
+        .await;
+    send(
+        app,
+        wallet,
+        Request::post("/allocate_bounty"),
+        json!({
+            "project_contract_address": contract_address,
+            "amount": amount.to_string(),
+            "currency": "STRK",
+            "bounty_expiry_date": chrono::Utc::now() + chrono::Duration::days(30),
+        }),
+    )
+    .await
+    .0
+}
+
+#[tokio::test]
+async fn owner_invites_and_removes_members() {
+    let app = TestApp::new().await;
+    let (owner, maintainer, viewer, stranger) =
+        (app.wallet(), app.wallet(), app.wallet(), app.wallet());
+    let (project_id, _) = create_project(&app, &owner).await;
+    let members_uri = format!("/projects/{project_id}/members");
+
+    let (status, member) = invite(&app, &owner, project_id, &maintainer, "maintainer").await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(member["role"], "maintainer");
+    assert_eq!(member["invited_by"], owner.address);
+    assert_eq!(member["accepted_at"], Value::Null);
+
+    let (status, body) = invite(&app, &owner, project_id, &maintainer, "viewer").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "already_member");
+    let (status, _) = invite(&app, &owner, project_id, &stranger, "owner").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = invite(&app, &maintainer, project_id, &stranger, "viewer").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // A pending invitation grants nothing yet.
+    let (status, _) = send(&app, &maintainer, Request::get(&members_uri), Value::Null).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, member) = accept(&app, &maintainer, project_id).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(member["accepted_at"].is_string());
+    let (status, body) = accept(&app, &maintainer, project_id).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "no_pending_invitation");
+
+    invite(&app, &owner, project_id, &viewer, "viewer").await;
+    let (status, members) = send(&app, &maintainer, Request::get(&members_uri), Value::Null).await;
+    assert_eq!(status, StatusCode::OK);
+    let members = members.as_array().unwrap();
+    assert_eq!(members.len(), 3);
+    assert_eq!(members[0]["wallet_address"], owner.address);
+    assert_eq!(members[0]["role"], "owner");
+    assert_eq!(members[1]["wallet_address"], maintainer.address);
+    assert_eq!(members[2]["wallet_address"], viewer.address);
+    assert_eq!(members[2]["accepted_at"], Value::Null);
+
+    // Members may leave; only the owner removes others.
+    let member_uri = format!("{members_uri}/{}", maintainer.address);
+    let (status, _) = send(&app, &viewer, Request::delete(&member_uri), Value::Null).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(
+        &app,
+        &viewer,
+        Request::delete(format!("{members_uri}/{}", viewer.address)),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send(&app, &owner, Request::delete(&member_uri), Value::Null).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send(&app, &owner, Request::delete(&member_uri), Value::Null).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, members) = send(&app, &owner, Request::get(&members_uri), Value::Null).await;
+    assert_eq!(members.as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn roles_grant_project_permissions() {
+    let app = TestApp::new().await;
+    let (owner, maintainer, viewer, researcher) =
+        (app.wallet(), app.wallet(), app.wallet(), app.wallet());
+    app.grant_role(&researcher.address, "researcher").await;
+    app.grant_role(&viewer.address, "researcher").await;
+    let (project_id, contract_address) = create_project(&app, &owner).await;
+    join(&app, &owner, project_id, &maintainer, "maintainer").await;
+    join(&app, &owner, project_id, &viewer, "viewer").await;
+
+    assert_eq!(
+        allocate(&app, &maintainer, &contract_address, 100).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        allocate(&app, &viewer, &contract_address, 100).await,
+        StatusCode::FORBIDDEN
+    );
+
+    let verify = Request::post(format!("/projects/{project_id}/verify"));
+    let repository = json!({ "repository_url": "https://github.com/vault/contracts" });
+    let (status, _) = send(&app, &viewer, verify, repository.clone()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let verify = Request::post(format!("/projects/{project_id}/verify"));
+    let (status, _) = send(&app, &maintainer, verify, repository).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Members cannot report on their own project.
+    let report = json!({
+        "project_id": project_id,
+        "scope_asset_id": Uuid::now_v7(),
+        "title": "Re-entrancy in withdraw",
+        "body": "The withdraw function updates balances after the external call, allowing re-entrancy.",
+    });
+    let (status, _) = send(&app, &viewer, Request::post("/report/submit"), report).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let report_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO research_report (title, project_id, body, reported_by)
+        VALUES ('Re-entrancy in withdraw', $1, $2, $3)
+        RETURNING id
+        "#,
+    )
+    .bind(project_id)
+    .bind("The withdraw function updates balances after the external call, allowing re-entrancy.")
+    .bind(&researcher.address)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap();
+    let report_uri = format!("/reports/{report_id}");
+    let (status, _) = send(&app, &viewer, Request::get(&report_uri), Value::Null).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, hits) = send(
+        &app,
+        &viewer,
+        Request::get("/reports/search?q=withdraw"),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(hits.as_array().unwrap().len(), 1);
+
+    let outsider = app.wallet();
+    let (status, _) = send(&app, &outsider, Request::get(&report_uri), Value::Null).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn ownership_moves_in_two_steps() {
+    let app = TestApp::new().await;
+    let (owner, successor, stranger) = (app.wallet(), app.wallet(), app.wallet());
+    let (project_id, _) = create_project(&app, &owner).await;
+    let transfer_uri = format!("/projects/{project_id}/ownership_transfer");
+    let accept_uri = format!("{transfer_uri}/accept");
+    let offer = json!({ "new_owner_address": successor.address });
+
+    let (status, _) = send(&app, &stranger, Request::post(&transfer_uri), offer.clone()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(
+        &app,
+        &owner,
+        Request::post(&transfer_uri),
+        json!({ "new_owner_address": owner.address }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let (status, transfer) = send(&app, &owner, Request::post(&transfer_uri), offer.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(transfer["pending_owner_address"], successor.address);
+
+    // The successor may decline, after which there is nothing to accept.
+    let (status, _) = send(
+        &app,
+        &successor,
+        Request::delete(&transfer_uri),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, body) = send(&app, &successor, Request::post(&accept_uri), Value::Null).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "no_pending_transfer");
+
+    send(&app, &owner, Request::post(&transfer_uri), offer).await;
+    let (status, _) = send(&app, &stranger, Request::post(&accept_uri), Value::Null).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, project) = send(&app, &successor, Request::post(&accept_uri), Value::Null).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(project["owner_address"], successor.address);
+
+    // The previous owner stays on as a maintainer.
+    let (status, members) = send(
+        &app,
+        &owner,
+        Request::get(format!("/projects/{project_id}/members")),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(members[0]["wallet_address"], successor.address);
+    assert_eq!(members[0]["role"], "owner");
+    assert_eq!(members[1]["wallet_address"], owner.address);
+    assert_eq!(members[1]["role"], "maintainer");
+
+    let (status, _) = send(
+        &app,
+        &owner,
+        Request::post(format!("/projects/{project_id}/members")),
+        json!({ "wallet_address": stranger.address, "role": "viewer" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (_, revisions) = send(
+        &app,
+        &owner,
+        Request::get(format!("/projects/{project_id}/revisions")),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(
+        revisions[0]["changes"]["owner_address"],
+        json!({ "from": owner.address, "to": successor.address })
+    );
+}
+
+#[tokio::test]
+async fn bounty_is_refunded_to_its_funders() {
+    let app = TestApp::new().await;
+    let (owner, maintainer, successor) = (app.wallet(), app.wallet(), app.wallet());
+    let (project_id, contract_address) = create_project(&app, &owner).await;
+    join(&app, &owner, project_id, &maintainer, "maintainer").await;
+    assert_eq!(
+        allocate(&app, &owner, &contract_address, 100).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        allocate(&app, &maintainer, &contract_address, 50).await,
+        StatusCode::OK
+    );
+
+    // A 30 STRK reward is paid from both shares in proportion, leaving 80 and 40.
+    let mut conn = app.db.pool.acquire().await.unwrap();
+    draw_bounty_funds(&mut conn, project_id, &BigDecimal::from(30))
+        .await
+        .unwrap();
+    sqlx::query("UPDATE projects SET bounty_amount = bounty_amount - 30 WHERE id = $1")
+        .bind(project_id)
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+
+    // A new owner does not inherit what the previous one put in.
+    let transfer_uri = format!("/projects/{project_id}/ownership_transfer");
+    let offer = json!({ "new_owner_address": successor.address });
+    send(&app, &owner, Request::post(&transfer_uri), offer).await;
+    let (status, _) = send(
+        &app,
+        &successor,
+        Request::post(format!("{transfer_uri}/accept")),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = send(
+        &app,
+        &successor,
+        Request::post("/closed_project"),
+        json!({ "project_id": project_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        app.balance(&owner.address, "STRK").await,
+        BigDecimal::from(80)
+    );
+    assert_eq!(
+        app.balance(&maintainer.address, "STRK").await,
+        BigDecimal::from(40)
+    );
+    assert_eq!(
+        app.balance(&successor.address, "STRK").await,
+        BigDecimal::from(0)
+    );
+}
@@ -0,0 +1,129 @@
+use crate::helpers::{TestApp, TestWallet, generate_address};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use bigdecimal::BigDecimal;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+async fn create_project(app: &TestApp, owner_address: &str) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO projects (name, description, contract_address, owner_address, contact_info)
+        VALUES ('Test Project', 'A project with rewards', $1, $2, 'test@example.com')
+        RETURNING id
+        "#,
+    )
+    .bind(generate_address())
+    .bind(owner_address)
+    .fetch_one(&app.db.pool)
+    .await
+    .expect("Failed to insert test project")
+}
+
+fn amount(value: &Value) -> BigDecimal {
+    value.as_str().unwrap().parse().unwrap()
+}
+
+async fn put_rewards(
+    app: &TestApp,
+    wallet: &TestWallet,
+    project_id: Uuid,
+    payload: Value,
+) -> StatusCode {
+    let req = app
+        .signed(
+            wallet,
+            Request::put(format!("/projects/{project_id}/rewards")),
+        )
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    app.request(req).await.status()
+}
+
+async fn get_rewards(app: &TestApp, project_id: Uuid) -> (StatusCode, Value) {
+    let req = Request::get(format!("/projects/{project_id}/rewards"))
+        .body(Body::empty())
+        .unwrap();
+    let res = app.request(req).await;
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+#[tokio::test]
+async fn owner_sets_and_updates_reward_matrix() {
+    let app = TestApp::new().await;
+    let owner = app.wallet();
+    let project_id = create_project(&app, &owner.address).await;
+
+    let (status, _) = get_rewards(&app, project_id).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let matrix = json!({ "low": "50", "medium": "200", "high": "500", "critical": "1000" });
+    assert_eq!(
+        put_rewards(&app, &owner, project_id, matrix).await,
+        StatusCode::OK
+    );
+    let matrix = json!({ "low": "0", "medium": "250.50", "high": "500", "critical": "1500" });
+    assert_eq!(
+        put_rewards(&app, &owner, project_id, matrix).await,
+        StatusCode::OK
+    );
+
+    let (status, rewards) = get_rewards(&app, project_id).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(amount(&rewards["low"]), BigDecimal::from(0));
+    assert_eq!(amount(&rewards["medium"]), "250.5".parse().unwrap());
+    assert_eq!(amount(&rewards["high"]), BigDecimal::from(500));
+    assert_eq!(amount(&rewards["critical"]), BigDecimal::from(1500));
+}
+
+#[tokio::test]
+async fn only_owner_sets_reward_matrix() {
+    let app = TestApp::new().await;
+    let owner = app.wallet();
+    let stranger = app.wallet();
+    let project_id = create_project(&app, &owner.address).await;
+    let matrix = json!({ "low": "50", "medium": "200", "high": "500", "critical": "1000" });
+
+    assert_eq!(
+        put_rewards(&app, &stranger, project_id, matrix.clone()).await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        put_rewards(&app, &owner, Uuid::now_v7(), matrix).await,
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn reward_matrix_rejects_negative_or_missing_amounts() {
+    let app = TestApp::new().await;
+    let owner = app.wallet();
+    let project_id = create_project(&app, &owner.address).await;
+
+    assert_eq!(
+        put_rewards(
+            &app,
+            &owner,
+            project_id,
+            json!({ "low": "-1", "medium": "200", "high": "500", "critical": "1000" }),
+        )
+        .await,
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        put_rewards(
+            &app,
+            &owner,
+            project_id,
+            json!({ "low": "50", "medium": "200", "high": "500" }),
+        )
+        .await,
+        StatusCode::UNPROCESSABLE_ENTITY
+    );
+}
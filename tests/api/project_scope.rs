@@ -0,0 +1,277 @@
+use crate::helpers::{TestApp, TestWallet, generate_address};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, request::Builder},
+};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+const REPORT_BODY: &str =
+    "The withdraw function updates balances after the external call, allowing re-entrancy.";
+
+async fn create_project(app: &TestApp, owner: &TestWallet) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO projects (name, owner_address, contract_address, description, contact_info)
+        VALUES ('Vault Protocol', $1, $2, 'Yield vaults for stablecoins.', 'test@example.com')
+        RETURNING id
+        "#,
+    )
+    .bind(&owner.address)
+    .bind(generate_address())
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap()
+}
+
+async fn send(
+    app: &TestApp,
+    wallet: Option<&TestWallet>,
+    req: Builder,
+    payload: Value,
+) -> (StatusCode, Value) {
+    let req = match wallet {
+        Some(wallet) => app.signed(wallet, req).await,
+        None => req,
+    }
+    .header("content-type", "application/json")
+    .body(Body::from(payload.to_string()))
+    .unwrap();
+    let res = app.request(req).await;
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+async fn set_scope(
+    app: &TestApp,
+    owner: &TestWallet,
+    project_id: Uuid,
+    scope: Value,
+) -> (StatusCode, Value) {
+    send(
+        app,
+        Some(owner),
+        Request::put(format!("/projects/{project_id}/scope")),
+        scope,
+    )
+    .await
+}
+
+async fn submit(
+    app: &TestApp,
+    researcher: &TestWallet,
+    project_id: Uuid,
+    scope_asset_id: &Value,
+) -> (StatusCode, Value) {
+    send(
+        app,
+        Some(researcher),
+        Request::post("/report/submit"),
+        json!({
+            "project_id": project_id,
+            "scope_asset_id": scope_asset_id,
+            "title": "Re-entrancy in withdraw",
+            "body": REPORT_BODY,
+        }),
+    )
+    .await
+}
+
+fn vault_scope(contract_address: &str) -> Value {
+    json!({
+        "rules": "No testing on mainnet. Report privately.",
+        "in_scope": [
+            {
+                "asset_type": "smart_contract",
+                "identifier": contract_address,
+                "description": "Vault",
+                "max_severity": "critical",
+            },
+            {
+                "asset_type": "web_url",
+                "identifier": "https://app.vault.example",
+                "max_severity": "medium",
+            },
+        ],
+        "out_of_scope": [
+            { "identifier": "Third-party price oracles" },
+            { "asset_type": "web_url", "identifier": "https://blog.vault.example" },
+        ],
+    })
+}
+
+#[tokio::test]
+async fn owner_publishes_versioned_scope() {
+    let app = TestApp::new().await;
+    let owner = app.wallet();
+    let project_id = create_project(&app, &owner).await;
+    let contract_address = generate_address();
+
+    let (status, detail) = send(
+        &app,
+        None,
+        Request::get(format!("/projects/{project_id}")),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(detail["scope"], Value::Null);
+
+    let (status, scope) = set_scope(&app, &owner, project_id, vault_scope(&contract_address)).await;
+    assert_eq!(status, StatusCode::CREATED, "{scope}");
+    assert_eq!(scope["version"], 1);
+    assert_eq!(scope["created_by"], owner.address);
+    assert_eq!(scope["in_scope"].as_array().unwrap().len(), 2);
+    assert_eq!(scope["out_of_scope"].as_array().unwrap().len(), 2);
+    assert_eq!(
+        scope["in_scope"][1]["identifier"],
+        "https://app.vault.example"
+    );
+    assert_eq!(scope["in_scope"][1]["max_severity"], "medium");
+
+    let (status, scope) = set_scope(
+        &app,
+        &owner,
+        project_id,
+        json!({ "in_scope": [{
+            "asset_type": "repository",
+            "identifier": "https://github.com/vault/contracts/tree/4f2a9c1/src",
+            "max_severity": "high",
+        }] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(scope["version"], 2);
+    assert_eq!(scope["rules"], Value::Null);
+    assert_eq!(scope["out_of_scope"], json!([]));
+
+    let (_, detail) = send(
+        &app,
+        None,
+        Request::get(format!("/projects/{project_id}")),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(detail["id"], project_id.to_string());
+    assert_eq!(detail["scope"]["version"], 2);
+    assert_eq!(detail["scope"]["in_scope"][0]["asset_type"], "repository");
+
+    let (status, first) = send(
+        &app,
+        None,
+        Request::get(format!("/projects/{project_id}/scope?version=1")),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(first["rules"], "No testing on mainnet. Report privately.");
+    assert_eq!(first["in_scope"].as_array().unwrap().len(), 2);
+
+    let (status, _) = send(
+        &app,
+        None,
+        Request::get(format!("/projects/{project_id}/scope?version=3")),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn scope_changes_are_validated_and_owner_only() {
+    let app = TestApp::new().await;
+    let (owner, stranger) = (app.wallet(), app.wallet());
+    let project_id = create_project(&app, &owner).await;
+
+    let (status, _) = set_scope(
+        &app,
+        &stranger,
+        project_id,
+        vault_scope(&generate_address()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = set_scope(&app, &owner, project_id, json!({ "in_scope": [] })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = set_scope(
+        &app,
+        &owner,
+        project_id,
+        json!({ "in_scope": [{
+            "asset_type": "smart_contract",
+            "identifier": "https://app.vault.example",
+            "max_severity": "high",
+        }] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = set_scope(
+        &app,
+        &owner,
+        project_id,
+        json!({ "in_scope": [{
+            "asset_type": "web_url",
+            "identifier": "app.vault.example",
+            "max_severity": "high",
+        }] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    sqlx::query("UPDATE projects SET closed_at = NOW() WHERE id = $1")
+        .bind(project_id)
+        .execute(&app.db.pool)
+        .await
+        .unwrap();
+    let (status, body) =
+        set_scope(&app, &owner, project_id, vault_scope(&generate_address())).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "project_closed");
+}
+
+#[tokio::test]
+async fn reports_must_target_an_asset_of_the_current_scope() {
+    let app = TestApp::new().await;
+    let (owner, researcher) = (app.wallet(), app.wallet());
+    app.grant_role(&researcher.address, "researcher").await;
+    let project_id = create_project(&app, &owner).await;
+
+    let (status, body) = submit(&app, &researcher, project_id, &json!(Uuid::now_v7())).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body["errors"]["scope_asset_id"].is_array());
+
+    let (_, first) = set_scope(&app, &owner, project_id, vault_scope(&generate_address())).await;
+    let (status, _) = submit(
+        &app,
+        &researcher,
+        project_id,
+        &first["out_of_scope"][0]["id"],
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let (status, report) = submit(&app, &researcher, project_id, &first["in_scope"][1]["id"]).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(report["scope_asset_id"], first["in_scope"][1]["id"]);
+
+    // Once the scope changes, its earlier assets no longer take new reports.
+    let (_, second) = set_scope(&app, &owner, project_id, vault_scope(&generate_address())).await;
+    let (status, _) = submit(&app, &researcher, project_id, &first["in_scope"][1]["id"]).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let (status, _) = submit(&app, &researcher, project_id, &second["in_scope"][1]["id"]).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let other_project = create_project(&app, &owner).await;
+    let (status, _) = submit(
+        &app,
+        &researcher,
+        other_project,
+        &second["in_scope"][1]["id"],
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
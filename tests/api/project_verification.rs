@@ -0,0 +1,386 @@
+use crate::helpers::{TestApp, TestWallet};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, request::Builder},
+};
+use fortichain_server::chain::UNIVERSAL_DEPLOYERS;
+use serde_json::{Value, json};
+use starknet::core::utils::get_selector_from_name;
+use uuid::Uuid;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{body_partial_json, method, path},
+};
+
+/// An app that fetches repositories from, and reads deployments through, `server`.
+async fn app(server: &MockServer) -> TestApp {
+    let uri = server.uri();
+    TestApp::with_configuration(|cfg| {
+        cfg.verification_repository_hosts = vec!["127.0.0.1".to_string()];
+        cfg.starknet_rpc_url = uri;
+    })
+    .await
+}
+
+async fn create_project(app: &TestApp, owner: &TestWallet, contract_address: &str) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO projects (name, owner_address, contract_address, description, contact_info)
+        VALUES ('Vault Protocol', $1, $2, 'Yield vaults for stablecoins.', 'test@example.com')
+        RETURNING id
+        "#,
+    )
+    .bind(&owner.address)
+    .bind(contract_address)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap()
+}
+
+async fn send(
+    app: &TestApp,
+    wallet: &TestWallet,
+    req: Builder,
+    payload: Value,
+) -> (StatusCode, Value) {
+    let req = app
+        .signed(wallet, req)
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let res = app.request(req).await;
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+async fn request_token(
+    app: &TestApp,
+    owner: &TestWallet,
+    project_id: Uuid,
+    repository_url: &str,
+) -> Value {
+    let (status, challenge) = send(
+        app,
+        owner,
+        Request::post(format!("/projects/{project_id}/verify")),
+        json!({ "repository_url": repository_url }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{challenge}");
+    challenge
+}
+
+async fn check(
+    app: &TestApp,
+    owner: &TestWallet,
+    project_id: Uuid,
+    proof: Value,
+) -> (StatusCode, Value) {
+    send(
+        app,
+        owner,
+        Request::post(format!("/projects/{project_id}/verify/check")),
+        proof,
+    )
+    .await
+}
+
+async fn is_verified(app: &TestApp, project_id: Uuid) -> bool {
+    sqlx::query_scalar("SELECT is_verified FROM projects WHERE id = $1")
+        .bind(project_id)
+        .fetch_one(&app.db.pool)
+        .await
+        .unwrap()
+}
+
+/// Serves `contents` as the `.fortichain` file of the repository at `/<repository>`.
+async fn commit_file(server: &MockServer, repository: &str, contents: &str) {
+    Mock::given(method("GET"))
+        .and(path(format!("/{repository}/raw/HEAD/.fortichain")))
+        .respond_with(ResponseTemplate::new(200).set_body_string(contents))
+        .mount(server)
+        .await;
+}
+
+/// Answers `starknet_getTransactionReceipt` for `transaction_hash` with a receipt holding
+/// `ContractDeployed(contract_address, deployer)` emitted by `emitter`.
+async fn deploy(
+    server: &MockServer,
+    transaction_hash: &str,
+    emitter: &str,
+    contract_address: &str,
+    deployer: &str,
+) {
+    let receipt = json!({
+        "type": "INVOKE",
+        "transaction_hash": transaction_hash,
+        "actual_fee": { "amount": "0x1", "unit": "FRI" },
+        "finality_status": "ACCEPTED_ON_L2",
+        "messages_sent": [],
+        "events": [{
+            "from_address": emitter,
+            "keys": [get_selector_from_name("ContractDeployed").unwrap().to_hex_string()],
+            "data": [contract_address, deployer, "0x0", "0x1234", "0x0", "0x5"],
+        }],
+        "execution_resources": { "l1_gas": 1, "l1_data_gas": 1, "l2_gas": 1 },
+        "execution_status": "SUCCEEDED",
+        "block_hash": "0x1",
+        "block_number": 1,
+    });
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "starknet_getTransactionReceipt",
+            "params": { "transaction_hash": transaction_hash },
+        })))
+        .respond_with(move |req: &wiremock::Request| {
+            let req: Value = req.body_json().unwrap();
+            ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": req["id"],
+                "result": receipt,
+            }))
+        })
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn owner_verifies_through_the_repository() {
+    let server = MockServer::start().await;
+    let app = app(&server).await;
+    let owner = app.wallet();
+    let project_id = create_project(&app, &owner, &app.wallet().address).await;
+    let repository_url = format!("{}/vault/contracts", server.uri());
+
+    let challenge = request_token(&app, &owner, project_id, &repository_url).await;
+    assert_eq!(challenge["file_path"], ".fortichain");
+    assert_eq!(challenge["repository_url"], repository_url);
+    let token = challenge["token"].as_str().unwrap();
+    assert!(!is_verified(&app, project_id).await);
+
+    let repository_file = json!({ "method": "repository_file" });
+    let (status, body) = check(&app, &owner, project_id, repository_file.clone()).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body["errors"]["verification"].is_array());
+    assert!(!is_verified(&app, project_id).await);
+
+    commit_file(&server, "vault/contracts", &format!("{token}\n")).await;
+    let (status, body) = check(&app, &owner, project_id, repository_file.clone()).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["status"], "verified");
+    assert_eq!(body["method"], "repository_file");
+    assert!(is_verified(&app, project_id).await);
+
+    let repository: Option<String> =
+        sqlx::query_scalar("SELECT repository_url FROM projects WHERE id = $1")
+            .bind(project_id)
+            .fetch_one(&app.db.pool)
+            .await
+            .unwrap();
+    assert_eq!(repository.as_deref(), Some(repository_url.as_str()));
+
+    let (status, body) = check(&app, &owner, project_id, repository_file).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "no_pending_verification");
+}
+
+#[tokio::test]
+async fn repository_must_hold_the_current_token() {
+    let server = MockServer::start().await;
+    let app = app(&server).await;
+    let owner = app.wallet();
+    let project_id = create_project(&app, &owner, &app.wallet().address).await;
+    let repository_url = format!("{}/vault/contracts", server.uri());
+
+    let first = request_token(&app, &owner, project_id, &repository_url).await;
+    commit_file(&server, "vault/contracts", first["token"].as_str().unwrap()).await;
+    // A new token replaces the one committed.
+    let second = request_token(&app, &owner, project_id, &repository_url).await;
+    assert_ne!(first["token"], second["token"]);
+
+    let (status, _) = check(
+        &app,
+        &owner,
+        project_id,
+        json!({ "method": "repository_file" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(!is_verified(&app, project_id).await);
+}
+
+#[tokio::test]
+async fn repositories_outside_the_allowed_hosts_are_not_fetched() {
+    let server = MockServer::start().await;
+    let app = app(&server).await;
+    let owner = app.wallet();
+    let project_id = create_project(&app, &owner, &app.wallet().address).await;
+
+    request_token(&app, &owner, project_id, "http://169.254.169.254/latest").await;
+    let (status, body) = check(
+        &app,
+        &owner,
+        project_id,
+        json!({ "method": "repository_file" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        body["errors"]["verification"][0],
+        "the repository could not be fetched"
+    );
+}
+
+#[tokio::test]
+async fn deployer_verifies_by_signing_the_token() {
+    let server = MockServer::start().await;
+    let app = app(&server).await;
+    let (owner, deployer, stranger) = (app.wallet(), app.wallet(), app.wallet());
+    // Any felt will do for the contract; the receipt below is what ties it to the deployer.
+    let contract_address = app.wallet().address;
+    let project_id = create_project(&app, &owner, &contract_address).await;
+    let udc = UNIVERSAL_DEPLOYERS[0].to_hex_string();
+
+    let deployment = format!("0x{}", "d".repeat(60));
+    deploy(
+        &server,
+        &deployment,
+        &udc,
+        &contract_address,
+        &deployer.address,
+    )
+    .await;
+    let forged = format!("0x{}", "f".repeat(60));
+    deploy(
+        &server,
+        &forged,
+        "0x4a11ce",
+        &contract_address,
+        &stranger.address,
+    )
+    .await;
+
+    let challenge = request_token(
+        &app,
+        &owner,
+        project_id,
+        "https://github.com/vault/contracts",
+    )
+    .await;
+    let typed_data = &challenge["typed_data"];
+    assert_eq!(typed_data["message"]["token"], challenge["token"]);
+
+    // Only the Universal Deployer's events count.
+    let (status, _) = check(
+        &app,
+        &owner,
+        project_id,
+        json!({
+            "method": "deployer_signature",
+            "deployment_transaction_hash": forged,
+            "signature": stranger.sign(typed_data),
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let (status, body) = check(
+        &app,
+        &owner,
+        project_id,
+        json!({
+            "method": "deployer_signature",
+            "deployment_transaction_hash": deployment,
+            "signature": stranger.sign(typed_data),
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        body["errors"]["verification"][0],
+        "the signature is not the deployer's"
+    );
+    assert!(!is_verified(&app, project_id).await);
+
+    let (status, body) = check(
+        &app,
+        &owner,
+        project_id,
+        json!({
+            "method": "deployer_signature",
+            "deployment_transaction_hash": deployment,
+            "signature": deployer.sign(typed_data),
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["method"], "deployer_signature");
+    assert!(is_verified(&app, project_id).await);
+}
+
+#[tokio::test]
+async fn admins_review_verifications_by_hand() {
+    let server = MockServer::start().await;
+    let app = app(&server).await;
+    let (owner, admin) = (app.wallet(), app.wallet());
+    app.grant_role(&admin.address, "admin").await;
+    let project_id = create_project(&app, &owner, &app.wallet().address).await;
+
+    request_token(&app, &owner, project_id, "https://github.com/vault/a").await;
+    let (status, pending) = send(
+        &app,
+        &admin,
+        Request::get("/admin/project_verifications"),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(pending.as_array().unwrap().len(), 1);
+    let id = pending[0]["id"].as_str().unwrap();
+
+    let (status, _) = send(
+        &app,
+        &owner,
+        Request::post(format!("/admin/project_verifications/{id}/approve")),
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(
+        &app,
+        &admin,
+        Request::post(format!("/admin/project_verifications/{id}/reject")),
+        json!({ "notes": "Repository belongs to another team" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "rejected");
+    assert_eq!(body["reviewed_by"], admin.address);
+    assert!(!is_verified(&app, project_id).await);
+
+    let (status, body) = send(
+        &app,
+        &admin,
+        Request::post(format!("/admin/project_verifications/{id}/approve")),
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "verification_not_pending");
+
+    let challenge = request_token(&app, &owner, project_id, "https://github.com/vault/b").await;
+    let id = challenge["verification_id"].as_str().unwrap();
+    let (status, body) = send(
+        &app,
+        &admin,
+        Request::post(format!("/admin/project_verifications/{id}/approve")),
+        json!({ "notes": "Confirmed with the team over a call" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["method"], "manual");
+    assert!(is_verified(&app, project_id).await);
+}
@@ -34,7 +34,7 @@ async fn test_get_project_success() {
     .await
     .expect("Failed to insert test project");
 
-    let req = Request::get(&format!("/projects/{}", project_id))
+    let req = Request::get(format!("/projects/{}", project_id))
         .body(Body::empty())
         .unwrap();
     let res = app.request(req).await;
@@ -58,7 +58,7 @@ async fn test_get_project_not_found() {
 
     // Test getting a non-existent project
     let non_existent_id = Uuid::now_v7();
-    let req = Request::get(&format!("/projects/{}", non_existent_id))
+    let req = Request::get(format!("/projects/{}", non_existent_id))
         .body(Body::empty())
         .unwrap();
     let res = app.request(req).await;
@@ -67,11 +67,12 @@ async fn test_get_project_not_found() {
 }
 
 #[tokio::test]
-async fn test_verify_project_success() {
+async fn test_verify_project_issues_token() {
     let app = TestApp::new().await;
 
     let project_id = Uuid::now_v7();
-    let owner_address = generate_address();
+    let owner = app.wallet();
+    let owner_address = owner.address.clone();
     let contract_address = generate_address();
     let contact_info = "test@example.com";
 
@@ -92,18 +93,27 @@ async fn test_verify_project_success() {
     .expect("Failed to insert test project");
 
     let verify_request = json!({
-        "repository_url": "https://github.com/test/repo",
-        "owner_address": owner_address
+        "repository_url": "https://github.com/test/repo"
     });
 
-    let req = Request::post(&format!("/projects/{}/verify", project_id))
+    let req = app
+        .signed(
+            &owner,
+            Request::post(format!("/projects/{}/verify", project_id)),
+        )
+        .await
         .header("content-type", "application/json")
         .body(Body::from(verify_request.to_string()))
         .unwrap();
     let res = app.request(req).await;
 
-    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.status(), StatusCode::CREATED);
+
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let challenge: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(challenge["file_path"], ".fortichain");
 
+    // The project stays unverified until the owner proves control of it.
     let updated_project =
         sqlx::query("SELECT is_verified, repository_url FROM projects WHERE id = $1")
             .bind(project_id)
@@ -114,25 +124,26 @@ async fn test_verify_project_success() {
     let is_verified: bool = updated_project.get("is_verified");
     let repository_url: Option<String> = updated_project.get("repository_url");
 
-    assert!(is_verified);
-    assert_eq!(
-        repository_url,
-        Some("https://github.com/test/repo".to_string())
-    );
+    assert!(!is_verified);
+    assert_eq!(repository_url, None);
 }
 
 #[tokio::test]
 async fn test_verify_project_invalid_repository_url() {
     let app = TestApp::new().await;
     let project_id = Uuid::now_v7();
-    let owner_address = generate_address();
+    let owner = app.wallet();
 
     let verify_request = json!({
-        "repository_url": "invalid-url",
-        "owner_address": owner_address
+        "repository_url": "invalid-url"
     });
 
-    let req = Request::post(&format!("/projects/{}/verify", project_id))
+    let req = app
+        .signed(
+            &owner,
+            Request::post(format!("/projects/{}/verify", project_id)),
+        )
+        .await
         .header("content-type", "application/json")
         .body(Body::from(verify_request.to_string()))
         .unwrap();
@@ -141,23 +152,21 @@ async fn test_verify_project_invalid_repository_url() {
 }
 
 #[tokio::test]
-async fn test_verify_project_invalid_owner_address() {
+async fn test_verify_project_without_signature() {
     let app = TestApp::new().await;
     let project_id = Uuid::now_v7();
 
-    // Test with invalid owner address
     let verify_request = json!({
-        "repository_url": "https://github.com/test/repo",
-        "owner_address": "invalid-address"
+        "repository_url": "https://github.com/test/repo"
     });
 
-    let req = Request::post(&format!("/projects/{}/verify", project_id))
+    let req = Request::post(format!("/projects/{}/verify", project_id))
         .header("content-type", "application/json")
         .body(Body::from(verify_request.to_string()))
         .unwrap();
     let res = app.request(req).await;
 
-    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
 }
 
 #[tokio::test]
@@ -166,7 +175,7 @@ async fn test_verify_project_not_owner() {
 
     let project_id = Uuid::now_v7();
     let owner_address = generate_address();
-    let different_address = generate_address();
+    let different_owner = app.wallet();
     let contract_address = generate_address();
     let contact_info = "test@example.com";
 
@@ -187,11 +196,15 @@ async fn test_verify_project_not_owner() {
     .expect("Failed to insert test project");
 
     let verify_request = json!({
-        "repository_url": "https://github.com/test/repo",
-        "owner_address": different_address
+        "repository_url": "https://github.com/test/repo"
     });
 
-    let req = Request::post(&format!("/projects/{}/verify", project_id))
+    let req = app
+        .signed(
+            &different_owner,
+            Request::post(format!("/projects/{}/verify", project_id)),
+        )
+        .await
         .header("content-type", "application/json")
         .body(Body::from(verify_request.to_string()))
         .unwrap();
@@ -204,7 +217,8 @@ async fn test_verify_project_already_verified() {
     let app = TestApp::new().await;
 
     let project_id = Uuid::now_v7();
-    let owner_address = generate_address();
+    let owner = app.wallet();
+    let owner_address = owner.address.clone();
     let contract_address = generate_address();
     let contact_info = "test@example.com";
 
@@ -228,11 +242,15 @@ async fn test_verify_project_already_verified() {
     .expect("Failed to insert test project");
 
     let verify_request = json!({
-        "repository_url": "https://github.com/test/repo",
-        "owner_address": owner_address
+        "repository_url": "https://github.com/test/repo"
     });
 
-    let req = Request::post(&format!("/projects/{}/verify", project_id))
+    let req = app
+        .signed(
+            &owner,
+            Request::post(format!("/projects/{}/verify", project_id)),
+        )
+        .await
         .header("content-type", "application/json")
         .body(Body::from(verify_request.to_string()))
         .unwrap();
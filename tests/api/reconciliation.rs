@@ -0,0 +1,198 @@
+use crate::helpers::{TestApp, TestWallet, generate_address};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use bigdecimal::BigDecimal;
+use fortichain_server::jobs::{MismatchKind, reconcile};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+async fn post(app: &TestApp, wallet: &TestWallet, uri: &str, payload: Value) -> StatusCode {
+    let req = app
+        .signed(wallet, Request::post(uri))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    app.request(req).await.status()
+}
+
+async fn report(app: &TestApp, wallet: &TestWallet) -> (StatusCode, Value) {
+    let req = app
+        .signed(wallet, Request::get("/admin/reconciliation"))
+        .await
+        .body(Body::empty())
+        .unwrap();
+    let res = app.request(req).await;
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+/// Deposits 500 STRK, withdraws 100, funds a 300 bounty and closes the project again, refunding
+/// what is left of it.
+async fn escrow_activity(app: &TestApp) -> (TestWallet, Uuid) {
+    let owner = app.wallet();
+    let deposit_hash = format!("0x{}", "7e".repeat(31) + "d");
+    assert_eq!(
+        post(
+            app,
+            &owner,
+            "/deposit",
+            json!({ "amount": "500", "currency": "STRK", "transaction_hash": deposit_hash }),
+        )
+        .await,
+        StatusCode::CREATED
+    );
+    app.deposit_on_chain(&owner.address, &deposit_hash, "STRK", 500)
+        .await;
+    assert_eq!(
+        post(
+            app,
+            &owner,
+            "/withdraw",
+            json!({ "amount": "100", "currency": "STRK" }),
+        )
+        .await,
+        StatusCode::CREATED
+    );
+
+    let contract_address = generate_address();
+    let project_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO projects (owner_address, contract_address, name, description, contact_info) \
+         VALUES ($1, $2, 'Test Project', 'A test project.', 'test@example.com') RETURNING id",
+    )
+    .bind(&owner.address)
+    .bind(&contract_address)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        post(
+            app,
+            &owner,
+            "/allocate_bounty",
+            json!({
+                "project_contract_address": contract_address,
+                "amount": "300",
+                "currency": "STRK",
+                "bounty_expiry_date": chrono::Utc::now() + chrono::Duration::days(30),
+            }),
+        )
+        .await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        post(
+            app,
+            &owner,
+            "/closed_project",
+            json!({ "project_id": project_id }),
+        )
+        .await,
+        StatusCode::OK
+    );
+
+    (owner, project_id)
+}
+
+#[tokio::test]
+async fn consistent_escrow_reconciles() {
+    let app = TestApp::new().await;
+    let (owner, _) = escrow_activity(&app).await;
+    assert_eq!(
+        app.balance(&owner.address, "STRK").await,
+        BigDecimal::from(400)
+    );
+
+    let refunds: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM escrow_transactions WHERE type = 'bounty_refund' AND amount = 300",
+    )
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap();
+    assert_eq!(refunds, 1);
+
+    let report = reconcile(&app.db.pool).await.unwrap();
+    assert!(report.mismatches.is_empty(), "{:?}", report.mismatches);
+    assert_eq!(report.wallets_checked, 1);
+    assert_eq!(report.projects_checked, 1);
+}
+
+#[tokio::test]
+async fn tampered_balances_are_reported() {
+    let app = TestApp::new().await;
+    let (owner, project_id) = escrow_activity(&app).await;
+    sqlx::query("UPDATE escrow_balances SET balance = balance + 50 WHERE wallet_address = $1")
+        .bind(&owner.address)
+        .execute(&app.db.pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE projects SET bounty_amount = 25 WHERE id = $1")
+        .bind(project_id)
+        .execute(&app.db.pool)
+        .await
+        .unwrap();
+
+    let report = reconcile(&app.db.pool).await.unwrap();
+    assert_eq!(report.mismatches.len(), 2);
+    let wallet = &report.mismatches[0];
+    assert_eq!(wallet.kind, MismatchKind::WalletBalance);
+    assert_eq!(
+        wallet.wallet_address.as_deref(),
+        Some(owner.address.as_str())
+    );
+    assert_eq!(wallet.currency.as_deref(), Some("STRK"));
+    assert_eq!(wallet.recorded, BigDecimal::from(450));
+    assert_eq!(wallet.expected, BigDecimal::from(400));
+    let project = &report.mismatches[1];
+    assert_eq!(project.kind, MismatchKind::ProjectBounty);
+    assert_eq!(project.project_id, Some(project_id));
+    assert_eq!(project.recorded, BigDecimal::from(25));
+    assert_eq!(project.expected, BigDecimal::from(0));
+
+    let stored: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM reconciliation_mismatches WHERE run_id = $1")
+            .bind(report.id)
+            .fetch_one(&app.db.pool)
+            .await
+            .unwrap();
+    assert_eq!(stored, 2);
+}
+
+#[tokio::test]
+async fn admins_read_the_latest_report() {
+    let app = TestApp::new().await;
+    let admin = app.wallet();
+    app.grant_role(&admin.address, "admin").await;
+
+    let (status, _) = report(&app, &admin).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (owner, _) = escrow_activity(&app).await;
+    reconcile(&app.db.pool).await.unwrap();
+    sqlx::query("UPDATE escrow_balances SET balance = 0 WHERE wallet_address = $1")
+        .bind(&owner.address)
+        .execute(&app.db.pool)
+        .await
+        .unwrap();
+    let latest = reconcile(&app.db.pool).await.unwrap();
+
+    let (status, body) = report(&app, &admin).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["id"], latest.id.to_string());
+    assert_eq!(body["mismatches"][0]["kind"], "wallet_balance");
+    assert_eq!(body["mismatches"][0]["wallet_address"], owner.address);
+    assert_eq!(
+        body["mismatches"][0]["recorded"]
+            .as_str()
+            .unwrap()
+            .parse::<BigDecimal>()
+            .unwrap(),
+        BigDecimal::from(0)
+    );
+
+    let (status, _) = report(&app, &owner).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
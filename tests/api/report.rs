@@ -30,7 +30,9 @@ async fn test_reject_report_success() {
     // Create a test report
     let report_id = Uuid::now_v7();
     let researcher_wallet = generate_address();
-    let validator_wallet = generate_address();
+    let validator = app.wallet();
+    let validator_wallet = validator.address.clone();
+    app.grant_role(&validator_wallet, "validator").await;
 
     sqlx::query(
         r#"
@@ -52,13 +54,12 @@ async fn test_reject_report_success() {
     let payload = json!({
         "report_id": report_id,
         "reason": "incomplete_information",
-        "validator_notes": "The report lacks sufficient technical details to assess the vulnerability.",
-        "validated_by": validator_wallet
+        "validator_notes": "The report lacks sufficient technical details to assess the vulnerability."
     });
 
-    let req = Request::builder()
-        .method("POST")
-        .uri("/report/reject")
+    let req = app
+        .signed(&validator, Request::post("/report/reject"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -106,18 +107,18 @@ async fn test_reject_report_success() {
 async fn test_reject_report_not_found() {
     let app = TestApp::new().await;
     let non_existent_report_id = Uuid::now_v7();
-    let validator_wallet = generate_address();
+    let validator = app.wallet();
+    app.grant_role(&validator.address, "validator").await;
 
     let payload = json!({
         "report_id": non_existent_report_id,
         "reason": "duplicate_report",
-        "validator_notes": "This report duplicates an existing finding.",
-        "validated_by": validator_wallet
+        "validator_notes": "This report duplicates an existing finding."
     });
 
-    let req = Request::builder()
-        .method("POST")
-        .uri("/report/reject")
+    let req = app
+        .signed(&validator, Request::post("/report/reject"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -155,7 +156,9 @@ async fn test_reject_report_already_rejected() {
     // Create an already rejected report
     let report_id = Uuid::now_v7();
     let researcher_wallet = generate_address();
-    let validator_wallet = generate_address();
+    let validator = app.wallet();
+    let validator_wallet = validator.address.clone();
+    app.grant_role(&validator_wallet, "validator").await;
 
     sqlx::query(
         r#"
@@ -179,13 +182,12 @@ async fn test_reject_report_already_rejected() {
     let payload = json!({
         "report_id": report_id,
         "reason": "out_of_scope",
-        "validator_notes": "Trying to reject again.",
-        "validated_by": validator_wallet
+        "validator_notes": "Trying to reject again."
     });
 
-    let req = Request::builder()
-        .method("POST")
-        .uri("/report/reject")
+    let req = app
+        .signed(&validator, Request::post("/report/reject"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -224,7 +226,9 @@ async fn test_reject_report_unauthorized_validator() {
     let report_id = Uuid::now_v7();
     let researcher_wallet = generate_address();
     let assigned_validator = generate_address();
-    let unauthorized_validator = generate_address();
+    let unauthorized_validator = app.wallet();
+    app.grant_role(&unauthorized_validator.address, "validator")
+        .await;
 
     sqlx::query(
         r#"
@@ -247,13 +251,12 @@ async fn test_reject_report_unauthorized_validator() {
     let payload = json!({
         "report_id": report_id,
         "reason": "already_known",
-        "validator_notes": "Unauthorized rejection attempt.",
-        "validated_by": unauthorized_validator
+        "validator_notes": "Unauthorized rejection attempt."
     });
 
-    let req = Request::builder()
-        .method("POST")
-        .uri("/report/reject")
+    let req = app
+        .signed(&unauthorized_validator, Request::post("/report/reject"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -291,7 +294,8 @@ async fn test_reject_report_invalid_reason() {
     // Create a test report
     let report_id = Uuid::now_v7();
     let researcher_wallet = generate_address();
-    let validator_wallet = generate_address();
+    let validator = app.wallet();
+    app.grant_role(&validator.address, "validator").await;
 
     sqlx::query(
         r#"
@@ -313,13 +317,36 @@ async fn test_reject_report_invalid_reason() {
     let payload = json!({
         "report_id": report_id,
         "reason": "invalid_reason",
-        "validator_notes": "Invalid reason test.",
-        "validated_by": validator_wallet
+        "validator_notes": "Invalid reason test."
     });
 
-    let req = Request::builder()
-        .method("POST")
-        .uri("/report/reject")
+    let req = app
+        .signed(&validator, Request::post("/report/reject"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+
+    let res = app.request(req).await;
+    let status = res.status();
+
+    // Unknown reasons no longer deserialize into `RejectionReason`.
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn test_reject_report_requires_validator_role() {
+    let app = TestApp::new().await;
+    let researcher = app.wallet();
+
+    let payload = json!({
+        "report_id": Uuid::now_v7(),
+        "reason": "duplicate_report"
+    });
+
+    let req = app
+        .signed(&researcher, Request::post("/report/reject"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -327,19 +354,18 @@ async fn test_reject_report_invalid_reason() {
     let res = app.request(req).await;
     let status = res.status();
 
-    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(status, StatusCode::FORBIDDEN);
 }
 
 #[tokio::test]
-async fn test_reject_report_invalid_validator_address() {
+async fn test_reject_report_without_signature() {
     let app = TestApp::new().await;
     let report_id = Uuid::now_v7();
 
     let payload = json!({
         "report_id": report_id,
         "reason": "duplicate_report",
-        "validator_notes": "Test with invalid address.",
-        "validated_by": "invalid_address"
+        "validator_notes": "Test without a wallet signature."
     });
 
     let req = Request::builder()
@@ -352,21 +378,24 @@ async fn test_reject_report_invalid_validator_address() {
     let res = app.request(req).await;
     let status = res.status();
 
-    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
 }
 
 #[tokio::test]
 async fn test_reject_report_missing_fields() {
     let app = TestApp::new().await;
 
+    let validator = app.wallet();
+    app.grant_role(&validator.address, "validator").await;
+
     let payload = json!({
         // Missing required fields
         "validator_notes": "Test missing fields."
     });
 
-    let req = Request::builder()
-        .method("POST")
-        .uri("/report/reject")
+    let req = app
+        .signed(&validator, Request::post("/report/reject"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -404,7 +433,9 @@ async fn test_reject_report_without_notes() {
     // Create a test report
     let report_id = Uuid::now_v7();
     let researcher_wallet = generate_address();
-    let validator_wallet = generate_address();
+    let validator = app.wallet();
+    let validator_wallet = validator.address.clone();
+    app.grant_role(&validator_wallet, "validator").await;
 
     sqlx::query(
         r#"
@@ -425,13 +456,12 @@ async fn test_reject_report_without_notes() {
     // Reject without validator notes
     let payload = json!({
         "report_id": report_id,
-        "reason": "out_of_scope",
-        "validated_by": validator_wallet
+        "reason": "out_of_scope"
     });
 
-    let req = Request::builder()
-        .method("POST")
-        .uri("/report/reject")
+    let req = app
+        .signed(&validator, Request::post("/report/reject"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -0,0 +1,792 @@
+use crate::helpers::{TestApp, TestWallet, generate_address};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use bigdecimal::BigDecimal;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+const REPORT_BODY: &str =
+    "The withdraw function updates balances after the external call, allowing re-entrancy.";
+
+/// A project with a 1000 STRK bounty paying 50/200/500/1000 for low/medium/high/critical findings,
+/// whose scope holds its contract.
+async fn create_project(app: &TestApp, owner_address: &str) -> Uuid {
+    let contract_address = generate_address();
+    let project_id = sqlx::query_scalar(
+        r#"
+        INSERT INTO projects (
+            name, description, contract_address, owner_address, contact_info,
+            bounty_amount, bounty_currency, bounty_expiry_date
+        )
+        VALUES (
+            'Test Project', 'A test project for reports', $1, $2, 'test@example.com',
+            1000, 'STRK', NOW() + INTERVAL '30 days'
+        )
+        RETURNING id
+        "#,
+    )
+    .bind(&contract_address)
+    .bind(owner_address)
+    .fetch_one(&app.db.pool)
+    .await
+    .expect("Failed to insert test project");
+
+    sqlx::query(
+        r#"
+        WITH scope AS (
+            INSERT INTO project_scopes (project_id, version, created_by)
+            VALUES ($1, 1, $2)
+            RETURNING project_id, version
+        )
+        INSERT INTO project_scope_assets (
+            project_id, version, in_scope, asset_type, identifier, max_severity
+        )
+        SELECT project_id, version, true, 'smart_contract', $3, 'critical' FROM scope
+        "#,
+    )
+    .bind(project_id)
+    .bind(owner_address)
+    .bind(&contract_address)
+    .execute(&app.db.pool)
+    .await
+    .expect("Failed to insert project scope");
+
+    sqlx::query(
+        r#"
+        INSERT INTO project_rewards (project_id, severity, amount)
+        VALUES ($1, 'low', 50), ($1, 'medium', 200), ($1, 'high', 500), ($1, 'critical', 1000)
+        "#,
+    )
+    .bind(project_id)
+    .execute(&app.db.pool)
+    .await
+    .expect("Failed to insert project rewards");
+
+    project_id
+}
+
+/// The in-scope contract of the project's current scope.
+async fn scope_asset(app: &TestApp, project_id: Uuid) -> Uuid {
+    sqlx::query_scalar(
+        "SELECT id FROM project_scope_assets WHERE project_id = $1 ORDER BY version DESC LIMIT 1",
+    )
+    .bind(project_id)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap()
+}
+
+fn amount(value: &Value) -> BigDecimal {
+    value.as_str().unwrap().parse().unwrap()
+}
+
+async fn post(
+    app: &TestApp,
+    wallet: &TestWallet,
+    uri: &str,
+    payload: Value,
+) -> (StatusCode, Value) {
+    let req = app
+        .signed(wallet, Request::post(uri))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    send(app, req).await
+}
+
+async fn get(app: &TestApp, wallet: &TestWallet, uri: &str) -> (StatusCode, Value) {
+    let req = app
+        .signed(wallet, Request::get(uri))
+        .await
+        .body(Body::empty())
+        .unwrap();
+    send(app, req).await
+}
+
+async fn send(app: &TestApp, req: Request<Body>) -> (StatusCode, Value) {
+    let res = app.request(req).await;
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+struct Fixture {
+    app: TestApp,
+    owner: TestWallet,
+    researcher: TestWallet,
+    validator: TestWallet,
+    project_id: Uuid,
+    scope_asset_id: Uuid,
+}
+
+async fn fixture() -> Fixture {
+    let app = TestApp::new().await;
+    let owner = app.wallet();
+    let researcher = app.wallet();
+    let validator = app.wallet();
+    app.grant_role(&researcher.address, "researcher").await;
+    app.grant_role(&validator.address, "validator").await;
+    let project_id = create_project(&app, &owner.address).await;
+    let scope_asset_id = scope_asset(&app, project_id).await;
+
+    Fixture {
+        app,
+        owner,
+        researcher,
+        validator,
+        project_id,
+        scope_asset_id,
+    }
+}
+
+async fn submit(f: &Fixture) -> Uuid {
+    let (status, report) = post(
+        &f.app,
+        &f.researcher,
+        "/report/submit",
+        json!({ "project_id": f.project_id, "scope_asset_id": f.scope_asset_id, "title": "Re-entrancy in withdraw", "body": REPORT_BODY }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    report["id"].as_str().unwrap().parse().unwrap()
+}
+
+async fn transition(f: &Fixture, wallet: &TestWallet, action: &str, payload: Value) -> StatusCode {
+    post(&f.app, wallet, &format!("/report/{action}"), payload)
+        .await
+        .0
+}
+
+#[tokio::test]
+async fn submit_report_success() {
+    let f = fixture().await;
+
+    let (status, report) = post(
+        &f.app,
+        &f.researcher,
+        "/report/submit",
+        json!({ "project_id": f.project_id, "scope_asset_id": f.scope_asset_id, "title": "Re-entrancy in withdraw", "body": REPORT_BODY }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(report["status"], "submitted");
+    assert_eq!(report["reported_by"], f.researcher.address);
+    assert_eq!(report["project_id"], f.project_id.to_string());
+}
+
+#[tokio::test]
+async fn submit_report_requires_researcher_role() {
+    let f = fixture().await;
+    let stranger = f.app.wallet();
+
+    let (status, _) = post(
+        &f.app,
+        &stranger,
+        "/report/submit",
+        json!({ "project_id": f.project_id, "scope_asset_id": f.scope_asset_id, "title": "Re-entrancy in withdraw", "body": REPORT_BODY }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn submit_report_rejects_unknown_closed_or_own_project() {
+    let f = fixture().await;
+
+    let (status, _) = post(
+        &f.app,
+        &f.researcher,
+        "/report/submit",
+        json!({ "project_id": Uuid::now_v7(), "scope_asset_id": f.scope_asset_id, "title": "Re-entrancy in withdraw", "body": REPORT_BODY }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let own_project = create_project(&f.app, &f.researcher.address).await;
+    let (status, _) = post(
+        &f.app,
+        &f.researcher,
+        "/report/submit",
+        json!({ "project_id": own_project, "scope_asset_id": f.scope_asset_id, "title": "Re-entrancy in withdraw", "body": REPORT_BODY }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    sqlx::query("UPDATE projects SET closed_at = NOW() WHERE id = $1")
+        .bind(f.project_id)
+        .execute(&f.app.db.pool)
+        .await
+        .unwrap();
+    let (status, body) = post(
+        &f.app,
+        &f.researcher,
+        "/report/submit",
+        json!({ "project_id": f.project_id, "scope_asset_id": f.scope_asset_id, "title": "Re-entrancy in withdraw", "body": REPORT_BODY }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "project_closed");
+}
+
+#[tokio::test]
+async fn submit_report_validates_body() {
+    let f = fixture().await;
+
+    let (status, _) = post(
+        &f.app,
+        &f.researcher,
+        "/report/submit",
+        json!({ "project_id": f.project_id, "scope_asset_id": f.scope_asset_id, "title": "Re-entrancy", "body": "Too short" }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn list_reports_returns_only_own_reports() {
+    let f = fixture().await;
+    let other = f.app.wallet();
+    f.app.grant_role(&other.address, "researcher").await;
+
+    let first = submit(&f).await;
+    let second = submit(&f).await;
+    post(
+        &f.app,
+        &other,
+        "/report/submit",
+        json!({ "project_id": f.project_id, "scope_asset_id": f.scope_asset_id, "title": "Someone else's report", "body": REPORT_BODY }),
+    )
+    .await;
+    transition(&f, &f.validator, "assign", json!({ "report_id": first })).await;
+
+    let (status, reports) = get(&f.app, &f.researcher, "/reports").await;
+    assert_eq!(status, StatusCode::OK);
+    let ids: Vec<&str> = reports
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids.len(), 2);
+    assert!(ids.contains(&first.to_string().as_str()));
+    assert!(ids.contains(&second.to_string().as_str()));
+
+    let (status, reports) = get(&f.app, &f.researcher, "/reports?status=assigned").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(reports.as_array().unwrap().len(), 1);
+    assert_eq!(reports[0]["id"], first.to_string());
+
+    let (status, _) = get(&f.app, &f.researcher, "/reports?status=bogus").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn get_report_visibility() {
+    let f = fixture().await;
+    let report_id = submit(&f).await;
+    let uri = format!("/reports/{report_id}");
+
+    for wallet in [&f.researcher, &f.owner, &f.validator] {
+        let (status, report) = get(&f.app, wallet, &uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(report["id"], report_id.to_string());
+    }
+
+    let stranger = f.app.wallet();
+    let (status, _) = get(&f.app, &stranger, &uri).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = get(
+        &f.app,
+        &f.researcher,
+        &format!("/reports/{}", Uuid::now_v7()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn report_full_lifecycle() {
+    let f = fixture().await;
+    let report_id = submit(&f).await;
+    let id = json!({ "report_id": report_id });
+
+    assert_eq!(
+        transition(&f, &f.validator, "assign", id.clone()).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        transition(&f, &f.validator, "review", id.clone()).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        transition(
+            &f,
+            &f.validator,
+            "request_info",
+            json!({ "report_id": report_id, "validator_notes": "Please include a proof of concept." }),
+        )
+        .await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        transition(
+            &f,
+            &f.researcher,
+            "provide_info",
+            json!({ "report_id": report_id, "researcher_response": "PoC attached in the repository." }),
+        )
+        .await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        transition(&f, &f.validator, "review", id.clone()).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        transition(
+            &f,
+            &f.validator,
+            "accept",
+            json!({ "report_id": report_id, "severity": "high" }),
+        )
+        .await,
+        StatusCode::OK
+    );
+
+    let (_, report) = get(&f.app, &f.researcher, &format!("/reports/{report_id}")).await;
+    assert_eq!(report["status"], "accepted");
+    assert_eq!(report["severity"], "high");
+    assert_eq!(amount(&report["allocated_reward"]), BigDecimal::from(500));
+    assert_eq!(report["validated_by"], f.validator.address);
+    assert_eq!(
+        report["researcher_response"],
+        "PoC attached in the repository."
+    );
+
+    assert_eq!(
+        transition(&f, &f.validator, "close", id).await,
+        StatusCode::OK
+    );
+    let (_, report) = get(&f.app, &f.researcher, &format!("/reports/{report_id}")).await;
+    assert_eq!(report["status"], "closed");
+}
+
+#[tokio::test]
+async fn report_transitions_enforce_predecessor_states() {
+    let f = fixture().await;
+    let report_id = submit(&f).await;
+    let id = json!({ "report_id": report_id });
+    let accept = json!({ "report_id": report_id, "severity": "low" });
+
+    // Nothing but assignment is possible from `submitted`.
+    assert_eq!(
+        transition(&f, &f.validator, "review", id.clone()).await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        transition(&f, &f.validator, "assign", id.clone()).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        transition(&f, &f.validator, "assign", id.clone()).await,
+        StatusCode::CONFLICT
+    );
+    assert_eq!(
+        transition(&f, &f.validator, "accept", accept.clone()).await,
+        StatusCode::CONFLICT
+    );
+    assert_eq!(
+        transition(&f, &f.validator, "close", id.clone()).await,
+        StatusCode::CONFLICT
+    );
+    assert_eq!(
+        transition(
+            &f,
+            &f.researcher,
+            "provide_info",
+            json!({ "report_id": report_id, "researcher_response": "Unprompted answer" }),
+        )
+        .await,
+        StatusCode::CONFLICT
+    );
+
+    assert_eq!(
+        transition(&f, &f.validator, "review", id.clone()).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        transition(&f, &f.validator, "review", id.clone()).await,
+        StatusCode::CONFLICT
+    );
+    assert_eq!(
+        transition(&f, &f.validator, "accept", accept).await,
+        StatusCode::OK
+    );
+
+    let (status, body) = post(&f.app, &f.validator, "/report/review", id).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "invalid_report_transition");
+    assert_eq!(body["message"], "cannot review a report that is accepted");
+}
+
+#[tokio::test]
+async fn report_transitions_require_assigned_validator() {
+    let f = fixture().await;
+    let other_validator = f.app.wallet();
+    f.app
+        .grant_role(&other_validator.address, "validator")
+        .await;
+    let report_id = submit(&f).await;
+    let id = json!({ "report_id": report_id });
+
+    assert_eq!(
+        transition(&f, &f.researcher, "assign", id.clone()).await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        transition(&f, &f.validator, "assign", id.clone()).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        transition(&f, &other_validator, "review", id.clone()).await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        transition(&f, &f.validator, "review", id).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        transition(
+            &f,
+            &f.validator,
+            "request_info",
+            json!({ "report_id": report_id, "validator_notes": "Which commit?" }),
+        )
+        .await,
+        StatusCode::OK
+    );
+
+    // Only the reporting researcher can answer.
+    assert_eq!(
+        transition(
+            &f,
+            &other_validator,
+            "provide_info",
+            json!({ "report_id": report_id, "researcher_response": "Not mine to answer" }),
+        )
+        .await,
+        StatusCode::FORBIDDEN
+    );
+}
+
+#[tokio::test]
+async fn rejected_report_can_be_closed() {
+    let f = fixture().await;
+    let report_id = submit(&f).await;
+    let id = json!({ "report_id": report_id });
+
+    assert_eq!(
+        transition(&f, &f.validator, "assign", id.clone()).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        transition(
+            &f,
+            &f.validator,
+            "reject",
+            json!({ "report_id": report_id, "reason": "out_of_scope" }),
+        )
+        .await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        transition(&f, &f.validator, "close", id.clone()).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        transition(&f, &f.validator, "close", id).await,
+        StatusCode::CONFLICT
+    );
+}
+
+#[tokio::test]
+async fn report_history_records_every_transition() {
+    let f = fixture().await;
+    let report_id = submit(&f).await;
+    let id = json!({ "report_id": report_id });
+
+    assert_eq!(
+        transition(&f, &f.validator, "assign", id.clone()).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        transition(&f, &f.validator, "review", id.clone()).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        transition(
+            &f,
+            &f.validator,
+            "request_info",
+            json!({ "report_id": report_id, "validator_notes": "Which commit?" }),
+        )
+        .await,
+        StatusCode::OK
+    );
+    // A rejected transition leaves no trace.
+    assert_eq!(
+        transition(&f, &f.validator, "close", id).await,
+        StatusCode::CONFLICT
+    );
+
+    let (status, history) = get(&f.app, &f.owner, &format!("/reports/{report_id}/history")).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let history = history.as_array().unwrap();
+    let steps: Vec<(&Value, &Value)> = history
+        .iter()
+        .map(|entry| (&entry["from_status"], &entry["to_status"]))
+        .collect();
+    assert_eq!(
+        steps,
+        vec![
+            (&Value::Null, &json!("submitted")),
+            (&json!("submitted"), &json!("assigned")),
+            (&json!("assigned"), &json!("in_review")),
+            (&json!("in_review"), &json!("info_requested")),
+        ]
+    );
+    assert_eq!(history[0]["changed_by"], f.researcher.address);
+    assert_eq!(history[1]["changed_by"], f.validator.address);
+    assert_eq!(history[3]["notes"], "Which commit?");
+
+    let outsider = f.app.wallet();
+    let (status, _) = get(&f.app, &outsider, &format!("/reports/{report_id}/history")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+/// Submits a report and walks it up to `in_review` with the fixture's validator.
+async fn in_review(f: &Fixture) -> Uuid {
+    let report_id = submit(f).await;
+    for action in ["assign", "review"] {
+        assert_eq!(
+            transition(f, &f.validator, action, json!({ "report_id": report_id })).await,
+            StatusCode::OK
+        );
+    }
+    report_id
+}
+
+async fn accept(f: &Fixture, report_id: Uuid, severity: &str) -> (StatusCode, Value) {
+    post(
+        &f.app,
+        &f.validator,
+        "/report/accept",
+        json!({ "report_id": report_id, "severity": severity }),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn accept_report_caps_reward_by_remaining_bounty() {
+    let f = fixture().await;
+
+    let first = in_review(&f).await;
+    let (status, report) = accept(&f, first, "high").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(amount(&report["allocated_reward"]), BigDecimal::from(500));
+
+    // Only 500 of the 1000 bounty is left for the critical finding.
+    let second = in_review(&f).await;
+    let (status, report) = accept(&f, second, "critical").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(amount(&report["allocated_reward"]), BigDecimal::from(500));
+
+    let third = in_review(&f).await;
+    let (status, body) = accept(&f, third, "low").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "bounty_exhausted");
+
+    // The failed acceptance left the report untouched.
+    let (_, report) = get(&f.app, &f.validator, &format!("/reports/{third}")).await;
+    assert_eq!(report["status"], "in_review");
+    assert_eq!(report["allocated_reward"], Value::Null);
+}
+
+#[tokio::test]
+async fn accept_report_fails_when_bounty_expired() {
+    let f = fixture().await;
+    let report_id = in_review(&f).await;
+
+    sqlx::query("UPDATE projects SET bounty_expiry_date = NOW() - INTERVAL '1 day' WHERE id = $1")
+        .bind(f.project_id)
+        .execute(&f.app.db.pool)
+        .await
+        .unwrap();
+
+    let (status, body) = accept(&f, report_id, "medium").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "bounty_expired");
+}
+
+#[tokio::test]
+async fn accept_report_requires_reward_for_severity() {
+    let f = fixture().await;
+    let report_id = in_review(&f).await;
+
+    sqlx::query("DELETE FROM project_rewards WHERE project_id = $1")
+        .bind(f.project_id)
+        .execute(&f.app.db.pool)
+        .await
+        .unwrap();
+
+    let (status, body) = accept(&f, report_id, "medium").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "reward_not_configured");
+}
+
+#[tokio::test]
+async fn accept_report_caps_severity_by_targeted_asset() {
+    let f = fixture().await;
+    let report_id = in_review(&f).await;
+
+    sqlx::query("UPDATE project_scope_assets SET max_severity = 'medium' WHERE id = $1")
+        .bind(f.scope_asset_id)
+        .execute(&f.app.db.pool)
+        .await
+        .unwrap();
+
+    let (status, body) = accept(&f, report_id, "high").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body["errors"]["severity"].is_array());
+
+    let (status, report) = accept(&f, report_id, "medium").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["scope_asset_id"], f.scope_asset_id.to_string());
+    assert_eq!(amount(&report["allocated_reward"]), BigDecimal::from(200));
+}
+
+#[tokio::test]
+async fn closing_accepted_report_disburses_reward() {
+    let f = fixture().await;
+    let report_id = in_review(&f).await;
+    let (status, _) = accept(&f, report_id, "high").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        transition(&f, &f.validator, "close", json!({ "report_id": report_id })).await,
+        StatusCode::OK
+    );
+
+    assert_eq!(
+        f.app.balance(&f.researcher.address, "STRK").await,
+        BigDecimal::from(500)
+    );
+
+    let bounty: BigDecimal = sqlx::query_scalar("SELECT bounty_amount FROM projects WHERE id = $1")
+        .bind(f.project_id)
+        .fetch_one(&f.app.db.pool)
+        .await
+        .unwrap();
+    assert_eq!(bounty, BigDecimal::from(500));
+
+    let (project_id, amount, currency): (Uuid, BigDecimal, String) = sqlx::query_as(
+        r#"
+        SELECT project_id, amount, currency
+        FROM escrow_transactions
+        WHERE wallet_address = $1 AND type = 'bounty_disbursement' AND status = 'completed'
+        "#,
+    )
+    .bind(&f.researcher.address)
+    .fetch_one(&f.app.db.pool)
+    .await
+    .unwrap();
+    assert_eq!(project_id, f.project_id);
+    assert_eq!(amount, BigDecimal::from(500));
+    assert_eq!(currency, "STRK");
+
+    // The researcher can take the reward out of escrow.
+    let (status, _) = post(
+        &f.app,
+        &f.researcher,
+        "/withdraw",
+        json!({
+            "amount": "500",
+            "currency": "STRK",
+            "transaction_hash": format!("0x{}", "a".repeat(63)),
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn project_with_accepted_report_cannot_be_closed() {
+    let f = fixture().await;
+    let report_id = in_review(&f).await;
+    let (status, _) = accept(&f, report_id, "high").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = post(
+        &f.app,
+        &f.owner,
+        "/closed_project",
+        json!({ "project_id": f.project_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "project_has_open_reports");
+
+    // The reward is still there for the researcher.
+    assert_eq!(
+        transition(&f, &f.validator, "close", json!({ "report_id": report_id })).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        f.app.balance(&f.researcher.address, "STRK").await,
+        BigDecimal::from(500)
+    );
+
+    let (status, _) = post(
+        &f.app,
+        &f.owner,
+        "/closed_project",
+        json!({ "project_id": f.project_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn closing_rejected_report_disburses_nothing() {
+    let f = fixture().await;
+    let report_id = in_review(&f).await;
+    assert_eq!(
+        transition(
+            &f,
+            &f.validator,
+            "reject",
+            json!({ "report_id": report_id, "reason": "already_known" }),
+        )
+        .await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        transition(&f, &f.validator, "close", json!({ "report_id": report_id })).await,
+        StatusCode::OK
+    );
+
+    let disbursements: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM escrow_transactions WHERE type = 'bounty_disbursement'",
+    )
+    .fetch_one(&f.app.db.pool)
+    .await
+    .unwrap();
+    assert_eq!(disbursements, 0);
+}
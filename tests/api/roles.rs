@@ -0,0 +1,225 @@
+use crate::helpers::{TestApp, TestWallet};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use serde_json::json;
+
+async fn change_role(
+    app: &TestApp,
+    admin: &TestWallet,
+    action: &str,
+    payload: serde_json::Value,
+) -> (StatusCode, serde_json::Value) {
+    let req = app
+        .signed(admin, Request::post(format!("/admin/roles/{action}")))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let res = app.request(req).await;
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+async fn wallet_roles(app: &TestApp, admin: &TestWallet, wallet: &str) -> serde_json::Value {
+    let req = app
+        .signed(admin, Request::get(format!("/admin/roles/{wallet}")))
+        .await
+        .body(Body::empty())
+        .unwrap();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn admin_grants_role() {
+    let app = TestApp::new().await;
+    let admin = app.wallet();
+    app.grant_role(&admin.address, "admin").await;
+    let researcher = app.wallet();
+
+    let (status, change) = change_role(
+        &app,
+        &admin,
+        "grant",
+        json!({
+            "wallet_address": researcher.address,
+            "role": "researcher",
+            "reason": "Passed onboarding"
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(change["action"], "granted");
+    assert_eq!(change["role"], "researcher");
+    assert_eq!(change["changed_by"], admin.address);
+
+    let roles = wallet_roles(&app, &admin, &researcher.address).await;
+    assert_eq!(roles["roles"], json!(["researcher"]));
+    assert_eq!(roles["changes"].as_array().unwrap().len(), 1);
+    assert_eq!(roles["changes"][0]["reason"], "Passed onboarding");
+}
+
+#[tokio::test]
+async fn grant_role_twice_conflicts() {
+    let app = TestApp::new().await;
+    let admin = app.wallet();
+    app.grant_role(&admin.address, "admin").await;
+    let agent = app.wallet();
+    app.grant_role(&agent.address, "support_agent").await;
+
+    let (status, _) = change_role(
+        &app,
+        &admin,
+        "grant",
+        json!({ "wallet_address": agent.address, "role": "support_agent" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn grant_user_role_is_rejected() {
+    let app = TestApp::new().await;
+    let admin = app.wallet();
+    app.grant_role(&admin.address, "admin").await;
+    let wallet = app.wallet();
+
+    let (status, _) = change_role(
+        &app,
+        &admin,
+        "grant",
+        json!({ "wallet_address": wallet.address, "role": "user" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = change_role(
+        &app,
+        &admin,
+        "grant",
+        json!({ "wallet_address": wallet.address, "role": "superuser" }),
+    )
+    .await;
+    assert!(status.is_client_error());
+}
+
+#[tokio::test]
+async fn non_admin_cannot_grant_roles() {
+    let app = TestApp::new().await;
+    let validator = app.wallet();
+    app.grant_role(&validator.address, "validator").await;
+
+    let (status, _) = change_role(
+        &app,
+        &validator,
+        "grant",
+        json!({ "wallet_address": validator.address, "role": "admin" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let req = Request::post("/admin/roles/grant")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({ "wallet_address": validator.address, "role": "admin" }).to_string(),
+        ))
+        .unwrap();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn admin_revokes_role() {
+    let app = TestApp::new().await;
+    let admin = app.wallet();
+    app.grant_role(&admin.address, "admin").await;
+    let agent = app.wallet();
+    app.grant_role(&agent.address, "support_agent").await;
+
+    let (status, change) = change_role(
+        &app,
+        &admin,
+        "revoke",
+        json!({ "wallet_address": agent.address, "role": "support_agent" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(change["action"], "revoked");
+
+    let roles = wallet_roles(&app, &admin, &agent.address).await;
+    assert_eq!(roles["roles"], json!([]));
+    assert_eq!(roles["changes"][0]["action"], "revoked");
+
+    // The wallet no longer passes the support agent guard.
+    let req = app
+        .signed(&agent, Request::post("/assign_ticket"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({ "ticket_id": uuid::Uuid::now_v7() }).to_string(),
+        ))
+        .unwrap();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn revoke_missing_role_is_not_found() {
+    let app = TestApp::new().await;
+    let admin = app.wallet();
+    app.grant_role(&admin.address, "admin").await;
+    let wallet = app.wallet();
+
+    let (status, _) = change_role(
+        &app,
+        &admin,
+        "revoke",
+        json!({ "wallet_address": wallet.address, "role": "validator" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn admin_cannot_revoke_own_admin_role() {
+    let app = TestApp::new().await;
+    let admin = app.wallet();
+    app.grant_role(&admin.address, "admin").await;
+
+    let (status, _) = change_role(
+        &app,
+        &admin,
+        "revoke",
+        json!({ "wallet_address": admin.address, "role": "admin" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn admin_passes_every_role_guard() {
+    let app = TestApp::new().await;
+    let admin = app.wallet();
+    app.grant_role(&admin.address, "admin").await;
+
+    // Reaching the handler's own not-found check proves the validator guard let the admin through.
+    let req = app
+        .signed(&admin, Request::post("/report/reject"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({
+                "report_id": uuid::Uuid::now_v7(),
+                "reason": "duplicate_report"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,236 @@
+use crate::helpers::{TestApp, TestWallet, generate_address};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+async fn project(app: &TestApp, owner: &str, name: &str, description: &str) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO projects (name, owner_address, contract_address, description, contact_info)
+        VALUES ($1, $2, $3, $4, 'test@example.com')
+        RETURNING id
+        "#,
+    )
+    .bind(name)
+    .bind(owner)
+    .bind(generate_address())
+    .bind(description)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap()
+}
+
+async fn report(app: &TestApp, project_id: Uuid, reported_by: &str, title: &str) -> Uuid {
+    let body = format!("{title}. Steps to reproduce follow, with a proof of concept attached.");
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO research_report (title, project_id, body, reported_by)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id
+        "#,
+    )
+    .bind(title)
+    .bind(project_id)
+    .bind(body)
+    .bind(reported_by)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap()
+}
+
+async fn get(app: &TestApp, uri: &str, wallet: Option<&TestWallet>) -> (StatusCode, Value) {
+    let req = match wallet {
+        Some(wallet) => app.signed(wallet, Request::get(uri)).await,
+        None => Request::get(uri),
+    };
+    let res = app.request(req.body(Body::empty()).unwrap()).await;
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn projects_are_searched_by_name_and_description() {
+    let app = TestApp::new().await;
+    let owner = generate_address();
+    let lending = project(
+        &app,
+        &owner,
+        "Lending Protocol",
+        "Overcollateralized loans with liquidation auctions.",
+    )
+    .await;
+    let bridge = project(
+        &app,
+        &owner,
+        "Token Bridge",
+        "Moves tokens between chains. Liquidation is not involved.",
+    )
+    .await;
+    project(&app, &owner, "Name Service", "Human readable wallet names.").await;
+
+    let (status, body) = get(&app, "/projects?q=liquidation", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let projects = body["projects"].as_array().unwrap();
+    assert_eq!(projects.len(), 2);
+    assert!(projects[0]["rank"].as_f64().unwrap() > 0.0);
+    assert!(
+        projects
+            .iter()
+            .all(|project| project["snippet"].as_str().unwrap().contains("<mark>"))
+    );
+
+    let (_, body) = get(&app, "/projects?q=lending%20OR%20bridge", None).await;
+    assert_eq!(body["projects"].as_array().unwrap().len(), 2);
+    let (_, body) = get(&app, "/projects?q=loans", None).await;
+    assert_eq!(body["projects"][0]["id"], lending.to_string());
+
+    // Names weigh more than descriptions.
+    let engine = project(&app, &owner, "Liquidation Engine", "Keeps markets solvent.").await;
+    let (_, body) = get(&app, "/projects?q=liquidation", None).await;
+    assert_eq!(body["projects"].as_array().unwrap().len(), 3);
+    assert_eq!(body["projects"][0]["id"], engine.to_string());
+
+    // Updates are indexed too.
+    sqlx::query(
+        "UPDATE projects SET description = 'Undercollateralized flash loans.' WHERE id = $1",
+    )
+    .bind(bridge)
+    .execute(&app.db.pool)
+    .await
+    .unwrap();
+    let (_, body) = get(&app, "/projects?q=flash", None).await;
+    assert_eq!(body["projects"][0]["id"], bridge.to_string());
+
+    // Without a query there is nothing to rank by.
+    let (_, body) = get(&app, "/projects", None).await;
+    assert!(body["projects"][0].get("rank").is_none());
+    let (status, _) = get(&app, "/projects?sort=relevance", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn search_pages_by_relevance() {
+    let app = TestApp::new().await;
+    let owner = generate_address();
+    for name in [
+        "Oracle Oracle Oracle",
+        "Oracle Feeds",
+        "Price Oracle Oracle",
+    ] {
+        project(&app, &owner, name, "Price data for lending markets.").await;
+    }
+
+    let (_, body) = get(&app, "/projects?q=oracle", None).await;
+    let ranked: Vec<Value> = body["projects"].as_array().unwrap().clone();
+
+    let mut paged = Vec::new();
+    let mut uri = "/projects?q=oracle&limit=1".to_string();
+    loop {
+        let (status, body) = get(&app, &uri, None).await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        paged.extend(body["projects"].as_array().unwrap().iter().cloned());
+        match body["next_cursor"].as_str() {
+            Some(cursor) => uri = format!("/projects?q=oracle&limit=1&cursor={cursor}"),
+            None => break,
+        }
+    }
+    assert_eq!(paged.len(), 3);
+    assert_eq!(
+        paged.iter().map(|p| &p["id"]).collect::<Vec<_>>(),
+        ranked.iter().map(|p| &p["id"]).collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+async fn reports_are_searched_within_what_the_caller_may_view() {
+    let app = TestApp::new().await;
+    let (owner, researcher, other) = (app.wallet(), app.wallet(), app.wallet());
+    let project_id = project(&app, &owner.address, "Vault", "Yield vault strategies.").await;
+    let other_project = project(&app, &generate_address(), "Dex", "Swaps and pools.").await;
+    let mine = report(
+        &app,
+        project_id,
+        &researcher.address,
+        "Reentrancy in withdraw",
+    )
+    .await;
+    let elsewhere = report(
+        &app,
+        other_project,
+        &generate_address(),
+        "Reentrancy in swap callback",
+    )
+    .await;
+
+    let search = "/reports/search?q=reentrancy";
+    let (status, body) = get(&app, search, Some(&researcher)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().len(), 1);
+    assert_eq!(body[0]["id"], mine.to_string());
+    assert!(body[0]["snippet"].as_str().unwrap().contains("<mark>"));
+    assert!(body[0]["rank"].as_f64().unwrap() > 0.0);
+
+    let (_, body) = get(&app, search, Some(&owner)).await;
+    assert_eq!(body.as_array().unwrap().len(), 1);
+    assert_eq!(body[0]["id"], mine.to_string());
+
+    let (_, body) = get(&app, search, Some(&other)).await;
+    assert!(body.as_array().unwrap().is_empty());
+
+    let validator = app.wallet();
+    app.grant_role(&validator.address, "validator").await;
+    let (_, body) = get(&app, search, Some(&validator)).await;
+    let found: Vec<&Value> = body.as_array().unwrap().iter().map(|r| &r["id"]).collect();
+    assert_eq!(found.len(), 2);
+    assert!(found.contains(&&Value::from(elsewhere.to_string())));
+
+    let (status, _) = get(&app, search, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = get(&app, "/reports/search?q=", Some(&researcher)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn snippets_escape_the_searched_text() {
+    let app = TestApp::new().await;
+    let (owner, researcher) = (app.wallet(), app.wallet());
+    let project_id = project(
+        &app,
+        &owner.address,
+        "Vault",
+        "Vault <script>alert('xss')</script> strategies <img src=x onerror=alert(1)>",
+    )
+    .await;
+    report(
+        &app,
+        project_id,
+        &researcher.address,
+        "Reentrancy <script>alert(\"xss\")</script> & more",
+    )
+    .await;
+
+    let (status, body) = get(&app, "/projects?q=vault", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let snippet = body["projects"][0]["snippet"].as_str().unwrap();
+    assert!(snippet.contains("<mark>"));
+    assert!(snippet.contains("&lt;script&gt;alert(&#39;xss&#39;)&lt;/script&gt;"));
+    assert!(!snippet.contains("<img"));
+    assert_eq!(
+        snippet.matches('<').count(),
+        snippet.matches("<mark>").count() * 2
+    );
+
+    let (status, body) = get(&app, "/reports/search?q=reentrancy", Some(&researcher)).await;
+    assert_eq!(status, StatusCode::OK);
+    let snippet = body[0]["snippet"].as_str().unwrap();
+    assert!(snippet.contains("<mark>Reentrancy</mark>"));
+    assert!(snippet.contains("&lt;script&gt;alert(&quot;xss&quot;)&lt;/script&gt; &amp; more"));
+    assert_eq!(
+        snippet.matches('<').count(),
+        snippet.matches("<mark>").count() * 2
+    );
+}
@@ -0,0 +1,253 @@
+use crate::helpers::TestApp;
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, header::WWW_AUTHENTICATE},
+};
+use serde_json::json;
+
+async fn insert_user(app: &TestApp, wallet: &str) {
+    sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
+        .bind(wallet)
+        .execute(&app.db.pool)
+        .await
+        .expect("Failed to insert user");
+}
+
+async fn open_ticket(app: &TestApp, access_token: &str) -> StatusCode {
+    let req = Request::post("/open_ticket")
+        .header("content-type", "application/json")
+        .header("authorization", format!("Token {access_token}"))
+        .body(Body::from(
+            json!({
+                "subject": "Valid Subject",
+                "message": "This is a valid message with enough length."
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    app.request(req).await.status()
+}
+
+async fn refresh(app: &TestApp, refresh_token: &str) -> (StatusCode, serde_json::Value) {
+    let req = Request::post("/auth/refresh")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({ "refresh_token": refresh_token }).to_string(),
+        ))
+        .unwrap();
+    let res = app.request(req).await;
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+#[tokio::test]
+async fn login_issues_tokens() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+
+    let session = app.login(&wallet).await;
+
+    assert_eq!(session["wallet_address"], wallet.address);
+    assert_eq!(session["access_token"].as_str().unwrap().len(), 64);
+    assert_eq!(session["refresh_token"].as_str().unwrap().len(), 64);
+    assert_ne!(session["access_token"], session["refresh_token"]);
+}
+
+#[tokio::test]
+async fn login_requires_signature() {
+    let app = TestApp::new().await;
+
+    let req = Request::post("/auth/login").body(Body::empty()).unwrap();
+    let res = app.request(req).await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn login_rejects_access_token() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    let session = app.login(&wallet).await;
+
+    let req = Request::post("/auth/login")
+        .header(
+            "authorization",
+            format!("Token {}", session["access_token"].as_str().unwrap()),
+        )
+        .body(Body::empty())
+        .unwrap();
+    let res = app.request(req).await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn access_token_authenticates_requests() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    insert_user(&app, &wallet.address).await;
+    let session = app.login(&wallet).await;
+    let access_token = session["access_token"].as_str().unwrap();
+
+    assert_eq!(open_ticket(&app, access_token).await, StatusCode::CREATED);
+    // Unlike a signature, the token can be reused until it expires.
+    assert_eq!(open_ticket(&app, access_token).await, StatusCode::CREATED);
+
+    let opened_by: String = sqlx::query_scalar("SELECT opened_by FROM request_ticket LIMIT 1")
+        .fetch_one(&app.db.pool)
+        .await
+        .unwrap();
+    assert_eq!(opened_by, wallet.address);
+}
+
+#[tokio::test]
+async fn unknown_access_token_is_unauthorized() {
+    let app = TestApp::new().await;
+
+    let req = Request::get("/health_check")
+        .header("authorization", format!("Token {}", "a".repeat(64)))
+        .body(Body::empty())
+        .unwrap();
+    let res = app.request(req).await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(res.headers()[WWW_AUTHENTICATE], "Token");
+}
+
+#[tokio::test]
+async fn expired_access_token_is_unauthorized() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    insert_user(&app, &wallet.address).await;
+    let session = app.login(&wallet).await;
+
+    sqlx::query("UPDATE sessions SET access_token_expires_at = NOW() - INTERVAL '1 minute'")
+        .execute(&app.db.pool)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        open_ticket(&app, session["access_token"].as_str().unwrap()).await,
+        StatusCode::UNAUTHORIZED
+    );
+}
+
+#[tokio::test]
+async fn refresh_rotates_tokens() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    insert_user(&app, &wallet.address).await;
+    let session = app.login(&wallet).await;
+    let old_access = session["access_token"].as_str().unwrap();
+    let old_refresh = session["refresh_token"].as_str().unwrap();
+
+    let (status, refreshed) = refresh(&app, old_refresh).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(refreshed["session_id"], session["session_id"]);
+    assert_ne!(refreshed["access_token"], session["access_token"]);
+    assert_ne!(refreshed["refresh_token"], session["refresh_token"]);
+
+    // The previous pair is no longer usable.
+    assert_eq!(
+        open_ticket(&app, old_access).await,
+        StatusCode::UNAUTHORIZED
+    );
+    let (status, _) = refresh(&app, old_refresh).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    assert_eq!(
+        open_ticket(&app, refreshed["access_token"].as_str().unwrap()).await,
+        StatusCode::CREATED
+    );
+}
+
+#[tokio::test]
+async fn expired_refresh_token_is_unauthorized() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    let session = app.login(&wallet).await;
+
+    sqlx::query("UPDATE sessions SET refresh_token_expires_at = NOW() - INTERVAL '1 minute'")
+        .execute(&app.db.pool)
+        .await
+        .unwrap();
+
+    let (status, _) = refresh(&app, session["refresh_token"].as_str().unwrap()).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn logout_revokes_session() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    insert_user(&app, &wallet.address).await;
+    let session = app.login(&wallet).await;
+    let other = app.login(&wallet).await;
+    let access_token = session["access_token"].as_str().unwrap();
+
+    let req = Request::post("/auth/logout")
+        .header("authorization", format!("Token {access_token}"))
+        .body(Body::empty())
+        .unwrap();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    assert_eq!(
+        open_ticket(&app, access_token).await,
+        StatusCode::UNAUTHORIZED
+    );
+    let (status, _) = refresh(&app, session["refresh_token"].as_str().unwrap()).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Other sessions of the same wallet are unaffected.
+    assert_eq!(
+        open_ticket(&app, other["access_token"].as_str().unwrap()).await,
+        StatusCode::CREATED
+    );
+}
+
+#[tokio::test]
+async fn logout_requires_session() {
+    let app = TestApp::new().await;
+
+    let req = Request::post("/auth/logout").body(Body::empty()).unwrap();
+    let res = app.request(req).await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn logout_all_revokes_every_session() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    let bystander = app.wallet();
+    insert_user(&app, &bystander.address).await;
+    let first = app.login(&wallet).await;
+    let second = app.login(&wallet).await;
+    let unrelated = app.login(&bystander).await;
+
+    let req = Request::post("/auth/logout_all")
+        .header(
+            "authorization",
+            format!("Token {}", first["access_token"].as_str().unwrap()),
+        )
+        .body(Body::empty())
+        .unwrap();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    for session in [&first, &second] {
+        assert_eq!(
+            open_ticket(&app, session["access_token"].as_str().unwrap()).await,
+            StatusCode::UNAUTHORIZED
+        );
+        let (status, _) = refresh(&app, session["refresh_token"].as_str().unwrap()).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    assert_eq!(
+        open_ticket(&app, unrelated["access_token"].as_str().unwrap()).await,
+        StatusCode::CREATED
+    );
+}
@@ -16,19 +16,20 @@ async fn open_ticket_happy_path() {
     let app = TestApp::new().await;
     let db = &app.db;
     // Insert a user into escrow_users
-    let wallet = generate_address();
+    let wallet = app.wallet();
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
-        .bind(&wallet)
+        .bind(&wallet.address)
         .execute(&db.pool)
         .await
         .expect("Failed to insert user");
 
     let payload = json!({
         "subject": "Help with my account",
-        "message": "I can't access my account. Please assist!",
-        "opened_by": wallet
+        "message": "I can't access my account. Please assist!"
     });
-    let req = Request::post("/open_ticket")
+    let req = app
+        .signed(&wallet, Request::post("/open_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -40,18 +41,19 @@ async fn open_ticket_happy_path() {
 async fn open_ticket_subject_too_short() {
     let app = TestApp::new().await;
     let db = &app.db;
-    let wallet = generate_address();
+    let wallet = app.wallet();
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
-        .bind(&wallet)
+        .bind(&wallet.address)
         .execute(&db.pool)
         .await
         .expect("Failed to insert user");
     let payload = json!({
         "subject": "Hi",
-        "message": "This is a valid message with enough length.",
-        "opened_by": wallet
+        "message": "This is a valid message with enough length."
     });
-    let req = Request::post("/open_ticket")
+    let req = app
+        .signed(&wallet, Request::post("/open_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -63,18 +65,19 @@ async fn open_ticket_subject_too_short() {
 async fn open_ticket_message_too_short() {
     let app = TestApp::new().await;
     let db = &app.db;
-    let wallet = generate_address();
+    let wallet = app.wallet();
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
-        .bind(&wallet)
+        .bind(&wallet.address)
         .execute(&db.pool)
         .await
         .expect("Failed to insert user");
     let payload = json!({
         "subject": "Valid Subject",
-        "message": "short",
-        "opened_by": wallet
+        "message": "short"
     });
-    let req = Request::post("/open_ticket")
+    let req = app
+        .signed(&wallet, Request::post("/open_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -83,30 +86,31 @@ async fn open_ticket_message_too_short() {
 }
 
 #[tokio::test]
-async fn open_ticket_empty_wallet() {
+async fn open_ticket_without_signature() {
     let app = TestApp::new().await;
     let payload = json!({
         "subject": "Valid Subject",
-        "message": "This is a valid message with enough length.",
-        "opened_by": ""
+        "message": "This is a valid message with enough length."
     });
     let req = Request::post("/open_ticket")
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
     let res = app.request(req).await;
-    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
 }
 
 #[tokio::test]
 async fn open_ticket_nonexistent_user() {
     let app = TestApp::new().await;
+    let wallet = app.wallet();
     let payload = json!({
         "subject": "Valid Subject",
-        "message": "This is a valid message with enough length.",
-        "opened_by": "0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        "message": "This is a valid message with enough length."
     });
-    let req = Request::post("/open_ticket")
+    let req = app
+        .signed(&wallet, Request::post("/open_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -118,19 +122,20 @@ async fn open_ticket_nonexistent_user() {
 async fn open_ticket_subject_max_length() {
     let app = TestApp::new().await;
     let db = &app.db;
-    let wallet = generate_address();
+    let wallet = app.wallet();
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
-        .bind(&wallet)
+        .bind(&wallet.address)
         .execute(&db.pool)
         .await
         .expect("Failed to insert user");
     let subject = "a".repeat(101);
     let payload = json!({
         "subject": subject,
-        "message": "This is a valid message with enough length.",
-        "opened_by": wallet
+        "message": "This is a valid message with enough length."
     });
-    let req = Request::post("/open_ticket")
+    let req = app
+        .signed(&wallet, Request::post("/open_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -142,19 +147,20 @@ async fn open_ticket_subject_max_length() {
 async fn open_ticket_subject_too_long() {
     let app = TestApp::new().await;
     let db = &app.db;
-    let wallet = generate_address();
+    let wallet = app.wallet();
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
-        .bind(&wallet)
+        .bind(&wallet.address)
         .execute(&db.pool)
         .await
         .expect("Failed to insert user");
     let subject = "a".repeat(101);
     let payload = json!({
         "subject": subject,
-        "message": "This is a valid message with enough length.",
-        "opened_by": wallet
+        "message": "This is a valid message with enough length."
     });
-    let req = Request::post("/open_ticket")
+    let req = app
+        .signed(&wallet, Request::post("/open_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -166,19 +172,20 @@ async fn open_ticket_subject_too_long() {
 async fn open_ticket_message_max_length() {
     let app = TestApp::new().await;
     let db = &app.db;
-    let wallet = generate_address();
+    let wallet = app.wallet();
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
-        .bind(&wallet)
+        .bind(&wallet.address)
         .execute(&db.pool)
         .await
         .expect("Failed to insert user");
     let message = "a".repeat(4999);
     let payload = json!({
         "subject": "Valid Subject",
-        "message": message,
-        "opened_by": wallet
+        "message": message
     });
-    let req = Request::post("/open_ticket")
+    let req = app
+        .signed(&wallet, Request::post("/open_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -190,19 +197,20 @@ async fn open_ticket_message_max_length() {
 async fn open_ticket_message_too_long() {
     let app = TestApp::new().await;
     let db = &app.db;
-    let wallet = generate_address();
+    let wallet = app.wallet();
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
-        .bind(&wallet)
+        .bind(&wallet.address)
         .execute(&db.pool)
         .await
         .expect("Failed to insert user");
     let message = "a".repeat(5001);
     let payload = json!({
         "subject": "Valid Subject",
-        "message": message,
-        "opened_by": wallet
+        "message": message
     });
-    let req = Request::post("/open_ticket")
+    let req = app
+        .signed(&wallet, Request::post("/open_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -214,18 +222,19 @@ async fn open_ticket_message_too_long() {
 async fn open_ticket_trimming() {
     let app = TestApp::new().await;
     let db = &app.db;
-    let wallet = generate_address();
+    let wallet = app.wallet();
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
-        .bind(&wallet)
+        .bind(&wallet.address)
         .execute(&db.pool)
         .await
         .expect("Failed to insert user");
     let payload = json!({
         "subject": "   Valid Subject   ",
-        "message": "   This is a valid message with enough length.   ",
-        "opened_by": wallet
+        "message": "   This is a valid message with enough length.   "
     });
-    let req = Request::post("/open_ticket")
+    let req = app
+        .signed(&wallet, Request::post("/open_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -240,37 +249,36 @@ async fn open_ticket_invalid_wallet_format() {
     // Not inserting user, and using an invalid wallet format
     let payload = json!({
         "subject": "Valid Subject",
-        "message": "This is a valid message with enough length.",
-        "opened_by": "not_a_wallet"
+        "message": "This is a valid message with enough length."
     });
     let req = Request::post("/open_ticket")
         .header("content-type", "application/json")
+        .header("x-wallet-address", "not_a_wallet")
+        .header("x-wallet-nonce", "0x1")
+        .header("x-wallet-signature", "0x1,0x2")
         .body(Body::from(payload.to_string()))
         .unwrap();
     let res = app.request(req).await;
-    // Should fail due to DB constraint
-    assert!(
-        res.status() == StatusCode::BAD_REQUEST
-            || res.status() == StatusCode::INTERNAL_SERVER_ERROR
-    );
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
 }
 
 #[tokio::test]
 async fn open_ticket_sql_injection() {
     let app = TestApp::new().await;
     let db = &app.db;
-    let wallet = generate_address();
+    let wallet = app.wallet();
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
-        .bind(&wallet)
+        .bind(&wallet.address)
         .execute(&db.pool)
         .await
         .expect("Failed to insert user");
     let payload = json!({
         "subject": "Valid Subject; DROP TABLE users; --",
-        "message": "This is a valid message; DROP TABLE request_ticket; --",
-        "opened_by": wallet
+        "message": "This is a valid message; DROP TABLE request_ticket; --"
     });
-    let req = Request::post("/open_ticket")
+    let req = app
+        .signed(&wallet, Request::post("/open_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -283,12 +291,9 @@ async fn assign_ticket_happy_path() {
     let app = TestApp::new().await;
     let db = &app.db;
     // Insert a support agent
-    let agent_wallet = generate_address();
-    sqlx::query("INSERT INTO escrow_users (wallet_address, type) VALUES ($1, 'support_agent');")
-        .bind(&agent_wallet)
-        .execute(&db.pool)
-        .await
-        .expect("Failed to insert support agent");
+    let agent = app.wallet();
+    let agent_wallet = agent.address.clone();
+    app.grant_role(&agent_wallet, "support_agent").await;
     // Insert a user
     let user_wallet = generate_address();
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
@@ -310,10 +315,11 @@ async fn assign_ticket_happy_path() {
 
     // Assign the ticket
     let payload = json!({
-        "ticket_id": ticket_id,
-        "support_agent_wallet": agent_wallet
+        "ticket_id": ticket_id
     });
-    let req = Request::post("/assign_ticket")
+    let req = app
+        .signed(&agent, Request::post("/assign_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -324,20 +330,17 @@ async fn assign_ticket_happy_path() {
 #[tokio::test]
 async fn assign_ticket_nonexistent_ticket() {
     let app = TestApp::new().await;
-    let db = &app.db;
     // Insert a support agent
-    let agent_wallet = generate_address();
-    sqlx::query("INSERT INTO escrow_users (wallet_address, type) VALUES ($1, 'support_agent');")
-        .bind(&agent_wallet)
-        .execute(&db.pool)
-        .await
-        .expect("Failed to insert support agent");
+    let agent = app.wallet();
+    let agent_wallet = agent.address.clone();
+    app.grant_role(&agent_wallet, "support_agent").await;
     // Try to assign a non-existent ticket
     let payload = json!({
-        "ticket_id": "99999999-9999-4999-9999-999999999999",
-        "support_agent_wallet": agent_wallet
+        "ticket_id": "99999999-9999-4999-9999-999999999999"
     });
-    let req = Request::post("/assign_ticket")
+    let req = app
+        .signed(&agent, Request::post("/assign_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -349,13 +352,10 @@ async fn assign_ticket_nonexistent_ticket() {
 async fn assign_ticket_already_assigned() {
     let app = TestApp::new().await;
     let db = &app.db;
-    let agent_wallet = generate_address();
+    let agent = app.wallet();
+    let agent_wallet = agent.address.clone();
     let user_wallet = generate_address();
-    sqlx::query("INSERT INTO escrow_users (wallet_address, type) VALUES ($1, 'support_agent');")
-        .bind(&agent_wallet)
-        .execute(&db.pool)
-        .await
-        .expect("Failed to insert support agent");
+    app.grant_role(&agent_wallet, "support_agent").await;
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
         .bind(&user_wallet)
         .execute(&db.pool)
@@ -375,10 +375,11 @@ async fn assign_ticket_already_assigned() {
     .expect("Failed to create ticket");
     // Try to assign again
     let payload = json!({
-        "ticket_id": ticket_id,
-        "support_agent_wallet": agent_wallet
+        "ticket_id": ticket_id
     });
-    let req = Request::post("/assign_ticket")
+    let req = app
+        .signed(&agent, Request::post("/assign_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -390,7 +391,8 @@ async fn assign_ticket_already_assigned() {
 async fn assign_ticket_non_agent() {
     let app = TestApp::new().await;
     let db = &app.db;
-    let not_agent_wallet = generate_address();
+    let not_agent = app.wallet();
+    let not_agent_wallet = not_agent.address.clone();
     let user_wallet = generate_address();
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
         .bind(&not_agent_wallet)
@@ -415,10 +417,11 @@ async fn assign_ticket_non_agent() {
     .expect("Failed to create ticket");
     // Try to assign to a non-agent
     let payload = json!({
-        "ticket_id": ticket_id,
-        "support_agent_wallet": not_agent_wallet
+        "ticket_id": ticket_id
     });
-    let req = Request::post("/assign_ticket")
+    let req = app
+        .signed(&not_agent, Request::post("/assign_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -430,14 +433,11 @@ async fn assign_ticket_non_agent() {
 async fn assign_ticket_agent_busy() {
     let app = TestApp::new().await;
     let db = &app.db;
-    let agent_wallet = generate_address();
+    let agent = app.wallet();
+    let agent_wallet = agent.address.clone();
     let user_wallet1 = generate_address();
     let user_wallet2 = generate_address();
-    sqlx::query("INSERT INTO escrow_users (wallet_address, type) VALUES ($1, 'support_agent');")
-        .bind(&agent_wallet)
-        .execute(&db.pool)
-        .await
-        .expect("Failed to insert support agent");
+    app.grant_role(&agent_wallet, "support_agent").await;
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
         .bind(&user_wallet1)
         .execute(&db.pool)
@@ -473,10 +473,11 @@ async fn assign_ticket_agent_busy() {
     .expect("Failed to create ticket 2");
     // Try to assign the busy agent to the second ticket
     let payload = json!({
-        "ticket_id": ticket_id2,
-        "support_agent_wallet": agent_wallet
+        "ticket_id": ticket_id2
     });
-    let req = Request::post("/assign_ticket")
+    let req = app
+        .signed(&agent, Request::post("/assign_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -506,35 +507,33 @@ async fn assign_ticket_nonexistent_agent() {
     .await
     .expect("Failed to create ticket");
     // Try to assign to a non-existent agent
-    let agent_wallet = generate_address();
+    let agent = app.wallet();
     let payload = json!({
-        "ticket_id": ticket_id,
-        "support_agent_wallet": agent_wallet
+        "ticket_id": ticket_id
     });
-    let req = Request::post("/assign_ticket")
+    let req = app
+        .signed(&agent, Request::post("/assign_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
     let res = app.request(req).await;
-    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
 }
 
 #[tokio::test]
 async fn assign_ticket_invalid_ticket_id() {
     let app = TestApp::new().await;
-    let db = &app.db;
-    let agent_wallet = generate_address();
-    sqlx::query("INSERT INTO escrow_users (wallet_address, type) VALUES ($1, 'support_agent');")
-        .bind(&agent_wallet)
-        .execute(&db.pool)
-        .await
-        .expect("Failed to insert support agent");
+    let agent = app.wallet();
+    let agent_wallet = agent.address.clone();
+    app.grant_role(&agent_wallet, "support_agent").await;
     // Try to assign with an invalid ticket id format
     let payload = json!({
-        "ticket_id": "not-a-uuid",
-        "support_agent_wallet": agent_wallet
+        "ticket_id": "not-a-uuid"
     });
-    let req = Request::post("/assign_ticket")
+    let req = app
+        .signed(&agent, Request::post("/assign_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -548,29 +547,28 @@ async fn resolve_ticket_happy_path() {
     let db = &app.db;
 
     // Insert a regular user who will open the ticket
-    let user_wallet = generate_address();
-    sqlx::query("INSERT INTO escrow_users (wallet_address, type) VALUES ($1, 'user') ON CONFLICT DO NOTHING")
+    let user = app.wallet();
+    let user_wallet = user.address.clone();
+    sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
         .bind(&user_wallet)
         .execute(&db.pool)
         .await
         .expect("Failed to insert user");
 
     // Insert an admin who will resolve the ticket
-    let admin_wallet = generate_address();
+    let admin = app.wallet();
+    let admin_wallet = admin.address.clone();
 
-    sqlx::query("INSERT INTO escrow_users (wallet_address, type) VALUES ($1, 'admin') ON CONFLICT DO NOTHING")
-        .bind(&admin_wallet)
-        .execute(&db.pool)
-        .await
-        .expect("Failed to insert admin");
+    app.grant_role(&admin_wallet, "admin").await;
 
     // Create a ticket first
     let ticket_payload = json!({
         "subject": "Help with my account",
-        "message": "I can't access my account. Please assist!",
-        "opened_by": user_wallet
+        "message": "I can't access my account. Please assist!"
     });
-    let req = Request::post("/open_ticket")
+    let req = app
+        .signed(&user, Request::post("/open_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(ticket_payload.to_string()))
         .unwrap();
@@ -588,11 +586,12 @@ async fn resolve_ticket_happy_path() {
     // Now resolve the ticket
     let resolve_payload = json!({
         "ticket_id": ticket_id.to_string(),
-        "resolution_response": "Your account has been successfully restored. Please try logging in again.",
-        "resolved_by": admin_wallet
+        "resolution_response": "Your account has been successfully restored. Please try logging in again."
     });
     tokio::time::sleep(Duration::from_millis(50)).await;
-    let req = Request::post("/resolve_ticket")
+    let req = app
+        .signed(&admin, Request::post("/resolve_ticket"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(resolve_payload.to_string()))
         .unwrap();
@@ -0,0 +1,205 @@
+use crate::helpers::{TestApp, TestWallet, generate_address};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, request::Builder},
+};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+async fn create_project(app: &TestApp, owner: &TestWallet, tags: &[&str]) -> Uuid {
+    let contract_address = generate_address();
+    let payload = json!({
+        "contract_address": contract_address,
+        "name": "Vault Protocol",
+        "description": "Yield vaults for stablecoins.",
+        "contact_info": "security@vault.example",
+        "tags": tags,
+    });
+    let (status, _) = send(app, owner, Request::post("/create_project"), payload).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    sqlx::query_scalar("SELECT id FROM projects WHERE contract_address = $1")
+        .bind(&contract_address)
+        .fetch_one(&app.db.pool)
+        .await
+        .unwrap()
+}
+
+async fn send(
+    app: &TestApp,
+    wallet: &TestWallet,
+    req: Builder,
+    payload: Value,
+) -> (StatusCode, Value) {
+    let req = app
+        .signed(wallet, req)
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let res = app.request(req).await;
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+async fn get(app: &TestApp, uri: &str) -> Value {
+    let req = Request::get(uri).body(Body::empty()).unwrap();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+fn project_count(tags: &Value, name: &str) -> Option<i64> {
+    tags.as_array()
+        .unwrap()
+        .iter()
+        .find(|tag| tag["name"] == name)
+        .map(|tag| tag["project_count"].as_i64().unwrap())
+}
+
+#[tokio::test]
+async fn tags_match_regardless_of_case() {
+    let app = TestApp::new().await;
+    let owner = app.wallet();
+    let first = create_project(&app, &owner, &["DeFi", " defi "]).await;
+    let second = create_project(&app, &owner, &["defi", "NFT"]).await;
+
+    let project = get(&app, &format!("/projects/{first}")).await;
+    assert_eq!(project["tags"], json!(["DeFi"]));
+    let project = get(&app, &format!("/projects/{second}")).await;
+    assert_eq!(project["tags"], json!(["DeFi", "NFT"]));
+
+    let tags = get(&app, "/tags").await;
+    assert_eq!(project_count(&tags, "DeFi"), Some(2));
+    assert_eq!(project_count(&tags, "defi"), None);
+    assert_eq!(project_count(&tags, "NFT"), Some(1));
+
+    let listed = get(&app, "/projects?tags=DEFI,nft").await;
+    let ids: Vec<&str> = listed["projects"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|project| project["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, [second.to_string()]);
+
+    // Re-submitting the same tags in another case is not an edit.
+    let (status, _) = send(
+        &app,
+        &owner,
+        Request::patch(format!("/projects/{second}")),
+        json!({ "tags": ["nft", "DEFI"] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        get(&app, &format!("/projects/{second}/revisions")).await,
+        json!([])
+    );
+}
+
+#[tokio::test]
+async fn owner_adds_and_removes_project_tags() {
+    let app = TestApp::new().await;
+    let (owner, stranger) = (app.wallet(), app.wallet());
+    let project_id = create_project(&app, &owner, &["defi"]).await;
+    let tags_uri = format!("/projects/{project_id}/tags");
+
+    let (status, _) = send(
+        &app,
+        &stranger,
+        Request::post(&tags_uri),
+        json!({ "tags": ["bridges"] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, project) = send(
+        &app,
+        &owner,
+        Request::post(&tags_uri),
+        json!({ "tags": ["Bridges", "DEFI", "bridges"] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(project["tags"], json!(["Bridges", "defi"]));
+
+    let (status, project) = send(
+        &app,
+        &owner,
+        Request::delete(format!("{tags_uri}/BRIDGES")),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(project["tags"], json!(["defi"]));
+    let (status, _) = send(
+        &app,
+        &owner,
+        Request::delete(format!("{tags_uri}/bridges")),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let revisions = get(&app, &format!("/projects/{project_id}/revisions")).await;
+    assert_eq!(revisions.as_array().unwrap().len(), 2);
+    assert_eq!(
+        revisions[0]["changes"]["tags"],
+        json!({ "from": ["Bridges", "defi"], "to": ["defi"] })
+    );
+    assert_eq!(
+        revisions[1]["changes"]["tags"],
+        json!({ "from": ["defi"], "to": ["Bridges", "defi"] })
+    );
+
+    // The tag outlives its last project until an admin merges it away.
+    assert_eq!(project_count(&get(&app, "/tags").await, "Bridges"), Some(0));
+}
+
+#[tokio::test]
+async fn admin_merges_tags() {
+    let app = TestApp::new().await;
+    let (owner, admin) = (app.wallet(), app.wallet());
+    app.grant_role(&admin.address, "admin").await;
+    let first = create_project(&app, &owner, &["defi", "decentralized-finance"]).await;
+    let second = create_project(&app, &owner, &["defi-protocols", "nft"]).await;
+    let merge = json!({
+        "source_tags": ["Decentralized-Finance", "defi-protocols", "defi"],
+        "target_tag": "DeFi",
+    });
+
+    let (status, _) = send(
+        &app,
+        &owner,
+        Request::post("/admin/tags/merge"),
+        merge.clone(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, body) = send(
+        &app,
+        &admin,
+        Request::post("/admin/tags/merge"),
+        json!({ "source_tags": ["defi", "lending"], "target_tag": "DeFi" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body["errors"]["source_tags"].is_array());
+
+    let (status, merged) = send(&app, &admin, Request::post("/admin/tags/merge"), merge).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(merged, json!({ "name": "DeFi", "project_count": 2 }));
+
+    let project = get(&app, &format!("/projects/{first}")).await;
+    assert_eq!(project["tags"], json!(["DeFi"]));
+    let project = get(&app, &format!("/projects/{second}")).await;
+    assert_eq!(project["tags"], json!(["DeFi", "nft"]));
+
+    let tags = get(&app, "/tags").await;
+    assert_eq!(project_count(&tags, "DeFi"), Some(2));
+    assert_eq!(project_count(&tags, "defi-protocols"), None);
+    assert_eq!(project_count(&tags, "decentralized-finance"), None);
+}
@@ -9,16 +9,17 @@ use serde_json::json;
 #[tokio::test]
 async fn test_deposit_successful_with_no_escrow_users() {
     let app = TestApp::new().await;
-    let wallet = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefcacdefabca";
+    let signer = app.wallet();
     let tx_hash = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefcacdefabc";
     let payload = json!({
-        "wallet_address": wallet,
         "amount": 10000000,
         "currency": "USDT",
         "notes": "Test project funding",
         "transaction_hash": tx_hash
     });
-    let request = Request::post("/deposit")
+    let request = app
+        .signed(&signer, Request::post("/deposit"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -31,7 +32,8 @@ async fn test_deposit_successful_with_escrow_users_available() {
     let app = TestApp::new().await;
     let db = &app.db;
     // Create escrow account
-    let wallet = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabca";
+    let signer = app.wallet();
+    let wallet = signer.address.as_str();
     let tx_hash = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabc";
     sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
         .bind(wallet)
@@ -40,13 +42,14 @@ async fn test_deposit_successful_with_escrow_users_available() {
         .expect("Failed to create escrow account");
 
     let payload = json!({
-        "wallet_address": wallet,
         "amount": 10000000,
         "currency": "USDT",
         "notes": "Test project funding",
         "transaction_hash": tx_hash
     });
-    let request = Request::post("/deposit")
+    let request = app
+        .signed(&signer, Request::post("/deposit"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -65,33 +68,21 @@ async fn test_withdraw_successful() {
     let app = TestApp::new().await;
     let db = &app.db;
 
-    let wallet = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcd";
+    let signer = app.wallet();
+    let wallet = signer.address.as_str();
     let initial_balance = BigDecimal::from(20000000);
-    sqlx::query!(
-        r#"
-        INSERT INTO escrow_users (wallet_address, balance)
-        VALUES ($1, $2)
-        ON CONFLICT (wallet_address) DO UPDATE
-        SET balance = EXCLUDED.balance
-        "#,
-        wallet,
-        initial_balance
-    )
-    .execute(&db.pool)
-    .await
-    .expect("Failed to create escrow account");
+    app.fund(wallet, "USDT", initial_balance.clone()).await;
 
-    let tx_hash = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabc";
     let withdrawal_amount = BigDecimal::from(10000000);
     let payload = json!({
-        "wallet_address": wallet,
         "amount": withdrawal_amount.to_string(),
         "currency": "USDT",
-        "notes": "Project withdrawal",
-        "transaction_hash": tx_hash
+        "notes": "Project withdrawal"
     });
 
-    let request = Request::post("/withdraw")
+    let request = app
+        .signed(&signer, Request::post("/withdraw"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -99,68 +90,56 @@ async fn test_withdraw_successful() {
     let res = app.request(request).await;
     assert_eq!(res.status(), StatusCode::CREATED);
 
-    let updated_balance: BigDecimal = sqlx::query_scalar!(
-        "SELECT balance FROM escrow_users WHERE wallet_address = $1",
-        wallet
-    )
-    .fetch_one(&db.pool)
-    .await
-    .expect("Failed to fetch balance");
+    let updated_balance = app.balance(wallet, "USDT").await;
 
     assert_eq!(updated_balance, initial_balance - withdrawal_amount.clone());
 
     #[derive(Debug, sqlx::FromRow)]
     struct Transaction {
         amount: BigDecimal,
-        r#type: String,
+        status: String,
+        transaction_hash: Option<String>,
     }
 
     let transaction = sqlx::query_as!(
         Transaction,
         r#"
-        SELECT amount, type as "type!: String"
+        SELECT amount, status as "status!: String", transaction_hash
         FROM escrow_transactions
-        WHERE wallet_address = $1 AND transaction_hash = $2
+        WHERE wallet_address = $1 AND type = 'withdrawal'
         "#,
-        wallet,
-        tx_hash
+        wallet
     )
     .fetch_one(&db.pool)
     .await
     .expect("Failed to fetch transaction");
 
     assert_eq!(transaction.amount, withdrawal_amount);
-    assert_eq!(transaction.r#type, "withdrawal".to_string());
+    // Queued for the payout worker, which signs the transfer.
+    assert_eq!(transaction.status, "pending");
+    assert_eq!(transaction.transaction_hash, None);
 }
 
 #[tokio::test]
 async fn test_withdraw_insufficient_balance() {
     let app = TestApp::new().await;
-    let db = &app.db;
 
     // Setup: Create escrow user with minimal balance
-    let wallet = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabce";
+    let signer = app.wallet();
+    let wallet = signer.address.as_str();
     let initial_balance = BigDecimal::from(5000000);
-    sqlx::query!(
-        "INSERT INTO escrow_users (wallet_address, balance) VALUES ($1, $2)",
-        wallet,
-        initial_balance
-    )
-    .execute(&db.pool)
-    .await
-    .expect("Failed to create escrow account");
+    app.fund(wallet, "USDT", initial_balance.clone()).await;
 
-    let tx_hash = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabe";
     let withdrawal_amount = 10000000; // More than balance
     let payload = json!({
-        "wallet_address": wallet,
         "amount": withdrawal_amount,
         "currency": "USDT",
-        "notes": "Project withdrawal",
-        "transaction_hash": tx_hash
+        "notes": "Project withdrawal"
     });
 
-    let request = Request::post("/withdraw")
+    let request = app
+        .signed(&signer, Request::post("/withdraw"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -169,32 +148,23 @@ async fn test_withdraw_insufficient_balance() {
     assert_eq!(res.status(), StatusCode::BAD_REQUEST);
 
     // Verify balance wasn't changed
-    let current_balance = sqlx::query!(
-        "SELECT balance FROM escrow_users WHERE wallet_address = $1",
-        wallet
-    )
-    .fetch_one(&db.pool)
-    .await
-    .expect("Failed to fetch balance");
-
-    assert_eq!(current_balance.balance, initial_balance);
+    assert_eq!(app.balance(wallet, "USDT").await, initial_balance);
 }
 
 #[tokio::test]
 async fn test_withdraw_nonexistent_wallet() {
     let app = TestApp::new().await;
 
-    let wallet = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcf";
-    let tx_hash = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabf";
+    let signer = app.wallet();
     let payload = json!({
-        "wallet_address": wallet,
         "amount": 10000000,
         "currency": "USDT",
-        "notes": "Project withdrawal",
-        "transaction_hash": tx_hash
+        "notes": "Project withdrawal"
     });
 
-    let request = Request::post("/withdraw")
+    let request = app
+        .signed(&signer, Request::post("/withdraw"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -207,17 +177,16 @@ async fn test_withdraw_nonexistent_wallet() {
 async fn test_withdraw_invalid_amount() {
     let app = TestApp::new().await;
 
-    let wallet = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabca";
-    let tx_hash = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabc";
+    let signer = app.wallet();
     let payload = json!({
-        "wallet_address": wallet,
         "amount": 0,  // Invalid amount
         "currency": "USDT",
-        "notes": "Project withdrawal",
-        "transaction_hash": tx_hash
+        "notes": "Project withdrawal"
     });
 
-    let request = Request::post("/withdraw")
+    let request = app
+        .signed(&signer, Request::post("/withdraw"))
+        .await
         .header("content-type", "application/json")
         .body(Body::from(payload.to_string()))
         .unwrap();
@@ -225,3 +194,45 @@ async fn test_withdraw_invalid_amount() {
     let res = app.request(request).await;
     assert_eq!(res.status(), StatusCode::BAD_REQUEST);
 }
+
+#[tokio::test]
+async fn test_withdraw_other_currency_is_rejected() {
+    let app = TestApp::new().await;
+    let signer = app.wallet();
+    let wallet = signer.address.as_str();
+
+    let deposit = json!({
+        "amount": "100",
+        "currency": "USDC",
+        "transaction_hash": "0x7bcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefab1"
+    });
+    let request = app
+        .signed(&signer, Request::post("/deposit"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(deposit.to_string()))
+        .unwrap();
+    assert_eq!(app.request(request).await.status(), StatusCode::CREATED);
+    app.deposit_on_chain(
+        wallet,
+        "0x7bcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefab1",
+        "USDC",
+        100,
+    )
+    .await;
+
+    let withdrawal = json!({
+        "amount": "100",
+        "currency": "STRK"
+    });
+    let request = app
+        .signed(&signer, Request::post("/withdraw"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(withdrawal.to_string()))
+        .unwrap();
+    assert_eq!(app.request(request).await.status(), StatusCode::BAD_REQUEST);
+
+    assert_eq!(app.balance(wallet, "USDC").await, BigDecimal::from(100));
+    assert_eq!(app.balance(wallet, "STRK").await, BigDecimal::from(0));
+}
@@ -0,0 +1,213 @@
+use crate::helpers::{TestApp, TestWallet, generate_address};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use serde_json::Value;
+
+async fn insert_transaction(
+    app: &TestApp,
+    wallet_address: &str,
+    transaction_type: &str,
+    amount: i64,
+    currency: &str,
+    created_at: DateTime<Utc>,
+) {
+    sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1) ON CONFLICT DO NOTHING")
+        .bind(wallet_address)
+        .execute(&app.db.pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO escrow_transactions (
+            wallet_address, type, amount, currency, transaction_hash, status, created_at
+        )
+        VALUES ($1, $2::transaction_type, $3, $4, $5, 'completed', $6)
+        "#,
+    )
+    .bind(wallet_address)
+    .bind(transaction_type)
+    .bind(BigDecimal::from(amount))
+    .bind(currency)
+    .bind(generate_address())
+    .bind(created_at)
+    .execute(&app.db.pool)
+    .await
+    .expect("Failed to insert transaction");
+}
+
+async fn list(app: &TestApp, wallet: &TestWallet, query: &str) -> (StatusCode, Value) {
+    let req = app
+        .signed(wallet, Request::get(format!("/transactions{query}")))
+        .await
+        .body(Body::empty())
+        .unwrap();
+    let res = app.request(req).await;
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+fn amount(value: &Value) -> BigDecimal {
+    value.as_str().unwrap().parse().unwrap()
+}
+
+/// 100 STRK in, 50 USDC in, 30 STRK out, one hour apart.
+async fn history(app: &TestApp, wallet: &str) -> DateTime<Utc> {
+    let start = Utc::now() - Duration::days(1);
+    insert_transaction(app, wallet, "deposit", 100, "STRK", start).await;
+    insert_transaction(
+        app,
+        wallet,
+        "deposit",
+        50,
+        "USDC",
+        start + Duration::hours(1),
+    )
+    .await;
+    insert_transaction(
+        app,
+        wallet,
+        "withdrawal",
+        30,
+        "STRK",
+        start + Duration::hours(2),
+    )
+    .await;
+    start
+}
+
+#[tokio::test]
+async fn lists_own_transactions_with_running_balance() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    history(&app, &wallet.address).await;
+    insert_transaction(
+        &app,
+        &generate_address(),
+        "deposit",
+        999,
+        "STRK",
+        Utc::now(),
+    )
+    .await;
+
+    let (status, page) = list(&app, &wallet, "").await;
+    assert_eq!(status, StatusCode::OK);
+    let transactions = page["transactions"].as_array().unwrap();
+    assert_eq!(transactions.len(), 3);
+    assert_eq!(page["next_cursor"], Value::Null);
+
+    assert_eq!(transactions[0]["transaction_type"], "withdrawal");
+    assert_eq!(
+        amount(&transactions[0]["running_balance"]),
+        BigDecimal::from(70)
+    );
+    assert_eq!(transactions[1]["currency"], "USDC");
+    assert_eq!(
+        amount(&transactions[1]["running_balance"]),
+        BigDecimal::from(50)
+    );
+    assert_eq!(transactions[2]["transaction_type"], "deposit");
+    assert_eq!(
+        amount(&transactions[2]["running_balance"]),
+        BigDecimal::from(100)
+    );
+    assert!(
+        transactions
+            .iter()
+            .all(|transaction| transaction["wallet_address"] == wallet.address)
+    );
+}
+
+#[tokio::test]
+async fn filters_transactions() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    let start = history(&app, &wallet.address).await;
+
+    let (_, page) = list(&app, &wallet, "?transaction_type=withdrawal").await;
+    assert_eq!(page["transactions"].as_array().unwrap().len(), 1);
+    // Filtering does not change the running balance.
+    assert_eq!(
+        amount(&page["transactions"][0]["running_balance"]),
+        BigDecimal::from(70)
+    );
+
+    let (_, page) = list(&app, &wallet, "?currency=USDC&transaction_status=completed").await;
+    assert_eq!(page["transactions"].as_array().unwrap().len(), 1);
+    assert_eq!(page["transactions"][0]["currency"], "USDC");
+
+    let query = format!(
+        "?from={}&to={}",
+        (start + Duration::minutes(30)).to_rfc3339_opts(SecondsFormat::Secs, true),
+        (start + Duration::minutes(90)).to_rfc3339_opts(SecondsFormat::Secs, true),
+    );
+    let (status, page) = list(&app, &wallet, &query).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["transactions"].as_array().unwrap().len(), 1);
+    assert_eq!(page["transactions"][0]["currency"], "USDC");
+
+    let (status, _) = list(&app, &wallet, "?transaction_type=refund").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn paginates_with_cursor() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    let created_at = Utc::now() - Duration::hours(1);
+    // Identical timestamps must still paginate deterministically.
+    for _ in 0..5 {
+        insert_transaction(&app, &wallet.address, "deposit", 10, "STRK", created_at).await;
+    }
+
+    let mut seen = Vec::new();
+    let mut query = "?limit=2".to_string();
+    let mut pages = 0;
+    loop {
+        let (status, page) = list(&app, &wallet, &query).await;
+        assert_eq!(status, StatusCode::OK);
+        pages += 1;
+        for transaction in page["transactions"].as_array().unwrap() {
+            seen.push(transaction["id"].as_str().unwrap().to_string());
+        }
+        match page["next_cursor"].as_str() {
+            Some(cursor) => query = format!("?limit=2&cursor={cursor}"),
+            None => break,
+        }
+    }
+
+    assert_eq!(pages, 3);
+    assert_eq!(seen.len(), 5);
+    seen.sort();
+    seen.dedup();
+    assert_eq!(seen.len(), 5);
+
+    let (status, _) = list(&app, &wallet, "?cursor=not-a-cursor").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn only_admins_see_other_wallets() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    let admin = app.wallet();
+    app.grant_role(&admin.address, "admin").await;
+    let other = generate_address();
+    history(&app, &wallet.address).await;
+    insert_transaction(&app, &other, "deposit", 5, "USDT", Utc::now()).await;
+
+    let (status, _) = list(&app, &wallet, &format!("?wallet_address={other}")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (_, page) = list(&app, &admin, "").await;
+    assert_eq!(page["transactions"].as_array().unwrap().len(), 4);
+
+    let (_, page) = list(&app, &admin, &format!("?wallet_address={other}")).await;
+    assert_eq!(page["transactions"].as_array().unwrap().len(), 1);
+    assert_eq!(page["transactions"][0]["wallet_address"], other);
+}
@@ -0,0 +1,230 @@
+use crate::helpers::{TestApp, TestWallet};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use bigdecimal::BigDecimal;
+use fortichain_server::{chain::ChainTransactionStatus, jobs::SettlementSummary};
+use serde_json::{Value, json};
+use starknet::core::types::Felt;
+
+async fn post(app: &TestApp, wallet: &TestWallet, uri: &str, payload: Value) -> StatusCode {
+    let req = app
+        .signed(wallet, Request::post(uri))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    app.request(req).await.status()
+}
+
+async fn status_of(app: &TestApp, transaction_hash: &str) -> String {
+    sqlx::query_scalar("SELECT status::text FROM escrow_transactions WHERE transaction_hash = $1")
+        .bind(transaction_hash)
+        .fetch_one(&app.db.pool)
+        .await
+        .unwrap()
+}
+
+async fn ledger_is_balanced(app: &TestApp) -> bool {
+    let admin = app.wallet();
+    app.grant_role(&admin.address, "admin").await;
+    let req = app
+        .signed(&admin, Request::get("/admin/ledger/check"))
+        .await
+        .body(Body::empty())
+        .unwrap();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let check: Value = serde_json::from_slice(&body).unwrap();
+    check["balanced"] == true
+}
+
+/// Hash of the latest payout sent to `wallet`.
+async fn payout_hash(app: &TestApp, wallet: &TestWallet) -> String {
+    sqlx::query_scalar(
+        "SELECT transaction_hash FROM escrow_transactions \
+         WHERE wallet_address = $1 AND type = 'withdrawal' AND transaction_hash IS NOT NULL \
+         ORDER BY created_at DESC LIMIT 1",
+    )
+    .bind(&wallet.address)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap()
+}
+
+fn tx_hash(n: u8) -> String {
+    format!("0x{}{n}", "7d".repeat(31))
+}
+
+#[tokio::test]
+async fn deposit_is_credited_only_from_the_escrow_event() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+
+    let deposit = json!({ "amount": "100", "currency": "STRK", "transaction_hash": tx_hash(1) });
+    assert_eq!(
+        post(&app, &wallet, "/deposit", deposit).await,
+        StatusCode::CREATED
+    );
+    assert_eq!(status_of(&app, &tx_hash(1)).await, "pending");
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(0)
+    );
+
+    // A final transaction proves nothing about what it moved, so the confirmation job leaves the
+    // claimed amount alone.
+    assert_eq!(app.confirm(&tx_hash(1)).await, SettlementSummary::default());
+    assert_eq!(status_of(&app, &tx_hash(1)).await, "pending");
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(0)
+    );
+
+    // The escrow contract's event credits what it actually received.
+    let summary = app
+        .deposit_on_chain(&wallet.address, &tx_hash(1), "STRK", 40)
+        .await;
+    assert_eq!(summary.deposits, 1);
+    // The watcher books the hash in its full 64 digit form.
+    let booked_hash = Felt::from_hex(&tx_hash(1)).unwrap().to_fixed_hex_string();
+    assert_eq!(status_of(&app, &booked_hash).await, "completed");
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(40)
+    );
+
+    // Settling again leaves the completed deposit alone.
+    assert_eq!(app.settle().await, SettlementSummary::default());
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(40)
+    );
+    assert!(ledger_is_balanced(&app).await);
+}
+
+#[tokio::test]
+async fn failed_deposit_is_never_credited() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+
+    let deposit = json!({ "amount": "100", "currency": "STRK", "transaction_hash": tx_hash(1) });
+    assert_eq!(
+        post(&app, &wallet, "/deposit", deposit).await,
+        StatusCode::CREATED
+    );
+
+    // A failed transaction emits no `Deposit` event, so the claim is never credited.
+    app.chain.set(&tx_hash(1), ChainTransactionStatus::Failed);
+    assert_eq!(app.settle().await, SettlementSummary::default());
+    app.confirm(&tx_hash(1)).await;
+    assert_eq!(status_of(&app, &tx_hash(1)).await, "pending");
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(0)
+    );
+    assert!(ledger_is_balanced(&app).await);
+}
+
+#[tokio::test]
+async fn unsettled_deposit_claims_do_not_hold_up_withdrawals() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+    app.fund(&wallet.address, "STRK", BigDecimal::from(100))
+        .await;
+    let withdrawal = json!({ "amount": "70", "currency": "STRK" });
+    assert_eq!(
+        post(&app, &wallet, "/withdraw", withdrawal).await,
+        StatusCode::CREATED
+    );
+    assert_eq!(app.pay_out().await.submitted, 1);
+    let payout = payout_hash(&app, &wallet).await;
+
+    // More claims than a run looks at, all older than the withdrawal and none ever settling.
+    let claimant = app.wallet();
+    for n in 0..150 {
+        let deposit = json!({
+            "amount": "1",
+            "currency": "STRK",
+            "transaction_hash": format!("0x{}{n:03}", "7c".repeat(30)),
+        });
+        assert_eq!(
+            post(&app, &claimant, "/deposit", deposit).await,
+            StatusCode::CREATED
+        );
+    }
+    sqlx::query(
+        "UPDATE escrow_transactions SET created_at = NOW() - INTERVAL '1 day' \
+         WHERE wallet_address = $1",
+    )
+    .bind(&claimant.address)
+    .execute(&app.db.pool)
+    .await
+    .unwrap();
+
+    app.chain.set(&payout, ChainTransactionStatus::Failed);
+    assert_eq!(app.settle().await.failed, 1);
+    assert_eq!(status_of(&app, &payout).await, "failed");
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(100)
+    );
+}
+
+#[tokio::test]
+async fn pending_withdrawal_reserves_funds_until_it_settles() {
+    let app = TestApp::new().await;
+    let wallet = app.wallet();
+
+    let deposit = json!({ "amount": "100", "currency": "STRK", "transaction_hash": tx_hash(1) });
+    assert_eq!(
+        post(&app, &wallet, "/deposit", deposit).await,
+        StatusCode::CREATED
+    );
+    app.deposit_on_chain(&wallet.address, &tx_hash(1), "STRK", 100)
+        .await;
+
+    let withdrawal = json!({ "amount": "70", "currency": "STRK" });
+    assert_eq!(
+        post(&app, &wallet, "/withdraw", withdrawal.clone()).await,
+        StatusCode::CREATED
+    );
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(30)
+    );
+
+    // The reserved funds cannot be withdrawn a second time.
+    assert_eq!(
+        post(&app, &wallet, "/withdraw", withdrawal.clone()).await,
+        StatusCode::BAD_REQUEST
+    );
+
+    assert_eq!(app.pay_out().await.submitted, 1);
+    let payout = payout_hash(&app, &wallet).await;
+    assert_eq!(status_of(&app, &payout).await, "pending");
+
+    app.chain.set(&payout, ChainTransactionStatus::Failed);
+    assert_eq!(app.settle().await.failed, 1);
+    assert_eq!(status_of(&app, &payout).await, "failed");
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(100)
+    );
+
+    assert_eq!(
+        post(&app, &wallet, "/withdraw", withdrawal).await,
+        StatusCode::CREATED
+    );
+    assert_eq!(app.pay_out().await.submitted, 1);
+    let payout = payout_hash(&app, &wallet).await;
+    assert_eq!(app.confirm(&payout).await.confirmed, 1);
+    assert_eq!(status_of(&app, &payout).await, "completed");
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(30)
+    );
+    assert!(ledger_is_balanced(&app).await);
+}
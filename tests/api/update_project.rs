@@ -0,0 +1,237 @@
+use crate::helpers::{TestApp, TestWallet, generate_address};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+async fn create_project(app: &TestApp, owner: &TestWallet) -> Uuid {
+    let contract_address = generate_address();
+    let payload = json!({
+        "contract_address": contract_address,
+        "name": "Vault Protocol",
+        "description": "Yield vaults for stablecoins.",
+        "contact_info": "security@vault.example",
+        "repository_url": "https://github.com/vault/contracts",
+        "tags": ["defi", "vaults"],
+    });
+    let req = app
+        .signed(owner, Request::post("/create_project"))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    assert_eq!(app.request(req).await.status(), StatusCode::CREATED);
+
+    sqlx::query_scalar("SELECT id FROM projects WHERE contract_address = $1")
+        .bind(&contract_address)
+        .fetch_one(&app.db.pool)
+        .await
+        .unwrap()
+}
+
+async fn patch(
+    app: &TestApp,
+    wallet: &TestWallet,
+    project_id: Uuid,
+    payload: Value,
+) -> (StatusCode, Value) {
+    let req = app
+        .signed(wallet, Request::patch(format!("/projects/{project_id}")))
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let res = app.request(req).await;
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+async fn revisions(app: &TestApp, project_id: Uuid) -> Value {
+    let req = Request::get(format!("/projects/{project_id}/revisions"))
+        .body(Body::empty())
+        .unwrap();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn owner_edits_are_recorded_as_revisions() {
+    let app = TestApp::new().await;
+    let owner = app.wallet();
+    let project_id = create_project(&app, &owner).await;
+
+    let (status, body) = patch(
+        &app,
+        &owner,
+        project_id,
+        json!({
+            "description": "Yield vaults for stablecoins and LSTs.",
+            "project_logo_path": "https://vault.example/logo.png",
+            "tags": ["vaults", "lst", "defi"],
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["description"],
+        "Yield vaults for stablecoins and LSTs."
+    );
+    assert_eq!(body["tags"], json!(["defi", "lst", "vaults"]));
+
+    // Unchanged values do not make a revision.
+    let (status, _) = patch(
+        &app,
+        &owner,
+        project_id,
+        json!({ "name": "Vault Protocol", "tags": ["defi", "lst", "vaults"] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = patch(
+        &app,
+        &owner,
+        project_id,
+        json!({ "contact_info": "https://vault.example/security" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let revisions = revisions(&app, project_id).await;
+    assert_eq!(revisions.as_array().unwrap().len(), 2);
+    assert_eq!(revisions[0]["revision"], 2);
+    assert_eq!(
+        revisions[0]["changes"],
+        json!({
+            "contact_info": {
+                "from": "security@vault.example",
+                "to": "https://vault.example/security",
+            }
+        })
+    );
+    let first = &revisions[1];
+    assert_eq!(first["revision"], 1);
+    assert_eq!(first["changed_by"], owner.address);
+    assert_eq!(
+        first["changes"]["tags"],
+        json!({ "from": ["defi", "vaults"], "to": ["defi", "lst", "vaults"] })
+    );
+    assert_eq!(
+        first["changes"]["project_logo_path"],
+        json!({ "from": null, "to": "https://vault.example/logo.png" })
+    );
+    assert!(first["changes"]["description"].is_object());
+    assert!(first["changes"].get("name").is_none());
+}
+
+#[tokio::test]
+async fn only_the_owner_can_edit() {
+    let app = TestApp::new().await;
+    let owner = app.wallet();
+    let project_id = create_project(&app, &owner).await;
+
+    let (status, _) = patch(
+        &app,
+        &app.wallet(),
+        project_id,
+        json!({ "description": "Taken over by someone else." }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = patch(
+        &app,
+        &owner,
+        Uuid::now_v7(),
+        json!({ "description": "No such project exists." }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(
+        revisions(&app, project_id)
+            .await
+            .as_array()
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn edits_are_validated_like_creation() {
+    let app = TestApp::new().await;
+    let owner = app.wallet();
+    let project_id = create_project(&app, &owner).await;
+
+    for payload in [
+        json!({ "description": "short" }),
+        json!({ "contact_info": "not a contact" }),
+        json!({ "project_logo_path": "logo.png" }),
+        json!({ "tags": [] }),
+        json!({ "name": "ab" }),
+    ] {
+        let (status, _) = patch(&app, &owner, project_id, payload.clone()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{payload}");
+    }
+}
+
+#[tokio::test]
+async fn immutable_fields_cannot_change() {
+    let app = TestApp::new().await;
+    let owner = app.wallet();
+    let project_id = create_project(&app, &owner).await;
+
+    let (status, body) = patch(
+        &app,
+        &owner,
+        project_id,
+        json!({ "contract_address": generate_address() }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body["errors"]["contract_address"].is_array());
+
+    // The repository can move until the project is verified.
+    let (status, _) = patch(
+        &app,
+        &owner,
+        project_id,
+        json!({ "repository_url": "https://github.com/vault/contracts-v2" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    sqlx::query("UPDATE projects SET is_verified = true, verification_date = NOW() WHERE id = $1")
+        .bind(project_id)
+        .execute(&app.db.pool)
+        .await
+        .unwrap();
+    let (status, body) = patch(
+        &app,
+        &owner,
+        project_id,
+        json!({ "repository_url": "https://github.com/attacker/contracts" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body["errors"]["repository_url"].is_array());
+
+    sqlx::query("UPDATE projects SET closed_at = NOW() WHERE id = $1")
+        .bind(project_id)
+        .execute(&app.db.pool)
+        .await
+        .unwrap();
+    let (status, body) = patch(
+        &app,
+        &owner,
+        project_id,
+        json!({ "description": "Editing after the project closed." }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "project_closed");
+}
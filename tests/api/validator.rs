@@ -1,6 +1,5 @@
-use crate::helpers::{TestApp, generate_address};
+use crate::helpers::TestApp;
 use axum::{body::Body, extract::Request, http::StatusCode};
-use serde_json::json;
 use uuid::Uuid;
 
 #[tokio::test]
@@ -9,7 +8,8 @@ async fn test_delete_validator_profile_success() {
     let db = &app.db;
 
     // Create a test validator profile
-    let wallet_address = generate_address();
+    let validator = app.wallet();
+    let wallet_address = validator.address.clone();
     let validator_id = Uuid::now_v7();
 
     // Insert validator profile
@@ -64,22 +64,16 @@ async fn test_delete_validator_profile_success() {
         .expect("Failed to insert validator expertise");
 
     // Add validator to escrow_users
-    sqlx::query("INSERT INTO escrow_users (wallet_address, balance) VALUES ($1, 0.0)")
+    sqlx::query("INSERT INTO escrow_users (wallet_address) VALUES ($1)")
         .bind(&wallet_address)
         .execute(&db.pool)
         .await
         .expect("Failed to insert escrow user");
 
-    // Prepare delete request
-    let payload = json!({
-        "wallet_address": wallet_address
-    });
-
-    let req = Request::builder()
-        .method("DELETE")
-        .uri("/validator/profile/delete")
-        .header("content-type", "application/json")
-        .body(Body::from(payload.to_string()))
+    let req = app
+        .signed(&validator, Request::delete("/validator/profile/delete"))
+        .await
+        .body(Body::empty())
         .unwrap();
 
     let res = app.request(req).await;
@@ -133,17 +127,15 @@ async fn test_delete_validator_profile_success() {
 #[tokio::test]
 async fn test_delete_validator_profile_not_found() {
     let app = TestApp::new().await;
-    let non_existent_address = generate_address();
-
-    let payload = json!({
-        "wallet_address": non_existent_address
-    });
+    let non_existent_validator = app.wallet();
 
-    let req = Request::builder()
-        .method("DELETE")
-        .uri("/validator/profile/delete")
-        .header("content-type", "application/json")
-        .body(Body::from(payload.to_string()))
+    let req = app
+        .signed(
+            &non_existent_validator,
+            Request::delete("/validator/profile/delete"),
+        )
+        .await
+        .body(Body::empty())
         .unwrap();
 
     let res = app.request(req).await;
@@ -153,43 +145,17 @@ async fn test_delete_validator_profile_not_found() {
 }
 
 #[tokio::test]
-async fn test_delete_validator_profile_invalid_address() {
-    let app = TestApp::new().await;
-
-    let payload = json!({
-        "wallet_address": "invalid_address"
-    });
-
-    let req = Request::builder()
-        .method("DELETE")
-        .uri("/validator/profile/delete")
-        .header("content-type", "application/json")
-        .body(Body::from(payload.to_string()))
-        .unwrap();
-
-    let res = app.request(req).await;
-    let status = res.status();
-
-    assert_eq!(status, StatusCode::BAD_REQUEST);
-}
-
-#[tokio::test]
-async fn test_delete_validator_profile_missing_wallet_address() {
+async fn test_delete_validator_profile_without_signature() {
     let app = TestApp::new().await;
 
-    let payload = json!({
-        // Missing wallet_address field
-    });
-
     let req = Request::builder()
         .method("DELETE")
         .uri("/validator/profile/delete")
-        .header("content-type", "application/json")
-        .body(Body::from(payload.to_string()))
+        .body(Body::empty())
         .unwrap();
 
     let res = app.request(req).await;
     let status = res.status();
 
-    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY); // 422 for missing required field
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
 }
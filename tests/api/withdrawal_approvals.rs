@@ -0,0 +1,224 @@
+use crate::helpers::{TestApp, TestWallet};
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, request::Builder},
+};
+use bigdecimal::BigDecimal;
+use serde_json::{Value, json};
+
+/// An app holding STRK withdrawals above 1000 for two admins' approval.
+async fn app() -> TestApp {
+    TestApp::with_configuration(|cfg| {
+        cfg.withdrawal_approval_thresholds = [("STRK".to_string(), BigDecimal::from(1000))].into();
+        cfg.withdrawal_approvals_required = 2;
+    })
+    .await
+}
+
+async fn send(
+    app: &TestApp,
+    wallet: &TestWallet,
+    req: Builder,
+    payload: Value,
+) -> (StatusCode, Value) {
+    let req = app
+        .signed(wallet, req)
+        .await
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let res = app.request(req).await;
+    let status = res.status();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+async fn admin(app: &TestApp) -> TestWallet {
+    let admin = app.wallet();
+    app.grant_role(&admin.address, "admin").await;
+    admin
+}
+
+async fn withdraw(app: &TestApp, wallet: &TestWallet, amount: &str) -> StatusCode {
+    let withdrawal = json!({ "amount": amount, "currency": "STRK" });
+    send(app, wallet, Request::post("/withdraw"), withdrawal)
+        .await
+        .0
+}
+
+/// The held withdrawals waiting for approval.
+async fn pending(app: &TestApp, admin: &TestWallet) -> Vec<Value> {
+    let (status, body) = send(app, admin, Request::get("/admin/withdrawals"), Value::Null).await;
+    assert_eq!(status, StatusCode::OK);
+    body.as_array().unwrap().clone()
+}
+
+async fn decide(
+    app: &TestApp,
+    admin: &TestWallet,
+    id: &Value,
+    decision: &str,
+) -> (StatusCode, Value) {
+    let uri = format!("/admin/withdrawals/{}/{decision}", id.as_str().unwrap());
+    let payload = json!({ "reason": "Destination wallet is flagged" });
+    send(app, admin, Request::post(uri), payload).await
+}
+
+async fn transaction_status(app: &TestApp, wallet: &TestWallet) -> String {
+    sqlx::query_scalar(
+        "SELECT status::text FROM escrow_transactions WHERE wallet_address = $1 AND type = 'withdrawal'",
+    )
+    .bind(&wallet.address)
+    .fetch_one(&app.db.pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn withdrawal_up_to_the_threshold_is_not_held() {
+    let app = app().await;
+    let wallet = app.wallet();
+    app.fund(&wallet.address, "STRK", BigDecimal::from(5000))
+        .await;
+
+    assert_eq!(withdraw(&app, &wallet, "1000").await, StatusCode::CREATED);
+    assert!(pending(&app, &admin(&app).await).await.is_empty());
+    assert_eq!(app.pay_out().await.submitted, 1);
+}
+
+#[tokio::test]
+async fn withdrawal_above_the_threshold_waits_for_two_admins() {
+    let app = app().await;
+    let wallet = app.wallet();
+    app.fund(&wallet.address, "STRK", BigDecimal::from(5000))
+        .await;
+    let (first, second) = (admin(&app).await, admin(&app).await);
+
+    assert_eq!(withdraw(&app, &wallet, "3000").await, StatusCode::ACCEPTED);
+    // Held funds cannot be spent elsewhere.
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(2000)
+    );
+    assert_eq!(
+        withdraw(&app, &wallet, "2500").await,
+        StatusCode::BAD_REQUEST
+    );
+
+    let held = pending(&app, &first).await;
+    assert_eq!(held.len(), 1);
+    let id = held[0]["id"].clone();
+    assert_eq!(held[0]["wallet_address"], wallet.address);
+    assert_eq!(held[0]["approvals_required"], 2);
+    assert_eq!(app.pay_out().await.submitted, 0);
+
+    let (status, body) = decide(&app, &first, &id, "approve").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "pending");
+    assert_eq!(body["approvals"][0]["admin_address"], first.address);
+    assert!(body["approvals"][0]["approved_at"].is_string());
+
+    // The same admin counts once.
+    let (status, body) = decide(&app, &first, &id, "approve").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "withdrawal_already_approved");
+    assert_eq!(app.pay_out().await.submitted, 0);
+
+    let (status, body) = decide(&app, &second, &id, "approve").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "approved");
+    assert_eq!(body["approvals"].as_array().unwrap().len(), 2);
+    assert!(body["decided_at"].is_string());
+    assert!(pending(&app, &first).await.is_empty());
+
+    assert_eq!(app.pay_out().await.submitted, 1);
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(2000)
+    );
+}
+
+#[tokio::test]
+async fn any_admin_can_reject_a_held_withdrawal() {
+    let app = app().await;
+    let wallet = app.wallet();
+    app.fund(&wallet.address, "STRK", BigDecimal::from(5000))
+        .await;
+    let (first, second) = (admin(&app).await, admin(&app).await);
+
+    assert_eq!(withdraw(&app, &wallet, "3000").await, StatusCode::ACCEPTED);
+    let id = pending(&app, &first).await[0]["id"].clone();
+    assert_eq!(decide(&app, &first, &id, "approve").await.0, StatusCode::OK);
+
+    let (status, body) = decide(&app, &second, &id, "reject").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "rejected");
+    assert_eq!(body["rejected_by"], second.address);
+    assert_eq!(body["rejection_reason"], "Destination wallet is flagged");
+
+    assert_eq!(transaction_status(&app, &wallet).await, "failed");
+    assert_eq!(
+        app.balance(&wallet.address, "STRK").await,
+        BigDecimal::from(5000)
+    );
+
+    let (status, body) = decide(&app, &first, &id, "approve").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["reason"], "withdrawal_not_pending");
+    assert_eq!(app.pay_out().await.submitted, 0);
+
+    let (status, body) = send(
+        &app,
+        &first,
+        Request::get("/admin/withdrawals?status=rejected"),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body[0]["id"], id);
+}
+
+#[tokio::test]
+async fn admin_cannot_approve_their_own_withdrawal() {
+    let app = app().await;
+    let requester = admin(&app).await;
+    app.fund(&requester.address, "STRK", BigDecimal::from(5000))
+        .await;
+
+    assert_eq!(
+        withdraw(&app, &requester, "3000").await,
+        StatusCode::ACCEPTED
+    );
+    let id = pending(&app, &requester).await[0]["id"].clone();
+    assert_eq!(
+        decide(&app, &requester, &id, "approve").await.0,
+        StatusCode::FORBIDDEN
+    );
+}
+
+#[tokio::test]
+async fn only_admins_decide_on_withdrawals() {
+    let app = app().await;
+    let wallet = app.wallet();
+    app.fund(&wallet.address, "STRK", BigDecimal::from(5000))
+        .await;
+    assert_eq!(withdraw(&app, &wallet, "3000").await, StatusCode::ACCEPTED);
+    let id = pending(&app, &admin(&app).await).await[0]["id"].clone();
+
+    let outsider = app.wallet();
+    let (status, _) = send(
+        &app,
+        &outsider,
+        Request::get("/admin/withdrawals"),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    for decision in ["approve", "reject"] {
+        assert_eq!(
+            decide(&app, &outsider, &id, decision).await.0,
+            StatusCode::FORBIDDEN
+        );
+    }
+    assert_eq!(transaction_status(&app, &wallet).await, "pending");
+}